@@ -18,7 +18,7 @@
 ///
 /// // Cycle through AI types
 /// let next_ai = ai_type.next();
-/// assert_eq!(next_ai, AIType::MinMax);
+/// assert_eq!(next_ai, AIType::QLearning);
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub enum AIType {
@@ -28,6 +28,8 @@ pub enum AIType {
     MinMax,
     /// Q-Learning algorithm - reinforcement learning approach
     QLearning,
+    /// Monte Carlo Tree Search - UCT with simulated playouts
+    Mcts,
 }
 
 impl std::fmt::Display for AIType {
@@ -42,12 +44,57 @@ impl std::fmt::Display for AIType {
     /// assert_eq!(format!("{}", AIType::AlphaBeta), "Alpha-Beta");
     /// assert_eq!(format!("{}", AIType::MinMax), "Min-Max");
     /// assert_eq!(format!("{}", AIType::QLearning), "Q-Learning");
+    /// assert_eq!(format!("{}", AIType::Mcts), "MCTS");
     /// ```
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AIType::AlphaBeta => write!(f, "Alpha-Beta"),
             AIType::MinMax => write!(f, "Min-Max"),
             AIType::QLearning => write!(f, "Q-Learning"),
+            AIType::Mcts => write!(f, "MCTS"),
+        }
+    }
+}
+
+impl AIType {
+    /// Returns the next AI type in the cycle.
+    ///
+    /// This method allows cycling through the available AI algorithms in a
+    /// predefined order, useful for the setup screens that let users switch
+    /// between AI implementations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(AIType::AlphaBeta.next(), AIType::QLearning);
+    /// assert_eq!(AIType::Mcts.next(), AIType::AlphaBeta);
+    /// ```
+    pub fn next(&self) -> AIType {
+        match self {
+            AIType::AlphaBeta => AIType::QLearning,
+            AIType::QLearning => AIType::MinMax,
+            AIType::MinMax => AIType::Mcts,
+            AIType::Mcts => AIType::AlphaBeta,
+        }
+    }
+
+    /// Returns the previous AI type in the cycle.
+    ///
+    /// This method allows cycling backwards through the available AI
+    /// algorithms, mirroring [`AIType::next`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(AIType::AlphaBeta.previous(), AIType::Mcts);
+    /// assert_eq!(AIType::QLearning.previous(), AIType::AlphaBeta);
+    /// ```
+    pub fn previous(&self) -> AIType {
+        match self {
+            AIType::AlphaBeta => AIType::Mcts,
+            AIType::QLearning => AIType::AlphaBeta,
+            AIType::MinMax => AIType::QLearning,
+            AIType::Mcts => AIType::MinMax,
         }
     }
 }
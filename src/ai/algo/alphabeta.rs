@@ -4,12 +4,19 @@
 //! of the minimax algorithm that reduces the number of nodes evaluated in
 //! the search tree by maintaining lower (alpha) and upper (beta) bounds.
 
-use std::thread;
+use std::time::Instant;
 
 use crate::{
     ai::{
-        action::Action, ai_type::AIType, heuristic::HeuristicType,
+        action::Action,
+        ai_type::AIType,
+        analysis::{Analysis, AnalyzedMove},
+        cancel::{CancelToken, SEARCH_CANCELLED},
+        heuristic::HeuristicType,
         heuristic_matrix::AIHeuristicMatrix,
+        opening_book::{play_book_move, BookMode},
+        search_info::SearchCounters,
+        worker_pool::{default_threads, WorkerPool},
     },
     game::{board::Board, cell::Cell, history_action::HistoryAction, player::Player},
 };
@@ -49,6 +56,12 @@ pub struct AIAlphaBeta {
     color: Cell,
     /// Matrix used by the heuristic function for position evaluation
     matrix: AIHeuristicMatrix,
+    /// How the opening book is used before searching
+    book_mode: BookMode,
+    /// Number of workers sharing the root moves
+    threads: usize,
+    /// Token that stops the running search
+    cancel: CancelToken,
 }
 
 impl AIAlphaBeta {
@@ -87,6 +100,9 @@ impl AIAlphaBeta {
             heuristic,
             color,
             matrix,
+            book_mode: BookMode::Best,
+            threads: default_threads(),
+            cancel: CancelToken::new(),
         }
     }
 
@@ -135,8 +151,8 @@ impl AIAlphaBeta {
     /// Performs one step of the alpha-beta pruning algorithm.
     ///
     /// This is the core recursive function that implements the alpha-beta pruning
-    /// algorithm. It alternates between maximizing and minimizing players based
-    /// on the current depth (odd depths are minimizing, even depths are maximizing).
+    /// algorithm. It maximizes the score when this AI is to move and minimizes it
+    /// when the opponent is to move, so passes are handled naturally.
     ///
     /// The algorithm maintains alpha (best value for maximizing player) and beta
     /// (best value for minimizing player) bounds, pruning branches when alpha >= beta.
@@ -154,61 +170,101 @@ impl AIAlphaBeta {
     ///
     /// # Algorithm Details
     ///
-    /// - Base case: When depth reaches 1 or no legal moves exist, return heuristic evaluation
+    /// - Base case: When depth reaches 1 or the game is over, return heuristic evaluation
     /// - Recursive case: Try all legal moves and recursively evaluate resulting positions
     /// - Pruning: When alpha >= beta, prune the remaining branches (they won't affect the result)
-    /// - Alternating players: Odd depths minimize, even depths maximize
+    /// - Alternating players: The AI's own moves maximize, the opponent's moves minimize
     pub fn tree_step(&self, board: &Board, depth: usize, alpha: &isize, beta: &isize) -> isize {
-        let mut alpha_mut = alpha.clone();
-        let mut beta_mut = beta.clone();
+        self.search_line(
+            board,
+            depth,
+            *alpha,
+            *beta,
+            1,
+            &mut SearchCounters::default(),
+        )
+        .0
+    }
+
+    /// Searches a position and returns its score with the best line found.
+    ///
+    /// Same algorithm as `tree_step`, but it also counts the visited nodes
+    /// and keeps the principal variation.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - Current board state to evaluate
+    /// * `depth` - Remaining search depth
+    /// * `alpha` - Lower bound for the maximizing player
+    /// * `beta` - Upper bound for the minimizing player
+    /// * `ply` - Distance from the root of the search
+    /// * `counters` - Node and depth counters of the current thread
+    ///
+    /// # Returns
+    ///
+    /// The score of the position and the moves of the best line from it.
+    fn search_line(
+        &self,
+        board: &Board,
+        depth: usize,
+        mut alpha: isize,
+        mut beta: isize,
+        ply: usize,
+        counters: &mut SearchCounters,
+    ) -> (isize, Vec<(usize, usize)>) {
+        // A cancelled search unwinds at once, its result is thrown away
+        if self.cancel.is_cancelled() {
+            return (0, Vec::new());
+        }
+        counters.visit(ply);
 
-        if depth == 1 || board.has_legal_moves(board.get_player_turn()) == None {
+        let player = board.get_player_turn();
+        let moves = match board.has_legal_moves(player) {
+            Some(moves) if depth > 1 && !board.is_game_over() => moves,
             // Base case: evaluate the current position using the heuristic function
-            let score = self
-                .heuristic
-                .evaluate(board, self.get_color(), self.matrix.clone());
-            return score;
-        } else {
-            // Recursive case: try all legal moves and find the best one
-            for case in board.has_legal_moves(board.get_player_turn()).unwrap() {
-                let mut new_board = board.clone();
-                match new_board.try_play_move(case.0, case.1, board.get_player_turn()) {
-                    Ok(_) => {
-                        let score = self.tree_step(&new_board, depth - 1, &alpha_mut, &beta_mut);
-                        if depth % 2 == 1 {
-                            // Minimizing player (odd depth)
-                            if score < beta_mut {
-                                beta_mut = score
-                            }
-                            if alpha_mut >= beta_mut {
-                                // Alpha-beta pruning: remaining branches won't improve the result
-                                return beta_mut;
-                            }
-                        } else {
-                            // Maximizing player (even depth)
-                            if score > alpha_mut {
-                                alpha_mut = score
-                            }
-                            if alpha_mut >= beta_mut {
-                                // Alpha-beta pruning: remaining branches won't improve the result
-                                return alpha_mut;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        println!("Error: {}", e);
-                    }
-                }
+            _ => {
+                let score = self
+                    .heuristic
+                    .evaluate(board, self.get_color(), self.matrix.clone());
+                return (score, Vec::new());
             }
-            // Return the best value found for the current player
-            if depth % 2 == 1 {
-                // Minimizing player returns beta
-                return beta_mut;
+        };
+
+        let maximizing = player == self.get_color();
+        let mut best_line = Vec::new();
+        for case in moves {
+            let mut new_board = board.clone();
+            if let Err(e) = new_board.play_and_advance(case.0, case.1) {
+                println!("Error: {}", e);
+                continue;
+            }
+            let (score, line) =
+                self.search_line(&new_board, depth - 1, alpha, beta, ply + 1, counters);
+            let improved = if maximizing {
+                score > alpha
             } else {
-                // Maximizing player returns alpha
-                return alpha_mut;
+                score < beta
+            };
+            if improved || best_line.is_empty() {
+                best_line = [vec![case], line].concat();
+            }
+            if maximizing && score > alpha {
+                alpha = score;
+            } else if !maximizing && score < beta {
+                beta = score;
+            }
+            if alpha >= beta {
+                // Alpha-beta pruning: remaining branches won't improve the result
+                break;
             }
         }
+
+        // Return the best value found for the current player
+        if maximizing {
+            (alpha, best_line)
+        } else {
+            (beta, best_line)
+        }
     }
 }
 
@@ -291,6 +347,31 @@ impl Player for AIAlphaBeta {
         self.depth = depth;
     }
 
+    /// Returns how the opening book is used before searching.
+    fn get_book_mode(&self) -> Option<BookMode> {
+        Some(self.book_mode)
+    }
+
+    /// Updates how the opening book is used before searching.
+    fn set_book_mode(&mut self, mode: BookMode) {
+        self.book_mode = mode;
+    }
+
+    /// Returns the number of workers sharing the root moves.
+    fn get_threads(&self) -> Option<usize> {
+        Some(self.threads)
+    }
+
+    /// Updates the number of workers sharing the root moves.
+    fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Gives the search the token that stops it.
+    fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = cancel;
+    }
+
     /// Calculates and executes the best move for the current board position.
     ///
     /// This method implements the main AI logic, using multithreading to
@@ -314,77 +395,100 @@ impl Player for AIAlphaBeta {
     /// - No legal moves are available
     /// - Move evaluation fails due to invalid board state
     /// - Thread join errors during parallel evaluation
+    /// - The search is cancelled through its `CancelToken`
     /// - Move execution fails on the board
     ///
     /// # Performance
     ///
     /// This method uses multithreading to evaluate moves in parallel,
     /// which can significantly improve performance on multi-core systems.
-    /// The number of threads created equals the number of legal moves available.
+    /// The moves are shared by a pool of at most `threads` workers, however
+    /// many legal moves are available.
+    ///
+    /// # Search Statistics
+    ///
+    /// The returned action carries a `SearchInfo` with the nodes visited by
+    /// all threads, the time taken, the deepest ply and the principal variation.
     fn play_turn(
         &self,
         board: &mut Board,
         _cell: Option<(usize, usize)>,
     ) -> Result<HistoryAction, String> {
-        // Initialize the best action with minimum score
-        let mut best_action = Action {
-            pos: (0, 0),
-            score: isize::MIN,
-        };
-        let mut handles = vec![];
+        // Play from the opening book while the position is in book
+        if let Some(action) = play_book_move(board, self.book_mode, self.get_color()) {
+            return action;
+        }
 
-        // Evaluate all legal moves in parallel using threads
-        for case in board.has_legal_moves(board.get_player_turn()).unwrap() {
-            let mut new_board = board.clone();
+        self.analyze(board)?.play_best(board, self.get_color())
+    }
 
-            match new_board.try_play_move(case.0, case.1, self.get_color()) {
-                Ok(_) => {
-                    // Clone the AI for the thread and spawn evaluation task
-                    let ai_cloned = self.clone();
-                    let handle = thread::spawn(move || {
-                        (case, ai_cloned.init_tree(&new_board, ai_cloned.depth))
-                    });
-                    handles.push(handle);
-                }
+    /// Scores every legal move with a full alpha-beta search.
+    ///
+    /// Each root move is searched with the whole window, so the scores of the
+    /// moves are exact and can be compared to each other. The root moves are
+    /// jobs of a `WorkerPool` of `threads` workers.
+    /// `play_turn` plays the first move of this ranking.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to analyze
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Analysis)` - Every legal move ranked by score, with the search statistics
+    /// * `Err(String)` - If a move cannot be played, a thread panics or the search is cancelled
+    fn analyze(&self, board: &Board) -> Result<Analysis, String> {
+        let start = Instant::now();
+        // Search from the point of view of the player to move
+        let mut ai = self.clone();
+        ai.color = board.get_player_turn();
+
+        let mut children = vec![];
+        for case in board.has_legal_moves(ai.color).unwrap_or_default() {
+            let mut new_board = board.clone();
+            match new_board.play_and_advance(case.0, case.1) {
+                Ok(_) => children.push((case, new_board)),
                 Err(e) => return Err(format!("Error: {}", e)),
             }
         }
 
-        // Collect results from all threads and find the best move
-        for handle in handles {
-            match handle.join() {
-                Ok((pos, score)) => {
-                    if score > best_action.score {
-                        best_action = Action { pos, score };
-                    }
-                }
-                Err(_) => {
-                    return Err("Thread join error".to_string());
-                }
-            }
+        // Evaluate all legal moves in parallel on the worker pool
+        let results = WorkerPool::new(ai.threads).map(children, |(case, new_board)| {
+            let mut counters = SearchCounters::default();
+            let (score, line) = ai.search_line(
+                &new_board,
+                ai.depth,
+                isize::MIN,
+                isize::MAX,
+                1,
+                &mut counters,
+            );
+            (case, score, line, counters)
+        })?;
+        if ai.cancel.is_cancelled() {
+            return Err(SEARCH_CANCELLED.to_string());
         }
 
-        // Execute the best move on the board and return the action history
-        match board.try_play_move(best_action.pos.0, best_action.pos.1, self.get_color()) {
-            Ok(gained_discs) => Ok(HistoryAction {
-                coordinates: Some(Board::coordinates_to_input(
-                    best_action.pos.0,
-                    best_action.pos.1,
-                )),
-                gained_discs: Some(gained_discs),
-                color: self.get_color(),
-                player_turn: board.get_player_turn(),
-                move_number: board.get_turn_number(),
-            }),
-            Err(e) => Err(format!("Error playing move: {}", e)),
+        // Collect the score of every move
+        let mut counters = SearchCounters::default();
+        let mut moves = vec![];
+        for (pos, score, line, thread_counters) in results {
+            counters.merge(thread_counters);
+            moves.push(AnalyzedMove {
+                action: Action { pos, score },
+                line: [vec![pos], line].concat(),
+            });
         }
+
+        Analysis::rank_by_score(&mut moves);
+        Ok(Analysis::new(moves, counters, start.elapsed()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ai::heuristic::HeuristicType;
+    use crate::ai::{algo::minmax::AIMinMax, heuristic::HeuristicType};
 
     /// Helper function to create a default AIAlphaBeta instance for testing
     fn create_test_ai() -> AIAlphaBeta {
@@ -482,4 +586,109 @@ mod tests {
         assert!(!ai.is_human());
         assert_eq!(ai.get_ai_type(), Some(AIType::AlphaBeta));
     }
+
+    #[test]
+    fn test_tree_step_matches_minmax() {
+        let alphabeta =
+            AIAlphaBeta::new(4, HeuristicType::Mixte, Cell::White, AIHeuristicMatrix::A);
+        let minmax = AIMinMax::new(
+            4,
+            HeuristicType::Mixte,
+            Cell::White,
+            AIHeuristicMatrix::A,
+            false,
+        );
+
+        // Alpha-beta pruning must not change the score of any reply to the first move
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+        for case in board.has_legal_moves(Cell::White).unwrap() {
+            let mut child = board.clone();
+            child.play_and_advance(case.0, case.1).unwrap();
+            let mut counters = SearchCounters::default();
+            let (score, _) = minmax.tree_step(&child, 4, 1, &mut counters);
+            assert_eq!(alphabeta.init_tree(&child, 4), score);
+        }
+    }
+
+    #[test]
+    fn test_search_matches_minmax() {
+        let ai = create_test_ai();
+        let minmax = AIMinMax::new(
+            4,
+            HeuristicType::Absolute,
+            Cell::Black,
+            AIHeuristicMatrix::A,
+            false,
+        );
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+
+        // Pruning must not change the score of a position
+        let mut counters = SearchCounters::default();
+        let (score, line) = minmax.tree_step(&board, 4, 1, &mut counters);
+        assert_eq!(ai.init_tree(&board, 4), score);
+        assert_eq!(line.len(), 3);
+        assert!(counters.nodes > 0);
+    }
+
+    #[test]
+    fn test_play_turn_reports_search_info() {
+        let mut ai = create_test_ai();
+        ai.set_book_mode(BookMode::Off);
+        let mut board = Board::new();
+        let action = ai.play_turn(&mut board, None).unwrap();
+        let info = action.search_info.unwrap();
+        assert!(info.nodes > 0);
+        assert_eq!(info.depth, 4);
+        assert_eq!(info.pv.len(), 4);
+        assert_eq!(Some(info.pv[0].clone()), action.coordinates);
+    }
+
+    #[test]
+    fn test_analyze_ranks_every_legal_move() {
+        let ai = create_test_ai();
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+
+        // White is to move: the black AI analyzes for White
+        let analysis = ai.analyze(&board).unwrap();
+        assert_eq!(analysis.moves.len(), 3);
+        assert!(analysis
+            .moves
+            .windows(2)
+            .all(|pair| pair[0].action.score >= pair[1].action.score));
+
+        let mut white = ai.clone();
+        white.color = Cell::White;
+        white.set_book_mode(BookMode::Off);
+        let action = white.play_turn(&mut board, None).unwrap();
+        let best = analysis.best().unwrap().action.pos;
+        assert_eq!(
+            action.coordinates,
+            Some(Board::coordinates_to_input(best.0, best.1))
+        );
+    }
+
+    #[test]
+    fn test_cancel_interrupts_deep_search() {
+        let mut ai = create_test_ai();
+        ai.set_depth(30);
+        ai.set_book_mode(BookMode::Off);
+        let token = CancelToken::new();
+        ai.set_cancel_token(token.clone());
+
+        let start = std::time::Instant::now();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            token.cancel();
+        });
+        let mut board = Board::new();
+        let result = ai.play_turn(&mut board, None);
+        canceller.join().unwrap();
+
+        assert_eq!(result.err(), Some(SEARCH_CANCELLED.to_string()));
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        assert_eq!(board.get_nb_discs(Cell::Black), Ok(2));
+    }
 }
@@ -0,0 +1,763 @@
+//! Monte Carlo Tree Search AI implementation for Othello game.
+//!
+//! This module implements Monte Carlo Tree Search with the UCT selection rule
+//! (Upper Confidence bounds applied to Trees). Instead of evaluating positions
+//! with a heuristic at a fixed depth, the AI grows a search tree by playing
+//! many simulated games (playouts) to the end and keeps win statistics for
+//! every explored move.
+
+use std::{
+    cmp::Reverse,
+    fmt::Display,
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    ai::{
+        action::Action,
+        ai_type::AIType,
+        analysis::{Analysis, AnalyzedMove},
+        cancel::{CancelToken, SEARCH_CANCELLED},
+        heuristic::HeuristicType,
+        heuristic_matrix::AIHeuristicMatrix,
+        opening_book::{play_book_move, BookMode},
+        search_info::SearchCounters,
+        worker_pool::default_threads,
+    },
+    consts::{MCTS_EXPLORATION, MCTS_ITERATIONS, MCTS_PLAYOUT_GREEDINESS, MCTS_TIME_MS},
+    game::{board::Board, cell::Cell, history_action::HistoryAction, player::Player},
+};
+
+/// Budget that bounds the work done by one MCTS search.
+///
+/// The budget is shared between all search threads: an iteration budget is
+/// split evenly between them, while a time budget applies to each of them.
+///
+/// # Examples
+///
+/// ```rust
+/// let budget = SearchBudget::Iterations(10000);
+/// let budget = SearchBudget::Time(Duration::from_millis(500));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchBudget {
+    /// Run a fixed number of playouts (reproducible when seeded)
+    Iterations(usize),
+    /// Run playouts until the given duration has elapsed
+    Time(Duration),
+}
+
+impl SearchBudget {
+    /// Returns the name of the budget kind, as shown on the setup screens.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SearchBudget::Iterations(_) => "Iterations",
+            SearchBudget::Time(_) => "Time",
+        }
+    }
+
+    /// Returns a budget of the other kind, with its default amount.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     SearchBudget::Iterations(100).other_kind(),
+    ///     SearchBudget::Time(Duration::from_millis(MCTS_TIME_MS))
+    /// );
+    /// ```
+    pub fn other_kind(&self) -> SearchBudget {
+        match self {
+            SearchBudget::Iterations(_) => SearchBudget::Time(Duration::from_millis(MCTS_TIME_MS)),
+            SearchBudget::Time(_) => SearchBudget::Iterations(MCTS_ITERATIONS),
+        }
+    }
+}
+
+/// Strategy used to pick moves during the simulation phase.
+///
+/// # Examples
+///
+/// ```rust
+/// let policy = PlayoutPolicy::Heuristic;
+/// assert_eq!(policy.next(), PlayoutPolicy::Random);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlayoutPolicy {
+    /// Uniformly random legal moves until the end of the game
+    Random,
+    /// Mostly greedy moves according to the one-ply heuristic evaluation
+    Heuristic,
+}
+
+impl Display for PlayoutPolicy {
+    /// Formats the playout policy for display purposes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(format!("{}", PlayoutPolicy::Random), "Random");
+    /// assert_eq!(format!("{}", PlayoutPolicy::Heuristic), "Heuristic");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayoutPolicy::Random => write!(f, "Random"),
+            PlayoutPolicy::Heuristic => write!(f, "Heuristic"),
+        }
+    }
+}
+
+impl PlayoutPolicy {
+    /// Returns the other playout policy.
+    ///
+    /// There are only two policies, so cycling forwards or backwards
+    /// toggles between them.
+    pub fn next(&self) -> PlayoutPolicy {
+        match self {
+            PlayoutPolicy::Random => PlayoutPolicy::Heuristic,
+            PlayoutPolicy::Heuristic => PlayoutPolicy::Random,
+        }
+    }
+}
+
+/// A node of the search tree, stored in a flat arena.
+///
+/// Statistics are kept from the point of view of `mover`, the player who
+/// played `pos` to reach this node, so the parent can directly compare its
+/// children by win rate.
+struct Node {
+    /// Move that leads from the parent to this node (None for the root)
+    pos: Option<(usize, usize)>,
+    /// Index of the parent node in the arena
+    parent: Option<usize>,
+    /// Indexes of the expanded children in the arena
+    children: Vec<usize>,
+    /// Legal moves that have not been expanded yet
+    untried: Vec<(usize, usize)>,
+    /// Number of playouts that went through this node
+    visits: u32,
+    /// Sum of playout rewards for `mover` (1 win, 0.5 draw, 0 loss)
+    wins: f64,
+    /// Player who made the move leading to this node
+    mover: Cell,
+}
+
+/// Aggregated statistics for one root move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootStat {
+    /// The board position (row, column) of the root move
+    pub pos: (usize, usize),
+    /// Number of playouts that started with this move
+    pub visits: u32,
+    /// Sum of playout rewards for the AI after this move
+    pub wins: f64,
+}
+
+/// An AI player that uses Monte Carlo Tree Search to choose its moves.
+///
+/// Each search runs the four classic MCTS phases many times:
+///
+/// 1. **Selection**: walk down the tree using the UCT formula
+/// 2. **Expansion**: add one untried move as a new node
+/// 3. **Simulation**: play the game to the end (random or heuristic-guided)
+/// 4. **Backpropagation**: update win statistics up to the root
+///
+/// The search is root-parallel: every thread grows its own tree from the
+/// same position and the root statistics are merged before picking the most
+/// visited move. With a seed and an iteration budget, the chosen move is
+/// fully reproducible for a given number of threads.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut ai = AIMcts::new(
+///     SearchBudget::Iterations(2000),
+///     HeuristicType::Matrix,
+///     Cell::White,
+///     AIHeuristicMatrix::A,
+/// )
+/// .with_seed(Some(42));
+/// ai.set_threads(4);
+///
+/// assert_eq!(ai.get_color(), Cell::White);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AIMcts {
+    /// Amount of work allowed for each search
+    budget: SearchBudget,
+    /// Exploration constant C of the UCT formula
+    exploration: f64,
+    /// Seed for reproducible searches (None uses OS entropy)
+    seed: Option<u64>,
+    /// Number of threads growing independent trees
+    threads: usize,
+    /// Move selection strategy during simulations
+    playout: PlayoutPolicy,
+    /// Heuristic function used by heuristic-guided playouts
+    heuristic: HeuristicType,
+    /// Matrix used by the heuristic function
+    matrix: AIHeuristicMatrix,
+    /// The color (Black or White) that this AI player represents
+    color: Cell,
+    /// How the opening book is used before searching
+    book_mode: BookMode,
+    /// Token that stops the running search
+    cancel: CancelToken,
+}
+
+impl AIMcts {
+    /// Creates a new MCTS AI player with default search settings.
+    ///
+    /// The exploration constant defaults to `MCTS_EXPLORATION`, playouts are
+    /// random, searches are unseeded and use every available CPU core. Use
+    /// the `with_*` methods to change these settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - Iteration or time budget for each search
+    /// * `heuristic` - Heuristic function used by guided playouts
+    /// * `color` - The color (Black or White) that this AI will play as
+    /// * `matrix` - Heuristic matrix used for position evaluation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let ai = AIMcts::new(
+    ///     SearchBudget::Time(Duration::from_secs(1)),
+    ///     HeuristicType::Absolute,
+    ///     Cell::Black,
+    ///     AIHeuristicMatrix::A,
+    /// );
+    /// ```
+    pub fn new(
+        budget: SearchBudget,
+        heuristic: HeuristicType,
+        color: Cell,
+        matrix: AIHeuristicMatrix,
+    ) -> Self {
+        Self {
+            budget,
+            exploration: MCTS_EXPLORATION,
+            seed: None,
+            threads: default_threads(),
+            playout: PlayoutPolicy::Random,
+            heuristic,
+            matrix,
+            color,
+            book_mode: BookMode::Best,
+            cancel: CancelToken::new(),
+        }
+    }
+
+    /// Sets the exploration constant C of the UCT formula.
+    pub fn with_exploration(mut self, exploration: f64) -> Self {
+        self.exploration = exploration;
+        self
+    }
+
+    /// Sets the seed used by the search threads (None for OS entropy).
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the move selection strategy used during simulations.
+    pub fn with_playout(mut self, playout: PlayoutPolicy) -> Self {
+        self.playout = playout;
+        self
+    }
+
+    /// Returns the color (Black or White) that this AI player represents.
+    pub fn get_color(&self) -> Cell {
+        self.color
+    }
+
+    /// Runs a full search from `board` and returns the merged root statistics.
+    ///
+    /// Every thread grows its own tree with its own random generator (derived
+    /// from the seed and the thread index), then the statistics of identical
+    /// root moves are summed. The result follows the order of
+    /// `Board::has_legal_moves`, which keeps it deterministic.
+    /// The threads stop early once the `CancelToken` is cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to search, with the AI to move
+    ///
+    /// # Returns
+    ///
+    /// One `RootStat` per legal move, or an empty vector if the player to
+    /// move has no legal move.
+    pub fn search(&self, board: &Board) -> Vec<RootStat> {
+        let moves = match board.has_legal_moves(board.get_player_turn()) {
+            Some(moves) => moves,
+            None => return Vec::new(),
+        };
+        let mut stats: Vec<RootStat> = moves
+            .iter()
+            .map(|pos| RootStat {
+                pos: *pos,
+                visits: 0,
+                wins: 0.0,
+            })
+            .collect();
+
+        let results: Vec<Vec<RootStat>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.threads)
+                .map(|index| {
+                    let budget = match self.budget {
+                        SearchBudget::Iterations(total) => {
+                            // Split the iterations, the first threads take the remainder
+                            let share =
+                                total / self.threads + usize::from(index < total % self.threads);
+                            SearchBudget::Iterations(share)
+                        }
+                        SearchBudget::Time(duration) => SearchBudget::Time(duration),
+                    };
+                    let mut rng = match self.seed {
+                        Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(index as u64)),
+                        None => StdRng::from_os_rng(),
+                    };
+                    scope.spawn(move || self.grow_tree(board, budget, &mut rng))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().ok())
+                .collect()
+        });
+
+        for result in results {
+            for child in result {
+                if let Some(stat) = stats.iter_mut().find(|stat| stat.pos == child.pos) {
+                    stat.visits += child.visits;
+                    stat.wins += child.wins;
+                }
+            }
+        }
+        stats
+    }
+
+    /// Grows one search tree from `root` within `budget`.
+    ///
+    /// # Returns
+    ///
+    /// The statistics of the expanded root children.
+    fn grow_tree(&self, root: &Board, budget: SearchBudget, rng: &mut StdRng) -> Vec<RootStat> {
+        let start = Instant::now();
+        let mut nodes = vec![Node {
+            pos: None,
+            parent: None,
+            children: Vec::new(),
+            untried: root
+                .has_legal_moves(root.get_player_turn())
+                .unwrap_or_default(),
+            visits: 0,
+            wins: 0.0,
+            mover: root.get_player_turn().get_opponent(),
+        }];
+
+        let mut iteration = 0;
+        loop {
+            match budget {
+                SearchBudget::Iterations(max) if iteration >= max => break,
+                SearchBudget::Time(duration) if start.elapsed() >= duration => break,
+                _ if self.cancel.is_cancelled() => break,
+                _ => {}
+            }
+            iteration += 1;
+
+            let mut board = root.clone();
+            let mut current = 0;
+
+            // Selection: follow UCT while the node is fully expanded
+            while nodes[current].untried.is_empty() && !nodes[current].children.is_empty() {
+                current = self.select_child(&nodes, current);
+                let pos = nodes[current].pos.unwrap();
+                let _ = board.play_and_advance(pos.0, pos.1);
+            }
+
+            // Expansion: add one random untried move
+            if !nodes[current].untried.is_empty() {
+                let index = rng.random_range(0..nodes[current].untried.len());
+                let pos = nodes[current].untried.swap_remove(index);
+                let mover = board.get_player_turn();
+                let _ = board.play_and_advance(pos.0, pos.1);
+                let untried = if board.is_game_over() {
+                    Vec::new()
+                } else {
+                    board
+                        .has_legal_moves(board.get_player_turn())
+                        .unwrap_or_default()
+                };
+                nodes.push(Node {
+                    pos: Some(pos),
+                    parent: Some(current),
+                    children: Vec::new(),
+                    untried,
+                    visits: 0,
+                    wins: 0.0,
+                    mover,
+                });
+                let child = nodes.len() - 1;
+                nodes[current].children.push(child);
+                current = child;
+            }
+
+            // Simulation: play the game to the end
+            self.playout(&mut board, rng);
+            let winner = board.get_winner();
+
+            // Backpropagation: credit every node from the mover's point of view
+            let mut node = Some(current);
+            while let Some(index) = node {
+                nodes[index].visits += 1;
+                nodes[index].wins += match winner {
+                    Some(color) if color == nodes[index].mover => 1.0,
+                    Some(_) => 0.0,
+                    None => 0.5,
+                };
+                node = nodes[index].parent;
+            }
+        }
+
+        nodes[0]
+            .children
+            .iter()
+            .map(|&child| RootStat {
+                pos: nodes[child].pos.unwrap(),
+                visits: nodes[child].visits,
+                wins: nodes[child].wins,
+            })
+            .collect()
+    }
+
+    /// Returns the child of `parent` with the highest UCT score.
+    fn select_child(&self, nodes: &[Node], parent: usize) -> usize {
+        let log_visits = (nodes[parent].visits.max(1) as f64).ln();
+        let mut best_child = nodes[parent].children[0];
+        let mut best_score = f64::MIN;
+        for &child in &nodes[parent].children {
+            let node = &nodes[child];
+            let visits = node.visits.max(1) as f64;
+            let score = node.wins / visits + self.exploration * (log_visits / visits).sqrt();
+            if score > best_score {
+                best_score = score;
+                best_child = child;
+            }
+        }
+        best_child
+    }
+
+    /// Plays the game on `board` until it is over, following the playout policy.
+    fn playout(&self, board: &mut Board, rng: &mut StdRng) {
+        while !board.is_game_over() {
+            let player = board.get_player_turn();
+            let moves = match board.has_legal_moves(player) {
+                Some(moves) => moves,
+                None => break,
+            };
+            let pos = if self.playout == PlayoutPolicy::Heuristic
+                && rng.random::<f64>() < MCTS_PLAYOUT_GREEDINESS
+            {
+                *moves
+                    .iter()
+                    .max_by_key(|pos| {
+                        let mut next = board.clone();
+                        let _ = next.try_play_move(pos.0, pos.1, player);
+                        self.heuristic.evaluate(&next, player, self.matrix.clone())
+                    })
+                    .unwrap()
+            } else {
+                moves[rng.random_range(0..moves.len())]
+            };
+            let _ = board.play_and_advance(pos.0, pos.1);
+        }
+    }
+}
+
+impl Player for AIMcts {
+    /// Returns false as this is an AI player, not a human player.
+    fn is_human(&self) -> bool {
+        false
+    }
+
+    /// Returns the AI type identifier for this player.
+    fn get_ai_type(&self) -> Option<AIType> {
+        Some(AIType::Mcts)
+    }
+
+    /// Returns a copy of the heuristic matrix used by guided playouts.
+    fn get_heuristic_matrix(&self) -> AIHeuristicMatrix {
+        self.matrix.clone()
+    }
+
+    /// Updates the heuristic matrix used by guided playouts.
+    fn set_heuristic_matrix(&mut self, matrix: AIHeuristicMatrix) {
+        self.matrix = matrix;
+    }
+
+    /// Returns the heuristic function used by guided playouts.
+    fn get_heuristic(&self) -> HeuristicType {
+        self.heuristic.clone()
+    }
+
+    /// Updates the heuristic function used by guided playouts.
+    fn set_heuristic(&mut self, heuristic: HeuristicType) {
+        self.heuristic = heuristic;
+    }
+
+    /// Returns the iteration budget, or `MCTS_ITERATIONS` for a time budget.
+    fn get_iterations(&self) -> usize {
+        match self.budget {
+            SearchBudget::Iterations(iterations) => iterations,
+            SearchBudget::Time(_) => MCTS_ITERATIONS,
+        }
+    }
+
+    /// Sets the playout count of an iteration budget. A time budget is kept.
+    fn set_iterations(&mut self, iterations: usize) {
+        if let SearchBudget::Iterations(_) = self.budget {
+            self.budget = SearchBudget::Iterations(iterations);
+        }
+    }
+
+    /// Returns the iteration or time budget of each search.
+    fn get_search_budget(&self) -> Option<SearchBudget> {
+        Some(self.budget)
+    }
+
+    /// Updates the iteration or time budget of each search.
+    fn set_search_budget(&mut self, budget: SearchBudget) {
+        self.budget = budget;
+    }
+
+    /// Returns the exploration constant C of the UCT formula.
+    fn get_exploration(&self) -> Option<f64> {
+        Some(self.exploration)
+    }
+
+    /// Updates the exploration constant C of the UCT formula.
+    fn set_exploration(&mut self, exploration: f64) {
+        self.exploration = exploration.max(0.0);
+    }
+
+    /// Returns the move selection strategy used during simulations.
+    fn get_playout_policy(&self) -> Option<PlayoutPolicy> {
+        Some(self.playout)
+    }
+
+    /// Updates the move selection strategy used during simulations.
+    fn set_playout_policy(&mut self, playout: PlayoutPolicy) {
+        self.playout = playout;
+    }
+
+    /// Returns how the opening book is used before searching.
+    fn get_book_mode(&self) -> Option<BookMode> {
+        Some(self.book_mode)
+    }
+
+    /// Updates how the opening book is used before searching.
+    fn set_book_mode(&mut self, mode: BookMode) {
+        self.book_mode = mode;
+    }
+
+    /// Returns the number of threads growing search trees.
+    fn get_threads(&self) -> Option<usize> {
+        Some(self.threads)
+    }
+
+    /// Updates the number of threads growing search trees.
+    fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Gives the search the token that stops it.
+    fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = cancel;
+    }
+
+    /// Searches the position and plays the most visited root move.
+    ///
+    /// The most visited move is the most robust choice in MCTS: its win rate
+    /// has been confirmed by the largest number of playouts. Ties are broken
+    /// by the order of `Board::has_legal_moves`.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - Mutable reference to the current game board
+    /// * `_cell` - Optional cell parameter (unused in this implementation)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HistoryAction)` - Details of the move that was played
+    /// * `Err(String)` - Error message if no move is available or it fails
+    fn play_turn(
+        &self,
+        board: &mut Board,
+        _cell: Option<(usize, usize)>,
+    ) -> Result<HistoryAction, String> {
+        // Play from the opening book while the position is in book
+        if let Some(action) = play_book_move(board, self.book_mode, self.get_color()) {
+            return action;
+        }
+
+        self.analyze(board)?.play_best(board, self.get_color())
+    }
+
+    /// Scores every legal move with a Monte Carlo search.
+    ///
+    /// The moves are ranked by number of visits, the criterion `play_turn`
+    /// uses to choose its move. The score of a move is its win rate in
+    /// percent and the playouts count as nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to analyze
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Analysis)` - Every legal move ranked by visits, with the search statistics
+    /// * `Err(String)` - If the search is cancelled
+    fn analyze(&self, board: &Board) -> Result<Analysis, String> {
+        let start = Instant::now();
+        let mut stats = self.search(board);
+        if self.cancel.is_cancelled() {
+            return Err(SEARCH_CANCELLED.to_string());
+        }
+        // Stable sort: the first generated move wins ties, as before
+        stats.sort_by_key(|stat| Reverse(stat.visits));
+
+        let counters = SearchCounters {
+            nodes: stats.iter().map(|stat| stat.visits as u64).sum(),
+            max_ply: 1,
+        };
+        let moves = stats
+            .iter()
+            .map(|stat| AnalyzedMove {
+                action: Action {
+                    pos: stat.pos,
+                    score: (stat.wins * 100.0 / stat.visits.max(1) as f64).round() as isize,
+                },
+                line: vec![stat.pos],
+            })
+            .collect();
+        Ok(Analysis::new(moves, counters, start.elapsed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_ai(iterations: usize, threads: usize) -> AIMcts {
+        let mut ai = AIMcts::new(
+            SearchBudget::Iterations(iterations),
+            HeuristicType::Absolute,
+            Cell::Black,
+            AIHeuristicMatrix::A,
+        )
+        .with_seed(Some(7));
+        ai.set_threads(threads);
+        ai
+    }
+
+    #[test]
+    fn test_new_ai_creation() {
+        let ai = create_test_ai(100, 2);
+        assert_eq!(ai.get_color(), Cell::Black);
+        assert_eq!(ai.get_search_budget(), Some(SearchBudget::Iterations(100)));
+        assert_eq!(ai.get_exploration(), Some(MCTS_EXPLORATION));
+        assert_eq!(ai.get_ai_type(), Some(AIType::Mcts));
+        assert!(!ai.is_human());
+    }
+
+    #[test]
+    fn test_search_uses_whole_budget() {
+        let ai = create_test_ai(203, 3);
+        let stats = ai.search(&Board::new());
+        assert_eq!(stats.len(), 4);
+        let visits: u32 = stats.iter().map(|stat| stat.visits).sum();
+        assert_eq!(visits, 203);
+    }
+
+    #[test]
+    fn test_seeded_search_is_reproducible() {
+        let ai = create_test_ai(300, 2).with_playout(PlayoutPolicy::Heuristic);
+        assert_eq!(ai.search(&Board::new()), ai.search(&Board::new()));
+    }
+
+    #[test]
+    fn test_analyze_ranks_by_visits() {
+        let ai = create_test_ai(400, 2);
+        let analysis = ai.analyze(&Board::new()).unwrap();
+        let stats = ai.search(&Board::new());
+        let most_visited = stats.iter().map(|stat| stat.visits).max().unwrap();
+        let best = analysis.best().unwrap().action.pos;
+        assert_eq!(
+            stats.iter().find(|stat| stat.pos == best).unwrap().visits,
+            most_visited
+        );
+        assert_eq!(analysis.info.nodes, 400);
+        assert!(analysis
+            .moves
+            .iter()
+            .all(|m| (0..=100).contains(&m.action.score)));
+    }
+
+    #[test]
+    fn test_play_turn_plays_legal_move() {
+        let ai = create_test_ai(100, 2);
+        let mut board = Board::new();
+        let action = ai.play_turn(&mut board, None).unwrap();
+        assert_eq!(action.color, Cell::Black);
+        assert_eq!(board.get_nb_discs(Cell::Black), Ok(4));
+        assert_eq!(
+            action.search_info.as_ref().map(|info| info.nodes),
+            Some(100)
+        );
+        assert!(["2D", "3C", "4F", "5E"].contains(&action.coordinates.unwrap().as_str()));
+    }
+
+    #[test]
+    fn test_iterations_setter_keeps_time_budget() {
+        let mut ai = create_test_ai(100, 1);
+        ai.set_iterations(4000);
+        assert_eq!(ai.get_search_budget(), Some(SearchBudget::Iterations(4000)));
+
+        let time = SearchBudget::Time(Duration::from_millis(10));
+        ai.set_search_budget(time);
+        assert_eq!(ai.get_iterations(), MCTS_ITERATIONS);
+        ai.set_iterations(2000);
+        assert_eq!(ai.get_search_budget(), Some(time));
+        assert_eq!(time.other_kind(), SearchBudget::Iterations(MCTS_ITERATIONS));
+    }
+
+    #[test]
+    fn test_time_budget_stops() {
+        let mut ai = create_test_ai(0, 2);
+        ai.budget = SearchBudget::Time(Duration::from_millis(20));
+        let start = Instant::now();
+        let stats = ai.search(&Board::new());
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(stats.iter().map(|stat| stat.visits).sum::<u32>() > 0);
+    }
+
+    #[test]
+    fn test_cancelled_search_returns_error() {
+        let mut ai = create_test_ai(0, 2);
+        ai.budget = SearchBudget::Time(Duration::from_secs(60));
+        let token = CancelToken::new();
+        ai.set_cancel_token(token.clone());
+        token.cancel();
+        let start = Instant::now();
+        assert_eq!(
+            ai.analyze(&Board::new()).err(),
+            Some(SEARCH_CANCELLED.to_string())
+        );
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}
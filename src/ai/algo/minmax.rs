@@ -4,14 +4,20 @@
 //! that assumes both players play optimally. The algorithm alternates between maximizing
 //! and minimizing players to find the best possible move for the current player.
 
-use std::{thread, vec};
+use std::{time::Instant, vec};
 
 use crate::{
     ai::{
-        action::Action, ai_type::AIType, heuristic::HeuristicType,
+        action::Action,
+        ai_type::AIType,
+        analysis::{Analysis, AnalyzedMove},
+        cancel::{CancelToken, SEARCH_CANCELLED},
+        heuristic::HeuristicType,
         heuristic_matrix::AIHeuristicMatrix,
+        opening_book::{play_book_move, BookMode},
+        search_info::SearchCounters,
+        worker_pool::{default_threads, WorkerPool},
     },
-    consts::MAX_DEPTH,
     game::{board::Board, cell::Cell, history_action::HistoryAction, player::Player},
 };
 
@@ -21,8 +27,8 @@ use crate::{
 /// moves in a game tree, assuming both players play optimally. It alternates between
 /// maximizing the score for the current player and minimizing it for the opponent.
 ///
-/// This implementation includes optional multithreading support for improved performance
-/// on multi-core systems when evaluating moves at the maximum depth.
+/// The search runs on a bounded worker pool. With double threading enabled, the
+/// replies to the root moves are also spread across the workers.
 ///
 /// # Examples
 ///
@@ -49,8 +55,14 @@ pub struct AIMinMax {
     color: Cell,
     /// Matrix used by the heuristic function for position evaluation
     matrix: AIHeuristicMatrix,
-    /// Whether to use multithreading for move evaluation
+    /// Whether the replies to the root moves are also split between the workers
     double_threading: bool,
+    /// How the opening book is used before searching
+    book_mode: BookMode,
+    /// Number of workers sharing the search jobs
+    threads: usize,
+    /// Token that stops the running search
+    cancel: CancelToken,
 }
 
 impl AIMinMax {
@@ -67,7 +79,7 @@ impl AIMinMax {
     /// * `heuristic` - The heuristic function used to evaluate board positions
     /// * `color` - The color (Black or White) that this AI will play as
     /// * `matrix` - Heuristic matrix used for position evaluation
-    /// * `double_threading` - Whether to enable multithreading for move evaluation
+    /// * `double_threading` - Whether the replies to the root moves are also split between the workers
     ///
     /// # Examples
     ///
@@ -94,6 +106,9 @@ impl AIMinMax {
             color,
             matrix,
             double_threading,
+            book_mode: BookMode::Best,
+            threads: default_threads(),
+            cancel: CancelToken::new(),
         }
     }
 
@@ -112,124 +127,91 @@ impl AIMinMax {
         self.color
     }
 
-    /// Initializes the minimax search tree from the root position.
-    ///
-    /// This method starts the minimax algorithm from the current board state,
-    /// evaluating all possible moves to the specified depth to find the best
-    /// move's evaluation score.
-    ///
-    /// # Arguments
-    ///
-    /// * `board` - The current game board state to evaluate
-    /// * `depth` - Maximum search depth for this evaluation
-    ///
-    /// # Returns
-    ///
-    /// The heuristic score of the best move found at the given depth
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// let ai = AIMinMax::new(4, HeuristicType::default(), Cell::Black, AIHeuristicMatrix::default(), false);
-    /// let board = Board::new();
-    /// let score = ai.init_tree(&board, 4);
-    /// // Score represents the minimax evaluation at depth 4
-    /// ```
-    pub fn init_tree(&self, board: &Board, depth: usize) -> isize {
-        self.tree_step(board, depth)
-    }
-
     /// Performs one step of the minimax algorithm recursively.
     ///
     /// This is the core recursive function that implements the minimax algorithm.
-    /// It alternates between maximizing and minimizing players based on the current
-    /// depth (even depths maximize, odd depths minimize).
+    /// It maximizes the score when this AI is to move and minimizes it when the
+    /// opponent is to move, so passes are handled naturally. Along the way it
+    /// counts the visited nodes and keeps the principal variation.
     ///
-    /// At the maximum depth, if multithreading is enabled, it will evaluate all
-    /// possible moves in parallel for improved performance.
+    /// The recursion itself is sequential: `analyze` spreads the subtrees
+    /// over the worker pool.
     ///
     /// # Arguments
     ///
     /// * `board` - Current board state to evaluate
     /// * `depth` - Remaining search depth (decreases with each recursive call)
+    /// * `ply` - Distance from the root of the search
+    /// * `counters` - Node and depth counters of the current thread
     ///
     /// # Returns
     ///
-    /// The minimax evaluation score for the current position
+    /// The minimax score of the position and the moves of the best line from it.
     ///
     /// # Algorithm Details
     ///
-    /// - Base case: When depth reaches 1 or no legal moves exist, return heuristic evaluation
+    /// - Base case: When depth reaches 1 or the game is over, return heuristic evaluation
     /// - Recursive case: Try all legal moves and find the best/worst score depending on player
     /// - Maximizing player: Seeks the highest score among all possible moves
     /// - Minimizing player: Seeks the lowest score among all possible moves
-    /// - Multithreading: At maximum depth, evaluates moves in parallel if enabled
-    pub fn tree_step(&self, board: &Board, depth: usize) -> isize {
-        // Determine comparison function and initial best score based on player type
-        let comparation_function: fn(isize, isize) -> isize;
-        let mut best_score;
-        if depth % 2 == 0 {
-            // Maximizing player (even depth)
-            comparation_function = isize::max;
-            best_score = isize::MIN;
-        } else {
-            // Minimizing player (odd depth)
-            comparation_function = isize::min;
-            best_score = isize::MAX;
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let ai = AIMinMax::new(4, HeuristicType::default(), Cell::Black, AIHeuristicMatrix::default(), false);
+    /// let mut counters = SearchCounters::default();
+    /// let (score, line) = ai.tree_step(&Board::new(), 4, 1, &mut counters);
+    /// // `line` holds the 4 moves of the principal variation
+    /// ```
+    pub fn tree_step(
+        &self,
+        board: &Board,
+        depth: usize,
+        ply: usize,
+        counters: &mut SearchCounters,
+    ) -> (isize, Vec<(usize, usize)>) {
+        // A cancelled search unwinds at once, its result is thrown away
+        if self.cancel.is_cancelled() {
+            return (0, Vec::new());
         }
+        counters.visit(ply);
 
-        if depth == 1 || board.has_legal_moves(board.get_player_turn()) == None {
+        let player = board.get_player_turn();
+        let moves = match board.has_legal_moves(player) {
+            Some(moves) if depth > 1 && !board.is_game_over() => moves,
             // Base case: evaluate the current position using the heuristic function
-            let score = self
-                .heuristic
-                .evaluate(board, self.get_color(), self.matrix.clone());
-            return score;
-        } else if depth == MAX_DEPTH && self.double_threading {
-            // Use multithreading at maximum depth for improved performance
-            let mut handles = vec![];
-            for case in board.has_legal_moves(board.get_player_turn()).unwrap() {
-                let mut new_board = board.clone();
-                match new_board.try_play_move(case.0, case.1, board.get_player_turn()) {
-                    Ok(_) => {
-                        let ai_cloned = self.clone();
-                        let handle =
-                            thread::spawn(move || ai_cloned.tree_step(&new_board, depth - 1));
-                        handles.push(handle);
-                    }
-                    Err(e) => {
-                        println!("Error: {}", e);
-                    }
-                }
+            _ => {
+                let score = self
+                    .heuristic
+                    .evaluate(board, self.get_color(), self.matrix.clone());
+                return (score, Vec::new());
             }
+        };
 
-            // Collect results from all threads
-            for handle in handles {
-                match handle.join() {
-                    Ok(score) => {
-                        best_score = comparation_function(best_score, score);
-                    }
-                    Err(_) => {
-                        println!("Thread panicked");
-                    }
-                }
+        // Determine comparison function and initial best score based on player type
+        let maximizing = player == self.get_color();
+        let mut best_score = if maximizing { isize::MIN } else { isize::MAX };
+        let mut best_line = Vec::new();
+        let mut keep_best = |case: (usize, usize), score: isize, line: Vec<(usize, usize)>| {
+            if is_better(maximizing, score, best_score) || best_line.is_empty() {
+                best_score = score;
+                best_line = [vec![case], line].concat();
             }
-            best_score
-        } else {
-            // Sequential evaluation of all possible moves
-            for case in board.has_legal_moves(board.get_player_turn()).unwrap() {
-                let mut new_board = board.clone();
-                match new_board.try_play_move(case.0, case.1, board.get_player_turn()) {
-                    Ok(_) => {
-                        let score = self.tree_step(&new_board, depth - 1);
-                        best_score = comparation_function(best_score, score);
-                    }
-                    Err(e) => {
-                        println!("Error: {}", e);
-                    }
+        };
+
+        for case in moves {
+            let mut new_board = board.clone();
+            match new_board.play_and_advance(case.0, case.1) {
+                Ok(_) => {
+                    let (score, line) = self.tree_step(&new_board, depth - 1, ply + 1, counters);
+                    keep_best(case, score, line);
+                }
+                Err(e) => {
+                    println!("Error: {}", e);
                 }
             }
-            best_score
         }
+        (best_score, best_line)
     }
 }
 
@@ -242,10 +224,11 @@ impl Player for AIMinMax {
         false
     }
 
-    /// Returns whether multithreading is enabled for this AI.
+    /// Returns whether double threading is enabled for this AI.
     ///
-    /// When enabled, the AI will use multiple threads to evaluate moves
-    /// in parallel at the maximum search depth, improving performance.
+    /// When enabled, the replies to every root move are searched as separate
+    /// jobs of the worker pool, which balances the work between the workers
+    /// when there are few root moves.
     fn get_double_threading(&self) -> bool {
         self.double_threading
     }
@@ -329,6 +312,31 @@ impl Player for AIMinMax {
         self.depth = depth;
     }
 
+    /// Returns how the opening book is used before searching.
+    fn get_book_mode(&self) -> Option<BookMode> {
+        Some(self.book_mode)
+    }
+
+    /// Updates how the opening book is used before searching.
+    fn set_book_mode(&mut self, mode: BookMode) {
+        self.book_mode = mode;
+    }
+
+    /// Returns the number of workers sharing the search jobs.
+    fn get_threads(&self) -> Option<usize> {
+        Some(self.threads)
+    }
+
+    /// Updates the number of workers sharing the search jobs.
+    fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    /// Gives the search the token that stops it.
+    fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = cancel;
+    }
+
     /// Calculates and executes the best move for the current board position.
     ///
     /// This method implements the main AI logic, using multithreading to
@@ -352,72 +360,129 @@ impl Player for AIMinMax {
     /// - No legal moves are available
     /// - Move evaluation fails due to invalid board state
     /// - Thread join errors during parallel evaluation
+    /// - The search is cancelled through its `CancelToken`
     /// - Move execution fails on the board
     ///
     /// # Performance
     ///
-    /// This method uses multithreading to evaluate moves in parallel,
+    /// This method evaluates the moves in parallel on a bounded `WorkerPool`,
     /// which can significantly improve performance on multi-core systems.
-    /// The number of threads created equals the number of legal moves available.
+    /// The pool runs at most `threads` workers, whatever the number of legal
+    /// moves: the root moves (or, with double threading, the root moves and
+    /// their replies) are queued as jobs that the workers share.
+    ///
+    /// # Search Statistics
+    ///
+    /// The returned action carries a `SearchInfo` with the nodes visited by
+    /// all threads, the time taken, the deepest ply and the principal variation.
     fn play_turn(
         &self,
         board: &mut Board,
         _cell: Option<(usize, usize)>,
     ) -> Result<HistoryAction, String> {
-        // Initialize the best action with minimum score
-        let mut best_action = Action {
-            pos: (0, 0),
-            score: isize::MIN,
-        };
-        let mut handles = vec![];
+        // Play from the opening book while the position is in book
+        if let Some(action) = play_book_move(board, self.book_mode, self.get_color()) {
+            return action;
+        }
 
-        // Evaluate all legal moves in parallel using threads
-        for case in board.has_legal_moves(board.get_player_turn()).unwrap() {
-            let mut new_board = board.clone();
+        self.analyze(board)?.play_best(board, self.get_color())
+    }
 
-            match new_board.try_play_move(case.0, case.1, self.get_color()) {
-                Ok(_) => {
-                    // Clone the AI for the thread and spawn evaluation task
-                    let ai_cloned = self.clone();
-                    let handle = thread::spawn(move || {
-                        (case, ai_cloned.init_tree(&new_board, ai_cloned.depth))
-                    });
-                    handles.push(handle);
-                }
-                Err(e) => {
-                    return Err(format!("Error: {}", e));
+    /// Scores every legal move with a minimax search.
+    ///
+    /// The search is split into jobs run by a `WorkerPool` of `threads`
+    /// workers: one job per root move, or with double threading one job per
+    /// root move and reply, so the pool has more and smaller jobs to share.
+    /// Either way the scores are those of a plain minimax search, and
+    /// `play_turn` plays the first move of the resulting ranking.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to analyze
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Analysis)` - Every legal move ranked by score, with the search statistics
+    /// * `Err(String)` - If a move cannot be played, a worker panics or the search is cancelled
+    fn analyze(&self, board: &Board) -> Result<Analysis, String> {
+        let start = Instant::now();
+        // Search from the point of view of the player to move
+        let mut ai = self.clone();
+        ai.color = board.get_player_turn();
+        let mut counters = SearchCounters::default();
+
+        // Root moves with whether the AI moves next, and the jobs of the pool
+        let mut roots = vec![];
+        let mut jobs = vec![];
+        for case in board.has_legal_moves(ai.color).unwrap_or_default() {
+            let mut child = board.clone();
+            child
+                .play_and_advance(case.0, case.1)
+                .map_err(|e| format!("Error: {}", e))?;
+            let root = roots.len();
+            roots.push((case, child.get_player_turn() == ai.color));
+
+            match child.has_legal_moves(child.get_player_turn()) {
+                Some(replies) if ai.double_threading && ai.depth > 1 && !child.is_game_over() => {
+                    counters.visit(1);
+                    for reply in replies {
+                        let mut grandchild = child.clone();
+                        grandchild
+                            .play_and_advance(reply.0, reply.1)
+                            .map_err(|e| format!("Error: {}", e))?;
+                        jobs.push((root, Some(reply), grandchild));
+                    }
                 }
+                _ => jobs.push((root, None, child)),
             }
         }
 
-        // Collect results from all threads and find the best move
-        for handle in handles {
-            match handle.join() {
-                Ok((pos, score)) => {
-                    if score > best_action.score {
-                        best_action = Action { pos, score };
-                    }
-                }
-                Err(_) => {
-                    return Err("Thread join error".to_string());
-                }
-            }
+        let results = WorkerPool::new(ai.threads).map(jobs, |(root, reply, position)| {
+            let mut counters = SearchCounters::default();
+            let (score, line) = match reply {
+                Some(_) => ai.tree_step(&position, ai.depth - 1, 2, &mut counters),
+                None => ai.tree_step(&position, ai.depth, 1, &mut counters),
+            };
+            (root, reply, score, line, counters)
+        })?;
+        if ai.cancel.is_cancelled() {
+            return Err(SEARCH_CANCELLED.to_string());
         }
 
-        // Execute the best move on the board and return the action history
-        match board.try_play_move(best_action.pos.0, best_action.pos.1, self.get_color()) {
-            Ok(gained_discs) => Ok(HistoryAction {
-                coordinates: Some(Board::coordinates_to_input(
-                    best_action.pos.0,
-                    best_action.pos.1,
-                )),
-                gained_discs: Some(gained_discs),
-                color: self.get_color(),
-                player_turn: board.get_player_turn(),
-                move_number: board.get_turn_number(),
-            }),
-            Err(e) => Err(format!("Error playing move: {}", e)),
+        // Keep, for every root move, the reply chosen by the player to move after it
+        let mut best: Vec<Option<AnalyzedMove>> = vec![None; roots.len()];
+        for (root, reply, score, line, thread_counters) in results {
+            counters.merge(thread_counters);
+            let (pos, maximizing) = roots[root];
+            if best[root]
+                .as_ref()
+                .is_none_or(|best| is_better(maximizing, score, best.action.score))
+            {
+                best[root] = Some(AnalyzedMove {
+                    action: Action { pos, score },
+                    line: [vec![pos], reply.into_iter().collect(), line].concat(),
+                });
+            }
         }
+
+        let mut moves: Vec<AnalyzedMove> = best.into_iter().flatten().collect();
+        Analysis::rank_by_score(&mut moves);
+        Ok(Analysis::new(moves, counters, start.elapsed()))
+    }
+}
+
+/// Tells whether `score` beats `best` for the player choosing the move.
+///
+/// # Arguments
+///
+/// * `maximizing` - Whether the player choosing is the AI (maximizing) or its opponent
+/// * `score` - The score of the candidate move
+/// * `best` - The best score found so far
+fn is_better(maximizing: bool, score: isize, best: isize) -> bool {
+    if maximizing {
+        score > best
+    } else {
+        score < best
     }
 }
 
@@ -565,4 +630,21 @@ mod tests {
         );
         assert_eq!(ai_large_depth.get_depth(), 1000);
     }
+
+    #[test]
+    fn test_double_threading_matches_root_split() {
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+
+        let mut single = create_test_ai();
+        single.set_threads(1);
+        let mut double = create_test_ai();
+        double.set_double_threading(true);
+        double.set_threads(3);
+
+        let expected = single.analyze(&board).unwrap();
+        let analysis = double.analyze(&board).unwrap();
+        assert_eq!(analysis.moves, expected.moves);
+        assert_eq!(analysis.info.nodes, expected.info.nodes);
+    }
 }
@@ -1,3 +1,4 @@
 pub mod alphabeta;
+pub mod mcts;
 pub mod minmax;
 pub mod qlearning;
@@ -4,16 +4,635 @@
 //! that learns to play Othello by exploring different actions and learning from
 //! rewards. The AI maintains a Q-table that maps board states to action values,
 //! gradually improving its play through experience.
+//!
+//! # State symmetry
+//!
+//! Q-table states are keyed on the canonical symmetry class of the position
+//! (see [`canonical_hash`]), and actions are stored in the coordinates of that
+//! canonical board. The eight rotations and reflections of a position thus
+//! share one entry, and what is learned on one of them is reused on all the
+//! others. Counting every position reachable from the start:
+//!
+//! | Ply | Raw states | Canonical states |
+//! |-----|-----------:|-----------------:|
+//! | 1   | 4          | 1                |
+//! | 2   | 12         | 3                |
+//! | 3   | 54         | 14               |
+//! | 4   | 236        | 60               |
+//! | 5   | 1,288      | 322              |
+//! | 6   | 7,092      | 1,773            |
+//! | 7   | 42,614     | 10,649           |
+//! | 8   | 269,352    | 67,245           |
+//! | 9   | 1,743,592  | 434,029          |
+//! | 1-9 | 2,064,244  | 514,096          |
+//!
+//! The opening, where training revisits states most often, is almost exactly
+//! four times smaller (the start position already has four symmetries of its
+//! own). These totals are printed by the `count-states` command, and the
+//! first plies are checked by the tests of
+//! [`count_positions`](crate::game::symmetry::count_positions).
+//!
+//! # Update rules
+//!
+//! The rule updating the values after each move is chosen with the
+//! hyperparameters (see [`UpdateRule`]). All rules share the state encoding
+//! above and the Q-table storage:
+//!
+//! - **Q-learning** bootstraps from the best value of the next state.
+//! - **SARSA** bootstraps from the value of the action the epsilon-greedy
+//!   policy actually plays next, which is chosen before the update.
+//! - **Expected SARSA** bootstraps from the value of the next state expected
+//!   under the epsilon-greedy policy, unseen actions being worth 0.
+//! - **Double Q-learning** keeps a second table, saved next to the Q-table
+//!   (see [`double_q_table_path`]). Each move updates one of the two tables
+//!   at random, from the other table's value of its own best next action,
+//!   and the AI plays with the sum of both.
+//! - **TD(λ)** spreads the Q-learning error of each move back over the
+//!   earlier moves of the game, through eligibility traces decaying by
+//!   `discount * trace_decay` per move.
+//!
+//! The rule is saved with the hyperparameters of a trained table, so rules
+//! can be compared on the same training budget.
+//!
+//! # Zero-sum values
+//!
+//! A value is always seen from the player to move in its state, who is also
+//! the player `play_turn` picks the best action for. Othello being zero-sum,
+//! what the next state is worth to the opponent is lost by the mover, so the
+//! rules bootstrap negamax-style, `r - γ V(s')`, when the opponent moves in
+//! the next state, and `r + γ V(s')` when the opponent passes (or, against a
+//! training opponent, has already answered). The reward `r` is the heuristic
+//! evaluation for the mover plus the win or loss bonus, and the value of a
+//! finished game is 0.
 
 use rand::{rng, Rng};
-use std::{collections::HashMap, fs::File, io::Write, sync::mpsc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::Write,
+    path::Path,
+    sync::mpsc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use crate::{
-    ai::{ai_type::AIType, heuristic::HeuristicType, heuristic_matrix::AIHeuristicMatrix},
-    consts::{EPSILON, GAMMA, LAMBDA_LEARN},
-    game::{board::Board, cell::Cell, history_action::HistoryAction, player::Player},
+    ai::{
+        ai_type::AIType,
+        algo::{alphabeta::AIAlphaBeta, minmax::AIMinMax},
+        cancel::CancelToken,
+        heuristic::HeuristicType,
+        heuristic_matrix::AIHeuristicMatrix,
+        opening_book::BookMode,
+        q_table::{load_q_table, save_q_table, QTable},
+        training_metrics::{
+            metrics_log_path, open_metrics_log, EvaluationOpponent, EvaluationResult,
+            MetricsTracker, TrainingMetrics, EVALUATION_OPPONENTS,
+        },
+    },
+    consts::{
+        EPSILON, EPSILON_DECAY, EPSILON_FLOOR, EPSILON_STEPS, GAMMA, LAMBDA_LEARN,
+        QLEARNING_CHECKPOINT_EPOCHS, QLEARNING_EVALUATION_EPOCHS, QLEARNING_EVALUATION_GAMES,
+        QLEARNING_METRICS_WINDOW, QLEARNING_OPPONENT_DEPTH, QLEARNING_SNAPSHOT_EPOCHS,
+        Q_TABLES_DIR, TRACE_DECAY, TRACE_THRESHOLD,
+    },
+    game::{
+        arena::play_game,
+        board::Board,
+        cell::Cell,
+        history_action::HistoryAction,
+        player::Player,
+        symmetry::{canonical_hash, Symmetry},
+    },
 };
 
+/// How epsilon decreases from its initial value to its floor during training.
+///
+/// # Examples
+///
+/// ```rust
+/// let schedule = EpsilonSchedule::Linear;
+/// assert_eq!(schedule.next(), EpsilonSchedule::Exponential);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EpsilonSchedule {
+    /// Decrease by the same amount each epoch, reaching the floor at the last epoch
+    Linear,
+    /// Multiply by the decay factor each epoch until the floor is reached
+    Exponential,
+    /// Drop by the same amount at each of `EPSILON_STEPS` equal plateaus
+    Step,
+}
+
+impl Display for EpsilonSchedule {
+    /// Formats the epsilon schedule for display purposes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(format!("{}", EpsilonSchedule::Step), "Step");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EpsilonSchedule::Linear => write!(f, "Linear"),
+            EpsilonSchedule::Exponential => write!(f, "Exponential"),
+            EpsilonSchedule::Step => write!(f, "Step"),
+        }
+    }
+}
+
+impl EpsilonSchedule {
+    /// Returns the next schedule in the cycle Linear → Exponential → Step → Linear.
+    pub fn next(&self) -> EpsilonSchedule {
+        match self {
+            EpsilonSchedule::Linear => EpsilonSchedule::Exponential,
+            EpsilonSchedule::Exponential => EpsilonSchedule::Step,
+            EpsilonSchedule::Step => EpsilonSchedule::Linear,
+        }
+    }
+
+    /// Returns the previous schedule in the cycle.
+    pub fn previous(&self) -> EpsilonSchedule {
+        match self {
+            EpsilonSchedule::Linear => EpsilonSchedule::Step,
+            EpsilonSchedule::Exponential => EpsilonSchedule::Linear,
+            EpsilonSchedule::Step => EpsilonSchedule::Exponential,
+        }
+    }
+}
+
+/// The opponent the Q-learning AI plays against during training.
+///
+/// # Examples
+///
+/// ```rust
+/// let opponent = TrainingOpponent::SelfPlay;
+/// assert_eq!(opponent.next(), TrainingOpponent::Random);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrainingOpponent {
+    /// The AI plays both sides with the same table and learns from every move
+    #[default]
+    SelfPlay,
+    /// A random mover
+    Random,
+    /// `AIAlphaBeta` at the opponent depth
+    AlphaBeta,
+    /// `AIMinMax` at the opponent depth
+    MinMax,
+    /// A frozen copy of the table, refreshed every `QLEARNING_SNAPSHOT_EPOCHS` epochs
+    Snapshot,
+}
+
+impl Display for TrainingOpponent {
+    /// Formats the training opponent for display purposes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(format!("{}", TrainingOpponent::AlphaBeta), "Alpha-Beta");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrainingOpponent::SelfPlay => write!(f, "Self-play"),
+            TrainingOpponent::Random => write!(f, "Random"),
+            TrainingOpponent::AlphaBeta => write!(f, "Alpha-Beta"),
+            TrainingOpponent::MinMax => write!(f, "Min-Max"),
+            TrainingOpponent::Snapshot => write!(f, "Snapshot"),
+        }
+    }
+}
+
+impl TrainingOpponent {
+    /// Returns the next opponent in the cycle
+    /// Self-play → Random → Alpha-Beta → Min-Max → Snapshot → Self-play.
+    pub fn next(&self) -> TrainingOpponent {
+        match self {
+            TrainingOpponent::SelfPlay => TrainingOpponent::Random,
+            TrainingOpponent::Random => TrainingOpponent::AlphaBeta,
+            TrainingOpponent::AlphaBeta => TrainingOpponent::MinMax,
+            TrainingOpponent::MinMax => TrainingOpponent::Snapshot,
+            TrainingOpponent::Snapshot => TrainingOpponent::SelfPlay,
+        }
+    }
+
+    /// Returns the previous opponent in the cycle.
+    pub fn previous(&self) -> TrainingOpponent {
+        match self {
+            TrainingOpponent::SelfPlay => TrainingOpponent::Snapshot,
+            TrainingOpponent::Random => TrainingOpponent::SelfPlay,
+            TrainingOpponent::AlphaBeta => TrainingOpponent::Random,
+            TrainingOpponent::MinMax => TrainingOpponent::AlphaBeta,
+            TrainingOpponent::Snapshot => TrainingOpponent::MinMax,
+        }
+    }
+
+    /// Returns whether the opponent is a search AI that uses the opponent depth.
+    pub fn uses_depth(&self) -> bool {
+        matches!(self, TrainingOpponent::AlphaBeta | TrainingOpponent::MinMax)
+    }
+}
+
+/// The rule updating the Q-values after each move of a training game.
+///
+/// # Examples
+///
+/// ```rust
+/// let rule = UpdateRule::QLearning;
+/// assert_eq!(rule.next(), UpdateRule::Sarsa);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UpdateRule {
+    /// One-step update towards the best value of the next state
+    #[default]
+    QLearning,
+    /// One-step update towards the value of the next action actually played
+    Sarsa,
+    /// One-step update towards the epsilon-greedy expected value of the next state
+    ExpectedSarsa,
+    /// Two tables, each updated from the other's value of its own best next action
+    DoubleQLearning,
+    /// Q-learning error spread over the earlier moves through eligibility traces
+    TdLambda,
+}
+
+impl Display for UpdateRule {
+    /// Formats the update rule for display purposes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(format!("{}", UpdateRule::TdLambda), "TD(λ)");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateRule::QLearning => write!(f, "Q-learning"),
+            UpdateRule::Sarsa => write!(f, "SARSA"),
+            UpdateRule::ExpectedSarsa => write!(f, "Expected SARSA"),
+            UpdateRule::DoubleQLearning => write!(f, "Double Q-learning"),
+            UpdateRule::TdLambda => write!(f, "TD(λ)"),
+        }
+    }
+}
+
+impl UpdateRule {
+    /// Returns the next rule in the cycle
+    /// Q-learning → SARSA → Expected SARSA → Double Q-learning → TD(λ) → Q-learning.
+    pub fn next(&self) -> UpdateRule {
+        match self {
+            UpdateRule::QLearning => UpdateRule::Sarsa,
+            UpdateRule::Sarsa => UpdateRule::ExpectedSarsa,
+            UpdateRule::ExpectedSarsa => UpdateRule::DoubleQLearning,
+            UpdateRule::DoubleQLearning => UpdateRule::TdLambda,
+            UpdateRule::TdLambda => UpdateRule::QLearning,
+        }
+    }
+
+    /// Returns the previous rule in the cycle.
+    pub fn previous(&self) -> UpdateRule {
+        match self {
+            UpdateRule::QLearning => UpdateRule::TdLambda,
+            UpdateRule::Sarsa => UpdateRule::QLearning,
+            UpdateRule::ExpectedSarsa => UpdateRule::Sarsa,
+            UpdateRule::DoubleQLearning => UpdateRule::ExpectedSarsa,
+            UpdateRule::TdLambda => UpdateRule::DoubleQLearning,
+        }
+    }
+}
+
+/// The hyperparameters a Q-table was trained with.
+///
+/// They are saved next to the Q-table with its [`QTableMetadata`], so every
+/// trained model records how it was trained.
+///
+/// # Examples
+///
+/// ```rust
+/// let params = QLearningHyperparameters::default();
+/// // Training starts fully exploring and ends at the floor
+/// assert_eq!(params.epsilon_at(0, 1000), params.epsilon);
+/// assert_eq!(params.epsilon_at(999, 1000), params.epsilon_floor);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QLearningHyperparameters {
+    /// Learning rate, the weight of new experiences against the current Q-value
+    pub learning_rate: f64,
+    /// Discount factor applied to the value of the next state
+    pub discount: f64,
+    /// Exploration rate at the first epoch
+    pub epsilon: f64,
+    /// Lowest exploration rate reached by the schedule
+    pub epsilon_floor: f64,
+    /// Factor applied to epsilon after each epoch by the exponential schedule
+    pub epsilon_decay: f64,
+    /// How epsilon goes from its initial value to its floor
+    pub epsilon_schedule: EpsilonSchedule,
+    /// Opponent played during training
+    #[serde(default)]
+    pub opponent: TrainingOpponent,
+    /// Search depth of the Alpha-Beta and Min-Max opponents
+    #[serde(default = "default_opponent_depth")]
+    pub opponent_depth: usize,
+    /// Rule updating the Q-values after each move
+    #[serde(default)]
+    pub update_rule: UpdateRule,
+    /// Decay of the eligibility traces, used by the TD(λ) rule only
+    #[serde(default = "default_trace_decay")]
+    pub trace_decay: f64,
+}
+
+/// Returns the default opponent depth, for hyperparameter files saved
+/// before opponents could be chosen.
+fn default_opponent_depth() -> usize {
+    QLEARNING_OPPONENT_DEPTH
+}
+
+/// Returns the default trace decay, for hyperparameter files saved before
+/// update rules could be chosen.
+fn default_trace_decay() -> f64 {
+    TRACE_DECAY
+}
+
+impl Default for QLearningHyperparameters {
+    /// Returns the hyperparameters defined in `consts.rs`.
+    fn default() -> Self {
+        Self {
+            learning_rate: LAMBDA_LEARN,
+            discount: GAMMA,
+            epsilon: EPSILON,
+            epsilon_floor: EPSILON_FLOOR,
+            epsilon_decay: EPSILON_DECAY,
+            epsilon_schedule: EpsilonSchedule::Exponential,
+            opponent: TrainingOpponent::SelfPlay,
+            opponent_depth: QLEARNING_OPPONENT_DEPTH,
+            update_rule: UpdateRule::QLearning,
+            trace_decay: TRACE_DECAY,
+        }
+    }
+}
+
+impl QLearningHyperparameters {
+    /// Returns the exploration rate to use at an epoch of the training.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - Index of the epoch, starting at 0
+    /// * `epochs` - Total number of epochs of the training
+    pub fn epsilon_at(&self, epoch: usize, epochs: usize) -> f64 {
+        let floor = self.epsilon_floor.min(self.epsilon);
+        let last = epochs.saturating_sub(1).max(1) as f64;
+        let epsilon = match self.epsilon_schedule {
+            EpsilonSchedule::Linear => {
+                self.epsilon - (self.epsilon - floor) * (epoch as f64 / last).min(1.0)
+            }
+            EpsilonSchedule::Exponential => self.epsilon * self.epsilon_decay.powf(epoch as f64),
+            EpsilonSchedule::Step => {
+                let plateau = (epoch * EPSILON_STEPS / epochs.max(1)).min(EPSILON_STEPS - 1);
+                self.epsilon - (self.epsilon - floor) * plateau as f64 / (EPSILON_STEPS - 1) as f64
+            }
+        };
+        epsilon.max(floor)
+    }
+}
+
+/// What is known about how a Q-table was trained.
+///
+/// It is saved next to the Q-table by [`QLearning::export_q_table`] (see
+/// [`QTableMetadata::path_for`]) and shown by the Q-table pickers of the
+/// setup screens. Files written before the colour, epochs and date were
+/// recorded only hold the hyperparameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QTableMetadata {
+    /// Colour the table was trained for, `None` when trained by self-play
+    #[serde(default)]
+    pub color: Option<Cell>,
+    /// Number of training epochs
+    #[serde(default)]
+    pub epochs: usize,
+    /// Epochs played when the table was saved, `None` when the training is complete
+    #[serde(default)]
+    pub completed_epochs: Option<usize>,
+    /// Exploration rate of the last epoch played, to resume the training with
+    #[serde(default)]
+    pub current_epsilon: Option<f64>,
+    /// Save time of the table, in seconds since the Unix epoch
+    #[serde(default)]
+    pub trained_at: Option<u64>,
+    /// Hyperparameters of the training
+    #[serde(flatten)]
+    pub hyperparameters: QLearningHyperparameters,
+}
+
+impl QTableMetadata {
+    /// Returns the path of the metadata file saved next to a Q-table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(
+    ///     QTableMetadata::path_for("q_tables/q_table_black.bin"),
+    ///     "q_tables/q_table_black.params.json"
+    /// );
+    /// ```
+    pub fn path_for(q_table_path: &str) -> String {
+        format!(
+            "{}.params.json",
+            Path::new(q_table_path).with_extension("").to_string_lossy()
+        )
+    }
+
+    /// Loads the metadata saved next to a Q-table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(QTableMetadata))` - The metadata of the table
+    /// * `Ok(None)` - If the table has no metadata file
+    /// * `Err(String)` - If the metadata file could not be read or parsed
+    pub fn load_for(q_table_path: &str) -> Result<Option<QTableMetadata>, String> {
+        let path = QTableMetadata::path_for(q_table_path);
+        if !Path::new(&path).exists() {
+            return Ok(None);
+        }
+        let file = File::open(&path).map_err(|e| e.to_string())?;
+        serde_json::from_reader(file)
+            .map(Some)
+            .map_err(|e| format!("Could not deserialize hyperparameters: {}", e))
+    }
+
+    /// Returns a one-line description of the training, for the pickers.
+    ///
+    /// Stopped trainings show their progress as "completed/total epochs".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let metadata = QTableMetadata {
+    ///     color: Some(Cell::White),
+    ///     epochs: 10000,
+    ///     completed_epochs: None,
+    ///     current_epsilon: None,
+    ///     trained_at: Some(0),
+    ///     hyperparameters: QLearningHyperparameters::default(),
+    /// };
+    /// assert_eq!(
+    ///     metadata.summary(),
+    ///     "WHITE, 10000 epochs, 1970-01-01 00:00 UTC, lr 0.80, gamma 0.99, eps 1.00-0.01 Exponential, vs Self-play, Q-learning"
+    /// );
+    /// ```
+    pub fn summary(&self) -> String {
+        let params = &self.hyperparameters;
+        format!(
+            "{}, {} epochs, {}, lr {:.2}, gamma {:.2}, eps {:.2}-{:.2} {}, vs {}, {}",
+            self.color
+                .map_or("Both colours".to_string(), |color| color.to_string()),
+            match self.completed_epochs {
+                Some(completed) => format!("{}/{}", completed, self.epochs),
+                None => self.epochs.to_string(),
+            },
+            self.trained_at
+                .map_or("unknown date".to_string(), format_timestamp),
+            params.learning_rate,
+            params.discount,
+            params.epsilon,
+            params.epsilon_floor,
+            params.epsilon_schedule,
+            params.opponent,
+            params.update_rule
+        )
+    }
+}
+
+/// A Q-table file found by [`list_q_tables`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct QTableFile {
+    /// Path of the Q-table
+    pub path: String,
+    /// Metadata saved next to it, if any could be read
+    pub metadata: Option<QTableMetadata>,
+}
+
+impl QTableFile {
+    /// Returns the file name followed by the training description.
+    pub fn summary(&self) -> String {
+        let name = Path::new(&self.path)
+            .file_name()
+            .map_or(self.path.clone(), |name| {
+                name.to_string_lossy().into_owned()
+            });
+        match &self.metadata {
+            Some(metadata) => format!("{}: {}", name, metadata.summary()),
+            None => format!("{}: no training metadata", name),
+        }
+    }
+}
+
+/// Lists the Q-table files of a directory, in file name order.
+///
+/// Every `.bin` and `.json` file is a Q-table, except the `.params.json`
+/// metadata files and the second tables of Double Q-learning.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the Q-tables
+///
+/// # Returns
+///
+/// The Q-tables with their metadata. A missing directory gives no table.
+pub fn list_q_tables(dir: &str) -> Vec<QTableFile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "bin" || ext == "json")
+        })
+        .map(|path| path.to_string_lossy().into_owned())
+        .filter(|path| !path.ends_with(".params.json"))
+        .filter(|path| {
+            !Path::new(path)
+                .file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().ends_with(".double"))
+        })
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| QTableFile {
+            metadata: QTableMetadata::load_for(&path).ok().flatten(),
+            path,
+        })
+        .collect()
+}
+
+/// Returns the Q-table file used by default for a colour.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(default_q_table_file(Cell::White), "q_tables/q_table_white.bin");
+/// ```
+pub fn default_q_table_file(color: Cell) -> String {
+    q_table_file_for(&format!("q_table_{}", color.to_string().to_lowercase()))
+}
+
+/// Returns the path of the Q-table called `name` in `Q_TABLES_DIR`.
+pub fn q_table_file_for(name: &str) -> String {
+    format!("{}/{}.bin", Q_TABLES_DIR, name)
+}
+
+/// Returns the path of the second table of Double Q-learning, saved next to
+/// a Q-table in the same format.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     double_q_table_path("q_tables/q_table_black.bin"),
+///     "q_tables/q_table_black.double.bin"
+/// );
+/// ```
+pub fn double_q_table_path(q_table_path: &str) -> String {
+    let path = Path::new(q_table_path);
+    match path.extension() {
+        Some(extension) => format!(
+            "{}.double.{}",
+            path.with_extension("").to_string_lossy(),
+            extension.to_string_lossy()
+        ),
+        None => format!("{}.double", q_table_path),
+    }
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time.
+fn format_timestamp(seconds: u64) -> String {
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds % 86400 / 3600,
+        seconds % 3600 / 60
+    )
+}
+
 /// An AI player that uses Q-Learning to learn optimal moves in Othello.
 ///
 /// Q-Learning is a reinforcement learning algorithm that learns to play by
@@ -45,7 +664,7 @@ pub struct QLearning {
     /// Maximum number of steps per training game
     max_step: usize,
     /// Q-table mapping board states to action values
-    q_table: HashMap<String, HashMap<String, isize>>,
+    q_table: QTable,
     /// Heuristic function used for reward calculation
     heuristic: HeuristicType,
     /// Matrix used by the heuristic function
@@ -54,6 +673,18 @@ pub struct QLearning {
     epoch: usize,
     /// Epsilon value for epsilon-greedy exploration
     epsilon: f64,
+    /// Learning rate, discount, epsilon schedule and opponent used for training
+    hyperparameters: QLearningHyperparameters,
+    /// Q-table file written by training and loaded before a game
+    q_table_file: String,
+    /// Number of epochs already played, training resumes from there
+    trained_epochs: usize,
+    /// Token stopping the training after the current epoch
+    cancel: CancelToken,
+    /// Frozen copy of the Q-table played by the snapshot opponent
+    snapshot: QTable,
+    /// Second table of Double Q-learning, empty with the other rules
+    double_q_table: QTable,
     /// The color (Black or White) that this AI represents
     color: Cell,
 }
@@ -98,6 +729,12 @@ impl QLearning {
             matrix,
             epoch: epoch,
             epsilon: EPSILON,
+            hyperparameters: QLearningHyperparameters::default(),
+            q_table_file: default_q_table_file(color),
+            trained_epochs: 0,
+            cancel: CancelToken::new(),
+            snapshot: HashMap::new(),
+            double_q_table: HashMap::new(),
             color: color,
         }
     }
@@ -155,9 +792,9 @@ impl QLearning {
 
     /// Returns a reference to the current Q-table.
     ///
-    /// The Q-table maps board states (as strings) to action values,
+    /// The Q-table maps canonical board states (as strings) to action values,
     /// representing the AI's learned knowledge about move quality.
-    pub fn get_q_table(&self) -> &HashMap<String, HashMap<String, isize>> {
+    pub fn get_q_table(&self) -> &QTable {
         &self.q_table
     }
 
@@ -194,6 +831,75 @@ impl QLearning {
         self.epsilon = epsilon;
     }
 
+    /// Loads the Q-table file of the AI to continue its training.
+    ///
+    /// The table comes back with its colour, hyperparameters, number of
+    /// epochs, and the epoch counter and epsilon it was saved with, so
+    /// `try_q_learning` carries on where it stopped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the training can be continued
+    /// * `Err(String)` - If the table could not be loaded, has no metadata
+    ///   or has already completed its training
+    pub fn resume_training(&mut self) -> Result<(), String> {
+        let file = self.q_table_file.clone();
+        let metadata = QTableMetadata::load_for(&file)?.ok_or(format!(
+            "{} has no training metadata to continue from",
+            file
+        ))?;
+        let Some(completed) = metadata.completed_epochs else {
+            return Err(format!(
+                "{} has already completed its {} epochs",
+                file, metadata.epochs
+            ));
+        };
+        if let Some(color) = metadata.color {
+            self.color = color;
+        }
+        self.import_q_table(&file)?;
+        self.epoch = metadata.epochs;
+        self.trained_epochs = completed;
+        self.snapshot = self.q_table.clone();
+        if let Some(epsilon) = metadata.current_epsilon {
+            self.epsilon = epsilon;
+        }
+        Ok(())
+    }
+
+    /// Updates the colour the AI plays and trains for.
+    ///
+    /// A Q-table file still on the default of the previous colour follows
+    /// the new colour.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The new colour of the AI
+    pub fn set_color(&mut self, color: Cell) {
+        if self.q_table_file == default_q_table_file(self.color) {
+            self.q_table_file = default_q_table_file(color);
+        }
+        self.color = color;
+    }
+
+    /// Returns the hyperparameters used for training.
+    pub fn get_hyperparameters(&self) -> &QLearningHyperparameters {
+        &self.hyperparameters
+    }
+
+    /// Updates the hyperparameters used for training.
+    ///
+    /// The current epsilon is reset to the initial epsilon of the new
+    /// hyperparameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `hyperparameters` - New learning rate, discount and epsilon schedule
+    pub fn set_hyperparameters(&mut self, hyperparameters: QLearningHyperparameters) {
+        self.epsilon = hyperparameters.epsilon;
+        self.hyperparameters = hyperparameters;
+    }
+
     /// Performs one complete Q-learning training game.
     ///
     /// This method plays a single game using the current Q-table and epsilon-greedy
@@ -205,117 +911,396 @@ impl QLearning {
     /// A tuple containing:
     /// * `isize` - Total reward accumulated during the game
     /// * `bool` - Whether the game ended naturally (true) or hit max steps (false)
+    /// * `Option<Cell>` - Winner of the game, `None` for a draw or an unfinished game
     ///
     /// # Algorithm Details
     ///
     /// - Uses epsilon-greedy action selection (random vs best known action)
-    /// - Updates Q-values using the Q-learning formula: Q[s,a] = (1-λ)*Q[s,a] + λ*(r + γ*max(Q[s',a']))
+    /// - Updates Q-values with the update rule of the hyperparameters, by default
+    ///   the Q-learning formula: Q[s,a] = (1-λ)*Q[s,a] + λ*(r - γ*max(Q[s',a'])),
+    ///   with `+ γ` instead when the mover plays again in s' (see "Zero-sum values")
     /// - Continues until game over or maximum steps reached
     /// - Applies win/loss bonuses to final rewards
-    pub fn q_learning(&mut self) -> (isize, bool) {
+    /// - In self-play, learns from the moves of both sides; against another
+    ///   opponent, only plays and learns its own colour, and the reward of a
+    ///   move is computed once the opponent has answered
+    pub fn q_learning(&mut self) -> (isize, bool, Option<Cell>) {
+        self.q_learning_from(Board::new())
+    }
+
+    /// Performs one training game from a given position, see `q_learning`.
+    fn q_learning_from(&mut self, mut board: Board) -> (isize, bool, Option<Cell>) {
         // Initialize game state
-        let mut board = Board::new();
         let mut step = 0;
-        let mut s = board.to_hash();
         let mut action: (usize, usize);
         let mut total_r = 0;
+        let self_play = self.hyperparameters.opponent == TrainingOpponent::SelfPlay;
+        let search = self.opponent_search();
+
+        // The opponent opens the game when the AI trains as White
+        if !self_play {
+            self.play_opponent_turns(&mut board, search.as_deref(), &mut step);
+        }
+        let (mut s, mut symmetry) = canonical_hash(&board);
+
+        // Action chosen ahead by SARSA, which bootstraps from it
+        let mut planned: Option<(usize, usize)> = None;
+        // Eligibility traces of TD(λ), keyed on state and action
+        let mut traces: HashMap<(String, String), f64> = HashMap::new();
 
         // Continue until max steps reached or game over
         while step < self.max_step && !board.check_game_over() {
             step += 1;
             // Choose action using epsilon-greedy strategy
             if let Some(actions) = board.has_legal_moves(board.get_player_turn()) {
-                if rng().random::<f64>() < self.epsilon || self.get_q_table().get(&s).is_none() {
-                    // Choose random action for exploration
-                    action = actions[rng().random_range(0..actions.len())];
-                } else {
-                    // Choose best known action for exploitation
-                    let mut best_action = None;
-                    let mut best_value = isize::MIN;
-                    if let Some(q_values) = self.get_q_table().get(&s) {
-                        for (action, value) in q_values {
-                            if *value > best_value {
-                                best_value = *value;
-                                best_action = Some(action);
-                            }
-                        }
-                    }
-                    action = Board::input_to_coordinates(best_action.unwrap().as_str()).unwrap();
-                }
+                let mover = board.get_player_turn();
+                action = planned
+                    .take()
+                    .unwrap_or_else(|| self.explore(&board, &actions));
 
                 // Execute the chosen action
-                board
-                    .try_play_move(action.0, action.1, board.get_player_turn())
-                    .unwrap();
+                board.try_play_move(action.0, action.1, mover).unwrap();
 
-                // Calculate reward using heuristic function
-                let mut r =
-                    self.heuristic
-                        .evaluate(&board, board.get_player_turn(), self.matrix.clone());
-
-                // Add win/loss bonus if game is over
-                if board.check_game_over() {
-                    let winner = board.get_winner();
-
-                    if let Some(w) = winner {
-                        if w == board.get_player_turn() {
-                            r += 1000; // Win bonus
-                        } else if w == board.get_player_turn().get_opponent() {
-                            r -= 1000; // Loss penalty
-                        }
-                    } else {
-                        r += 0; // Draw - no bonus
+                // Against an opponent, the move is judged once it has answered
+                if !self_play {
+                    if !board.check_game_over() {
+                        board.next_turn();
                     }
+                    self.play_opponent_turns(&mut board, search.as_deref(), &mut step);
                 }
 
-                // Update Q-value using Q-learning formula
-                // Q[s, a] = (1-λ)*Q[s, a] + λ*(r + γ * max(Q[new_state, :]))
-                let new_s = board.to_hash();
-
-                let q_value = self
-                    .get_q_table()
-                    .get(&s)
-                    .and_then(|q_values| {
-                        q_values.get(Board::coordinates_to_input(action.0, action.1).as_str())
-                    })
-                    .cloned()
-                    .unwrap_or(0);
-                let new_q_value = (1.0 - LAMBDA_LEARN) * q_value as f64
-                    + LAMBDA_LEARN
-                        * (r as f64
-                            + GAMMA
-                                * self
-                                    .get_q_table()
-                                    .get(&new_s)
-                                    .and_then(|q_values| q_values.values().cloned().max())
-                                    .unwrap_or(0) as f64);
-
-                self.set_q_table(
-                    s.clone(),
-                    (
-                        Board::coordinates_to_input(action.0, action.1),
-                        new_q_value as isize,
-                    ),
+                // Calculate reward using heuristic function
+                let r = self.reward(&mut board, mover);
+
+                // Hand the turn over so the next state is keyed like `play_turn` looks it up
+                if self_play {
+                    board.next_turn();
+                }
+
+                // SARSA bootstraps from the action it will actually play next
+                let (new_s, new_symmetry) = canonical_hash(&board);
+                if self.hyperparameters.update_rule == UpdateRule::Sarsa && !board.check_game_over()
+                {
+                    planned = board
+                        .has_legal_moves(board.get_player_turn())
+                        .map(|next_actions| self.explore(&board, &next_actions));
+                }
+
+                // Update the Q-values with the rule of the hyperparameters
+                self.learn(
+                    Transition {
+                        state: s,
+                        action: action_key(symmetry, action),
+                        mover,
+                        reward: r,
+                        next: &board,
+                        next_state: &new_s,
+                        next_symmetry: new_symmetry,
+                        next_action: planned,
+                    },
+                    &mut traces,
                 );
 
-                // Update state and total reward, advance to next turn
+                // Update state and total reward
                 s = new_s;
+                symmetry = new_symmetry;
                 total_r += r;
-                board.next_turn();
             }
         }
 
-        (total_r, board.check_game_over())
+        let game_over = board.check_game_over();
+        (
+            total_r,
+            game_over,
+            game_over.then(|| board.get_winner()).flatten(),
+        )
+    }
+
+    /// Returns the reward of a move for the player who played it: the
+    /// heuristic evaluation of the position reached, plus a bonus of 1000
+    /// for a win or minus 1000 for a loss.
+    fn reward(&self, board: &mut Board, mover: Cell) -> isize {
+        let mut r = self.heuristic.evaluate(board, mover, self.matrix.clone());
+
+        // Add win/loss bonus if game is over
+        if board.check_game_over() {
+            let winner = board.get_winner();
+
+            if let Some(w) = winner {
+                if w == mover {
+                    r += 1000; // Win bonus
+                } else if w == mover.get_opponent() {
+                    r -= 1000; // Loss penalty
+                }
+            } else {
+                r += 0; // Draw - no bonus
+            }
+        }
+        r
+    }
+
+    /// Chooses an action with the epsilon-greedy strategy: the best known
+    /// action, or a random one with probability epsilon and in positions
+    /// never seen.
+    fn explore(&self, board: &Board, actions: &[(usize, usize)]) -> (usize, usize) {
+        match greedy_action(&self.tables(), board) {
+            // Choose best known action for exploitation
+            Some(best_action) if rng().random::<f64>() >= self.get_epsilon() => best_action,
+            // Choose random action for exploration
+            _ => actions[rng().random_range(0..actions.len())],
+        }
+    }
+
+    /// Returns the tables the AI plays with: both tables with Double
+    /// Q-learning, the Q-table alone otherwise.
+    fn tables(&self) -> Vec<&QTable> {
+        match self.hyperparameters.update_rule {
+            UpdateRule::DoubleQLearning => vec![&self.q_table, &self.double_q_table],
+            _ => vec![&self.q_table],
+        }
+    }
+
+    /// Updates the Q-values after a move, with the update rule of the
+    /// hyperparameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `transition` - The move played and the position it led to
+    /// * `traces` - Eligibility traces of the game, used by TD(λ) only
+    fn learn(&mut self, transition: Transition, traces: &mut HashMap<(String, String), f64>) {
+        let rule = self.hyperparameters.update_rule;
+        let learning_rate = self.hyperparameters.learning_rate;
+        // The next state is worth its value to the mover only if the mover
+        // plays it, and the opposite of its value if the opponent does
+        let discount = if transition.next.get_player_turn() == transition.mover {
+            self.hyperparameters.discount
+        } else {
+            -self.hyperparameters.discount
+        };
+
+        // Double Q-learning updates one of its two tables, picked at random
+        let update_double = rule == UpdateRule::DoubleQLearning && rng().random_bool(0.5);
+        let (table, other) = if update_double {
+            (&self.double_q_table, self.get_q_table())
+        } else {
+            (self.get_q_table(), &self.double_q_table)
+        };
+        let next_values = table.get(transition.next_state);
+        let next_value = match rule {
+            UpdateRule::QLearning | UpdateRule::TdLambda => next_values
+                .and_then(|q_values| q_values.values().cloned().max())
+                .unwrap_or(0) as f64,
+            UpdateRule::Sarsa => transition
+                .next_action
+                .and_then(|next| {
+                    next_values?
+                        .get(&action_key(transition.next_symmetry, next))
+                        .cloned()
+                })
+                .unwrap_or(0) as f64,
+            UpdateRule::ExpectedSarsa => expected_value(
+                next_values,
+                transition.next,
+                transition.next_symmetry,
+                self.epsilon,
+            ),
+            UpdateRule::DoubleQLearning => next_values
+                .and_then(|q_values| q_values.iter().max_by_key(|(_, value)| **value))
+                .and_then(|(best, _)| other.get(transition.next_state)?.get(best).cloned())
+                .unwrap_or(0) as f64,
+        };
+        let q_value = table
+            .get(&transition.state)
+            .and_then(|q_values| q_values.get(&transition.action))
+            .cloned()
+            .unwrap_or(0) as f64;
+        let target = transition.reward as f64 + discount * next_value;
+
+        if rule != UpdateRule::TdLambda {
+            // Q[s, a] = (1-λ)*Q[s, a] + λ*(r ± γ * V(s'))
+            let new_q_value = (1.0 - learning_rate) * q_value + learning_rate * target;
+            let update = (transition.action, new_q_value as isize);
+            if update_double {
+                self.double_q_table
+                    .entry(transition.state)
+                    .or_default()
+                    .insert(update.0, update.1);
+            } else {
+                self.set_q_table(transition.state, update);
+            }
+            return;
+        }
+
+        // TD(λ): the error of this move also corrects the earlier moves,
+        // with the opposite sign for the moves of the other player
+        let error = target - q_value;
+        traces.insert((transition.state, transition.action), 1.0);
+        for ((state, action), trace) in traces.iter_mut() {
+            let value = self
+                .get_q_table()
+                .get(state)
+                .and_then(|q_values| q_values.get(action))
+                .cloned()
+                .unwrap_or(0) as f64;
+            let new_value = value + learning_rate * error * *trace;
+            self.set_q_table(state.clone(), (action.clone(), new_value as isize));
+            *trace *= discount * self.hyperparameters.trace_decay;
+        }
+        traces.retain(|_, trace| trace.abs() >= TRACE_THRESHOLD);
+    }
+
+    /// Builds the search AI used as training opponent, if the opponent is
+    /// Alpha-Beta or Min-Max.
+    ///
+    /// The search plays the other colour at the configured depth, with the
+    /// same heuristic as the rewards and without opening book, so the AI
+    /// learns to beat the search itself rather than the book.
+    fn opponent_search(&self) -> Option<Box<dyn Player>> {
+        let depth = self.hyperparameters.opponent_depth;
+        let color = self.color.get_opponent();
+        let mut search: Box<dyn Player> = match self.hyperparameters.opponent {
+            TrainingOpponent::AlphaBeta => Box::new(AIAlphaBeta::new(
+                depth,
+                self.heuristic.clone(),
+                color,
+                self.matrix.clone(),
+            )),
+            TrainingOpponent::MinMax => Box::new(AIMinMax::new(
+                depth,
+                self.heuristic.clone(),
+                color,
+                self.matrix.clone(),
+                false,
+            )),
+            _ => return None,
+        };
+        search.set_book_mode(BookMode::Off);
+        search.set_threads(1);
+        Some(search)
+    }
+
+    /// Plays greedy evaluation games against every evaluation opponent.
+    ///
+    /// The AI plays its best known move everywhere (epsilon 0, see
+    /// `play_turn`), half of the games as Black and half as White. The
+    /// Alpha-Beta opponents use the heuristic of the rewards, without
+    /// opening book; the random opponent is an untrained Q-learning AI,
+    /// which only plays random moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `games` - Number of games against each opponent
+    ///
+    /// # Returns
+    ///
+    /// The wins, draws and losses of the AI against each opponent.
+    pub fn evaluate(&self, games: usize) -> Vec<EvaluationResult> {
+        EVALUATION_OPPONENTS
+            .iter()
+            .map(|&opponent| {
+                let mut result = EvaluationResult::new(opponent);
+                for game in 0..games {
+                    let color = if game % 2 == 0 {
+                        Cell::Black
+                    } else {
+                        Cell::White
+                    };
+                    let other = self.evaluation_opponent(opponent, color.get_opponent());
+                    let board = match color {
+                        Cell::Black => play_game(self, other.as_ref(), Board::new()),
+                        _ => play_game(other.as_ref(), self, Board::new()),
+                    };
+                    match board.map(|board| board.get_winner()) {
+                        Ok(Some(winner)) if winner == color => result.wins += 1,
+                        Ok(Some(_)) => result.losses += 1,
+                        Ok(None) => result.draws += 1,
+                        Err(e) => result.error = Some(e),
+                    }
+                }
+                result
+            })
+            .collect()
+    }
+
+    /// Builds the player of an evaluation opponent for `color`.
+    fn evaluation_opponent(&self, opponent: EvaluationOpponent, color: Cell) -> Box<dyn Player> {
+        match opponent {
+            EvaluationOpponent::Random => Box::new(QLearning::new(
+                0,
+                self.heuristic.clone(),
+                self.matrix.clone(),
+                0,
+                color,
+            )),
+            EvaluationOpponent::AlphaBeta(depth) => {
+                let mut search =
+                    AIAlphaBeta::new(depth, self.heuristic.clone(), color, self.matrix.clone());
+                search.set_book_mode(BookMode::Off);
+                search.set_threads(1);
+                Box::new(search)
+            }
+        }
+    }
+
+    /// Plays the opponent's moves until it is the AI's turn again or the
+    /// game is over.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The training game
+    /// * `search` - The search AI built by `opponent_search`, if any
+    /// * `step` - Step counter of the game, increased for each move played
+    fn play_opponent_turns(
+        &self,
+        board: &mut Board,
+        search: Option<&dyn Player>,
+        step: &mut usize,
+    ) {
+        while !board.check_game_over() && board.get_player_turn() != self.color {
+            *step += 1;
+            if let Some(search) = search {
+                search.play_turn(board, None).unwrap();
+            } else {
+                let actions = board.has_legal_moves(board.get_player_turn()).unwrap();
+                let snapshot_action = match self.hyperparameters.opponent {
+                    TrainingOpponent::Snapshot => greedy_action(&[&self.snapshot], board),
+                    _ => None,
+                };
+                let (row, col) = snapshot_action
+                    .unwrap_or_else(|| actions[rng().random_range(0..actions.len())]);
+                board
+                    .try_play_move(row, col, board.get_player_turn())
+                    .unwrap();
+            }
+            if !board.check_game_over() {
+                board.next_turn();
+            }
+        }
     }
 
     /// Trains the AI through multiple epochs of Q-learning.
     ///
     /// This method performs the complete training process by playing multiple
-    /// games and learning from each one. The epsilon value is gradually decreased
-    /// to reduce exploration over time, allowing the AI to exploit its learned
-    /// knowledge more as training progresses.
+    /// games and learning from each one. The epsilon value follows the epsilon
+    /// schedule of the hyperparameters down to its floor, allowing the AI to
+    /// exploit its learned knowledge more as training progresses.
+    ///
+    /// After training, the Q-table and its metadata are exported to the
+    /// Q-table file of the AI for future use. A checkpoint is also written
+    /// every `QLEARNING_CHECKPOINT_EPOCHS` epochs, and the training stops
+    /// after the current epoch, saving the table, once the cancel token of
+    /// the AI is cancelled. Training resumes from `trained_epochs`, see
+    /// [`QLearning::resume_training`].
     ///
-    /// After training, the Q-table is exported to a file for future use.
+    /// The [`TrainingMetrics`] of every epoch are sent to `tx` and appended
+    /// to the CSV log of the Q-table (see [`metrics_log_path`]), measured over
+    /// the last `QLEARNING_METRICS_WINDOW` games. The win rate counts the
+    /// games won by the AI's colour. Every `QLEARNING_EVALUATION_EPOCHS`
+    /// epochs, and after the last one, the metrics also carry the results of
+    /// `QLEARNING_EVALUATION_GAMES` greedy games against each evaluation
+    /// opponent (see [`QLearning::evaluate`]). Failed evaluation games and
+    /// metrics log errors are reported in the `status` of the metrics, since
+    /// the terminal is drawn by the training screen.
     ///
     /// # Examples
     ///
@@ -323,27 +1308,106 @@ impl QLearning {
     /// let mut ai = QLearning::new(1000, HeuristicType::default(), AIHeuristicMatrix::default(), 500, Cell::Black);
     /// ai.try_q_learning();  // Train for 500 epochs
     /// ```
-    pub fn try_q_learning(&mut self, tx: mpsc::Sender<f64>) {
-        for i in 0..self.epoch {
-            // Play one training game and learn from it
-            let (_total_r, _done) = self.q_learning();
+    pub fn try_q_learning(&mut self, tx: mpsc::Sender<TrainingMetrics>) {
+        if let Some(dir) = Path::new(&self.q_table_file).parent() {
+            std::fs::create_dir_all(dir).ok();
+        }
+        let mut tracker = MetricsTracker::new(QLEARNING_METRICS_WINDOW);
+        let log_path = metrics_log_path(&self.q_table_file);
+        let mut log_error = None;
+        let mut log = match open_metrics_log(&log_path, self.trained_epochs > 0) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                log_error = Some(format!(
+                    "Could not open the metrics log {}: {}",
+                    log_path, e
+                ));
+                None
+            }
+        };
+        for i in self.trained_epochs..self.epoch {
+            // Stop gracefully, the table is saved below
+            if self.cancel.is_cancelled() {
+                break;
+            }
+            // Refresh the frozen copy of the table the snapshot opponent plays with
+            if self.hyperparameters.opponent == TrainingOpponent::Snapshot
+                && i.is_multiple_of(QLEARNING_SNAPSHOT_EPOCHS)
+            {
+                self.snapshot = self.q_table.clone();
+            }
             // Gradually reduce epsilon to favor exploitation over exploration
-            self.set_epsilon(self.get_epsilon() * 0.999);
+            self.set_epsilon(self.hyperparameters.epsilon_at(i, self.epoch));
+            // Play one training game and learn from it
+            let (total_r, _done, winner) = self.q_learning();
+            self.trained_epochs = i + 1;
+            tracker.record(total_r, winner == Some(self.color));
+
+            // Save a checkpoint the training can be continued from
+            if self
+                .trained_epochs
+                .is_multiple_of(QLEARNING_CHECKPOINT_EPOCHS)
+                && self.trained_epochs < self.epoch
+            {
+                self.export_q_table(&self.q_table_file);
+            }
 
-            // Send progress update to the channel
-            if let Err(e) = tx.send(i as f64 / (self.epoch - 1) as f64) {
-                eprintln!("Error sending training progress: {}", e);
+            // Log the metrics and send them to the channel
+            let mut metrics = tracker.metrics(
+                self.trained_epochs,
+                self.epoch,
+                self.epsilon,
+                self.q_table.len(),
+            );
+            // Measure the greedy play of the AI against fixed opponents
+            if self
+                .trained_epochs
+                .is_multiple_of(QLEARNING_EVALUATION_EPOCHS)
+                || self.trained_epochs == self.epoch
+            {
+                metrics.evaluation = Some(self.evaluate(QLEARNING_EVALUATION_GAMES));
+            }
+            if let Some(file) = log.as_mut() {
+                if let Err(e) = writeln!(file, "{}", metrics.to_csv()) {
+                    log_error = Some(format!(
+                        "Could not write the metrics log {}: {}",
+                        log_path, e
+                    ));
+                    log = None;
+                }
+            }
+            metrics.status = metrics
+                .evaluation
+                .iter()
+                .flatten()
+                .find_map(|result| {
+                    let error = result.error.as_ref()?;
+                    Some(format!(
+                        "Evaluation against {} failed: {}",
+                        result.opponent, error
+                    ))
+                })
+                .or_else(|| log_error.clone());
+            // Nobody follows the training any more, stop it and save the table
+            if tx.send(metrics).is_err() {
                 break;
             }
         }
+        if let Some(mut file) = log {
+            file.flush().ok();
+        }
         // Export the learned Q-table for future use
-        self.export_q_table("q_table_player_1.json");
+        self.export_q_table(&self.q_table_file);
     }
 
     /// Imports a Q-table from a JSON file.
     ///
     /// This allows loading a previously trained Q-table, enabling the AI
-    /// to use pre-learned knowledge without retraining.
+    /// to use pre-learned knowledge without retraining. The hyperparameters
+    /// saved next to the Q-table are loaded too when the file exists; older
+    /// Q-tables without one keep the current hyperparameters. A table
+    /// trained for the other colour is rejected, since its states never
+    /// have this AI to move.
     ///
     /// # Arguments
     ///
@@ -358,19 +1422,29 @@ impl QLearning {
     ///
     /// ```rust
     /// let mut ai = QLearning::new(1000, HeuristicType::default(), AIHeuristicMatrix::default(), 0, Cell::Black);
-    /// ai.import_q_table("trained_q_table.json").expect("Failed to load Q-table");
+    /// ai.import_q_table("trained_q_table.bin").expect("Failed to load Q-table");
     /// ```
     pub fn import_q_table(&mut self, file_path: &str) -> Result<(), String> {
-        match File::open(file_path) {
-            Ok(file) => match serde_json::from_reader(file) {
-                Ok(q_table) => {
-                    self.q_table = q_table;
-                    Ok(())
-                }
-                Err(e) => Err(format!("Could not deserialize Q-table: {}", e)),
-            },
-            Err(e) => Err(e.to_string()),
+        self.q_table = load_q_table(file_path)?;
+
+        if let Some(metadata) = QTableMetadata::load_for(file_path)? {
+            if metadata.color.is_some_and(|color| color != self.color) {
+                return Err(format!(
+                    "This Q-table was trained for {}",
+                    metadata.color.unwrap()
+                ));
+            }
+            self.set_hyperparameters(metadata.hyperparameters);
         }
+        let double_file = double_q_table_path(file_path);
+        self.double_q_table = if self.hyperparameters.update_rule == UpdateRule::DoubleQLearning
+            && Path::new(&double_file).exists()
+        {
+            load_q_table(&double_file)?
+        } else {
+            HashMap::new()
+        };
+        Ok(())
     }
 
     /// Returns the color (Black or White) that this AI player represents.
@@ -380,10 +1454,13 @@ impl QLearning {
         self.color
     }
 
-    /// Exports the current Q-table to a JSON file.
+    /// Exports the current Q-table to a file.
     ///
     /// This saves the AI's learned knowledge to a file, allowing it to be
-    /// loaded later without retraining.
+    /// loaded later without retraining. Paths ending with `.json` are saved
+    /// as JSON, any other in the compact binary format (see [`save_q_table`]). The colour, number of epochs, date
+    /// and hyperparameters of the training are saved next to it (see
+    /// [`QTableMetadata::path_for`]).
     ///
     /// # Arguments
     ///
@@ -393,17 +1470,126 @@ impl QLearning {
     ///
     /// ```rust
     /// let ai = QLearning::new(1000, HeuristicType::default(), AIHeuristicMatrix::default(), 0, Cell::Black);
-    /// ai.export_q_table("my_q_table.json");
+    /// ai.export_q_table("my_q_table.bin");
     /// ```
     pub fn export_q_table(&self, file_path: &str) {
+        save_q_table(&self.q_table, file_path).expect("Could not write Q-table");
+        if self.hyperparameters.update_rule == UpdateRule::DoubleQLearning {
+            save_q_table(&self.double_q_table, &double_q_table_path(file_path))
+                .expect("Could not write Q-table");
+        }
+
+        let metadata = QTableMetadata {
+            color: match self.hyperparameters.opponent {
+                TrainingOpponent::SelfPlay => None,
+                _ => Some(self.color),
+            },
+            epochs: self.epoch,
+            completed_epochs: (self.trained_epochs < self.epoch).then_some(self.trained_epochs),
+            current_epsilon: Some(self.epsilon),
+            trained_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_secs()),
+            hyperparameters: self.hyperparameters.clone(),
+        };
         let json =
-            serde_json::to_string_pretty(&self.q_table).expect("Could not serialize Q-table");
-        let mut file = File::create(file_path).expect("Could not create file");
+            serde_json::to_string_pretty(&metadata).expect("Could not serialize hyperparameters");
+        let mut file =
+            File::create(QTableMetadata::path_for(file_path)).expect("Could not create file");
         file.write_all(json.as_bytes())
             .expect("Could not write to file");
     }
 }
 
+/// Returns the Q-table key of an action played on the real board, expressed in
+/// the coordinates of the canonical board reached through `symmetry`.
+fn action_key(symmetry: Symmetry, action: (usize, usize)) -> String {
+    let (row, col) = symmetry.apply(action);
+    Board::coordinates_to_input(row, col)
+}
+
+/// Returns the action with the highest Q-value for a position, in real board
+/// coordinates, or `None` if the position is not in the tables.
+///
+/// With several tables, as with Double Q-learning, the values of an action
+/// in the tables are summed.
+fn greedy_action(tables: &[&QTable], board: &Board) -> Option<(usize, usize)> {
+    let (state, symmetry) = canonical_hash(board);
+    let best = match tables {
+        [table] => table
+            .get(&state)?
+            .iter()
+            .max_by_key(|(_, value)| **value)
+            .map(|(action, _)| action.as_str()),
+        _ => {
+            let mut values: HashMap<&str, isize> = HashMap::new();
+            for q_values in tables.iter().filter_map(|table| table.get(&state)) {
+                for (action, value) in q_values {
+                    *values.entry(action.as_str()).or_insert(0) += value;
+                }
+            }
+            values
+                .into_iter()
+                .max_by_key(|(_, value)| *value)
+                .map(|(action, _)| action)
+        }
+    };
+    action_from_key(symmetry, best?)
+}
+
+/// Returns the value of a position expected under the epsilon-greedy policy:
+/// the best value with probability `1 - epsilon`, the mean value of the legal
+/// actions otherwise. Actions never seen are worth 0, and so are positions
+/// without legal action.
+fn expected_value(
+    q_values: Option<&HashMap<String, isize>>,
+    board: &Board,
+    symmetry: Symmetry,
+    epsilon: f64,
+) -> f64 {
+    let Some(actions) = board.has_legal_moves(board.get_player_turn()) else {
+        return 0.0;
+    };
+    let values: Vec<f64> = actions
+        .iter()
+        .map(|&action| {
+            q_values
+                .and_then(|q_values| q_values.get(&action_key(symmetry, action)))
+                .cloned()
+                .unwrap_or(0) as f64
+        })
+        .collect();
+    let best = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    (1.0 - epsilon) * best + epsilon * mean
+}
+
+/// A move of a training game, as seen by the update rules.
+struct Transition<'a> {
+    /// Canonical state the move was played from
+    state: String,
+    /// Action played, in the coordinates of the canonical state
+    action: String,
+    /// Player who played the action
+    mover: Cell,
+    /// Reward of the move
+    reward: isize,
+    /// Position reached after the move
+    next: &'a Board,
+    /// Canonical state of the position reached
+    next_state: &'a str,
+    /// Symmetry from the position reached to its canonical state
+    next_symmetry: Symmetry,
+    /// Action SARSA plays next, in real board coordinates
+    next_action: Option<(usize, usize)>,
+}
+
+/// Maps a Q-table action key back from canonical to real board coordinates.
+fn action_from_key(symmetry: Symmetry, key: &str) -> Option<(usize, usize)> {
+    Board::input_to_coordinates(key).map(|pos| symmetry.inverse().apply(pos))
+}
+
 impl Player for QLearning {
     /// Returns false as this is an AI player, not a human player.
     ///
@@ -433,6 +1619,21 @@ impl Player for QLearning {
         }
     }
 
+    /// Returns the Q-table file written by training and loaded before a game.
+    fn get_q_table_file(&self) -> Option<String> {
+        Some(self.q_table_file.clone())
+    }
+
+    /// Updates the Q-table file written by training and loaded before a game.
+    fn set_q_table_file(&mut self, file: String) {
+        self.q_table_file = file;
+    }
+
+    /// Gives the AI the token that stops its training after the current epoch.
+    fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = cancel;
+    }
+
     /// Returns the AI type identifier for this player.
     ///
     /// This method allows the game engine to identify that this is a Q-Learning AI,
@@ -493,8 +1694,9 @@ impl Player for QLearning {
     ///
     /// # Strategy
     ///
-    /// 1. Look up the current board state in the Q-table
-    /// 2. If Q-values exist, select the action with the highest Q-value
+    /// 1. Look up the canonical form of the current board state in the Q-table
+    /// 2. If Q-values exist, select the action with the highest Q-value and map
+    ///    it back from canonical to real board coordinates
     /// 3. If no Q-values exist, select a random legal move
     /// 4. Execute the selected move on the board
     fn play_turn(
@@ -504,28 +1706,13 @@ impl Player for QLearning {
     ) -> Result<HistoryAction, String> {
         let actions = board.has_legal_moves(board.get_player_turn()).unwrap();
 
-        // Select the best action based on Q-table values
-        let mut best_action = None;
-        let mut best_value = isize::MIN;
+        // Select the best action based on Q-table values, or a random one
+        // when the position has never been seen
+        let best_action = greedy_action(&self.tables(), board)
+            .or_else(|| Some(actions[rng().random_range(0..actions.len())]));
 
-        if let Some(q_values) = self.get_q_table().get(&board.to_hash()) {
-            // Find the action with the highest Q-value
-            for (action, value) in q_values {
-                if *value > best_value {
-                    best_value = *value;
-                    best_action = Some(action.clone());
-                }
-            }
-        } else {
-            // No Q-values available, choose a random action
-            let random_index = rng().random_range(0..actions.len());
-            let random_action =
-                Board::coordinates_to_input(actions[random_index].0, actions[random_index].1);
-            best_action = Some(random_action);
-        }
-
-        // Convert action string to coordinates and execute the move
-        let action_coords = Board::input_to_coordinates(best_action.unwrap().as_str()).unwrap();
+        // Execute the move on the real board
+        let action_coords = best_action.ok_or("Invalid action in Q-table")?;
 
         match board.try_play_move(action_coords.0, action_coords.1, board.get_player_turn()) {
             Ok(gained_discs) => Ok(HistoryAction {
@@ -537,6 +1724,8 @@ impl Player for QLearning {
                 color: self.get_color(),
                 player_turn: board.get_player_turn(),
                 move_number: board.get_turn_number(),
+                from_book: false,
+                search_info: None,
             }),
             Err(e) => Err(format!("Error playing move: {}", e)),
         }
@@ -573,6 +1762,14 @@ mod tests {
         )
     }
 
+    /// Helper function to get a file or directory path unique to this test run
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
     #[test]
     fn test_new_creates_ai_with_correct_parameters() {
         let max_step = 1000;
@@ -730,15 +1927,83 @@ mod tests {
         ai.set_q_table("state1".to_string(), ("action1".to_string(), 100));
         ai.set_q_table("state2".to_string(), ("action2".to_string(), 200));
 
-        let test_file = "test_q_table.json";
-        ai.export_q_table(test_file);
+        let test_file = temp_path("test_q_table.json");
+        ai.export_q_table(&test_file);
 
         // Verify file was created by trying to read it
-        let file_exists = std::fs::metadata(test_file).is_ok();
-        assert!(file_exists);
+        let file_exists = std::fs::metadata(&test_file).is_ok();
 
         // Clean up
-        std::fs::remove_file(test_file).ok();
+        std::fs::remove_file(&test_file).ok();
+        std::fs::remove_file(QTableMetadata::path_for(&test_file)).ok();
+        assert!(file_exists);
+    }
+
+    #[test]
+    fn test_hyperparameters_round_trip_next_to_q_table() {
+        let mut ai = create_test_ai();
+        ai.set_q_table("state".to_string(), ("2D".to_string(), 10));
+        ai.set_hyperparameters(QLearningHyperparameters {
+            learning_rate: 0.3,
+            discount: 0.9,
+            epsilon: 0.5,
+            epsilon_floor: 0.05,
+            epsilon_decay: 0.99,
+            epsilon_schedule: EpsilonSchedule::Step,
+            opponent: TrainingOpponent::AlphaBeta,
+            opponent_depth: 3,
+            update_rule: UpdateRule::Sarsa,
+            trace_decay: 0.6,
+        });
+
+        let test_file = temp_path("test_q_table_hyperparameters.json");
+        ai.export_q_table(&test_file);
+        let mut loaded = QLearning::new(
+            500,
+            HeuristicType::Matrix,
+            AIHeuristicMatrix::B,
+            50,
+            Cell::Black,
+        );
+        let result = loaded.import_q_table(&test_file);
+        std::fs::remove_file(&test_file).ok();
+        std::fs::remove_file(QTableMetadata::path_for(&test_file)).ok();
+
+        result.unwrap();
+        assert_eq!(loaded.get_hyperparameters(), ai.get_hyperparameters());
+        assert_eq!(loaded.get_epsilon(), 0.5);
+        assert_eq!(loaded.get_q_table(), ai.get_q_table());
+    }
+
+    #[test]
+    fn test_epsilon_schedules_go_from_start_to_floor() {
+        let mut params = QLearningHyperparameters {
+            epsilon: 1.0,
+            epsilon_floor: 0.1,
+            epsilon_decay: 0.5,
+            ..QLearningHyperparameters::default()
+        };
+        for schedule in [
+            EpsilonSchedule::Linear,
+            EpsilonSchedule::Exponential,
+            EpsilonSchedule::Step,
+        ] {
+            params.epsilon_schedule = schedule;
+            assert_eq!(params.epsilon_at(0, 100), 1.0);
+            assert!((params.epsilon_at(99, 100) - 0.1).abs() < 1e-9);
+            for epoch in 1..100 {
+                assert!(params.epsilon_at(epoch, 100) <= params.epsilon_at(epoch - 1, 100));
+            }
+        }
+
+        params.epsilon_schedule = EpsilonSchedule::Linear;
+        assert!((params.epsilon_at(33, 100) - 0.7).abs() < 1e-9);
+        params.epsilon_schedule = EpsilonSchedule::Exponential;
+        assert_eq!(params.epsilon_at(2, 100), 0.25);
+        params.epsilon_schedule = EpsilonSchedule::Step;
+        // Constant within a plateau, then one drop of (1.0 - 0.1) / 9
+        assert_eq!(params.epsilon_at(9, 100), 1.0);
+        assert!((params.epsilon_at(10, 100) - 0.9).abs() < 1e-9);
     }
 
     #[test]
@@ -751,11 +2016,12 @@ mod tests {
             Cell::Black,
         );
 
-        let (total_reward, game_over) = ai.q_learning();
+        let (total_reward, game_over, winner) = ai.q_learning();
 
         // With 0 max steps, the game should not progress
         assert_eq!(total_reward, 0);
         assert!(!game_over); // Game doesn't have time to end
+        assert_eq!(winner, None);
     }
 
     #[test]
@@ -767,9 +2033,15 @@ mod tests {
             0,
             Cell::Black,
         );
+        let dir = temp_path("test_q_tables");
+        let test_file = format!("{}/zero_epochs.json", dir);
+        ai.set_q_table_file(test_file.clone());
 
         let (tx, rx) = mpsc::channel();
         ai.try_q_learning(tx);
+        let written = std::fs::metadata(&test_file).is_ok();
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(written);
 
         // With 0 epochs, no training should occur
         // The Q-table should remain empty
@@ -778,4 +2050,658 @@ mod tests {
         // Should not receive any progress updates
         assert!(rx.try_recv().is_err());
     }
+
+    #[test]
+    fn test_play_turn_reuses_values_learned_on_a_symmetric_position() {
+        // Learn a reply on one opening, then play the mirrored opening
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+        let (state, symmetry) = canonical_hash(&board);
+        let mut ai = create_test_ai();
+        ai.set_q_table(state.clone(), (action_key(symmetry, (2, 2)), 100));
+        ai.set_q_table(state, (action_key(symmetry, (4, 2)), -100));
+
+        let mirror = Symmetry::Transpose;
+        let mut mirrored = Board::from_hash(&mirror.apply_to_hash(&board.to_hash())).unwrap();
+        let action = ai.play_turn(&mut mirrored, None).unwrap();
+
+        let (row, col) = mirror.apply((2, 2));
+        assert_eq!(
+            action.coordinates,
+            Some(Board::coordinates_to_input(row, col))
+        );
+    }
+
+    #[test]
+    fn test_q_learning_stores_canonical_states() {
+        let mut ai = create_test_ai();
+        ai.set_epsilon(1.0);
+        for _ in 0..5 {
+            ai.q_learning();
+        }
+
+        // Every opening move leads to the same canonical state
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+        let opening = canonical_hash(&board).0;
+        assert!(ai.get_q_table().contains_key(&opening));
+        for state in ai.get_q_table().keys() {
+            let board = Board::from_hash(state).unwrap();
+            assert_eq!(canonical_hash(&board).0, *state);
+        }
+    }
+
+    #[test]
+    fn test_training_against_an_opponent_only_learns_its_own_moves() {
+        let mut ai = QLearning::new(
+            100,
+            HeuristicType::Absolute,
+            AIHeuristicMatrix::A,
+            1,
+            Cell::White,
+        );
+        ai.set_hyperparameters(QLearningHyperparameters {
+            opponent: TrainingOpponent::Random,
+            ..QLearningHyperparameters::default()
+        });
+        for _ in 0..5 {
+            let (_, game_over, _) = ai.q_learning();
+            assert!(game_over);
+        }
+
+        assert!(!ai.get_q_table().is_empty());
+        assert!(ai.get_q_table().keys().all(|state| state.starts_with('W')));
+    }
+
+    #[test]
+    fn test_training_against_a_search_opponent_finishes_games() {
+        let mut ai = create_test_ai();
+        for opponent in [TrainingOpponent::AlphaBeta, TrainingOpponent::MinMax] {
+            ai.set_hyperparameters(QLearningHyperparameters {
+                opponent,
+                opponent_depth: 1,
+                ..QLearningHyperparameters::default()
+            });
+            let (_, game_over, _) = ai.q_learning();
+            assert!(game_over);
+        }
+        assert!(ai.get_q_table().keys().all(|state| state.starts_with('B')));
+    }
+
+    #[test]
+    fn test_snapshot_opponent_plays_its_frozen_table() {
+        let mut ai = QLearning::new(
+            100,
+            HeuristicType::Absolute,
+            AIHeuristicMatrix::A,
+            1,
+            Cell::White,
+        );
+        ai.set_hyperparameters(QLearningHyperparameters {
+            opponent: TrainingOpponent::Snapshot,
+            ..QLearningHyperparameters::default()
+        });
+        let mut board = Board::new();
+        let (state, symmetry) = canonical_hash(&board);
+        ai.snapshot
+            .entry(state)
+            .or_default()
+            .insert(action_key(symmetry, (5, 4)), 100);
+
+        let mut step = 0;
+        ai.play_opponent_turns(&mut board, None, &mut step);
+
+        assert_eq!(step, 1);
+        assert_eq!(board.get_player_turn(), Cell::White);
+        assert_eq!(board.get_cell(5, 4).unwrap(), Cell::Black);
+    }
+
+    #[test]
+    fn test_hyperparameters_without_opponent_default_to_self_play() {
+        let json = r#"{"learning_rate":0.8,"discount":0.99,"epsilon":1.0,"epsilon_floor":0.01,"epsilon_decay":0.999,"epsilon_schedule":"Linear"}"#;
+        let params: QLearningHyperparameters = serde_json::from_str(json).unwrap();
+        assert_eq!(params.opponent, TrainingOpponent::SelfPlay);
+        assert_eq!(params.opponent_depth, QLEARNING_OPPONENT_DEPTH);
+        assert_eq!(params.update_rule, UpdateRule::QLearning);
+        assert_eq!(params.trace_decay, TRACE_DECAY);
+    }
+
+    #[test]
+    fn test_every_update_rule_learns_from_training_games() {
+        let mut rule = UpdateRule::QLearning;
+        loop {
+            let mut ai = create_test_ai();
+            ai.set_hyperparameters(QLearningHyperparameters {
+                update_rule: rule,
+                ..QLearningHyperparameters::default()
+            });
+            for _ in 0..3 {
+                let (_, game_over, _) = ai.q_learning();
+                assert!(game_over);
+            }
+            assert!(!ai.get_q_table().is_empty(), "{} learned nothing", rule);
+            assert_eq!(
+                ai.double_q_table.is_empty(),
+                rule != UpdateRule::DoubleQLearning
+            );
+
+            rule = rule.next();
+            if rule == UpdateRule::QLearning {
+                break;
+            }
+        }
+    }
+
+    /// Applies a move of the start position rewarded by `reward`, followed
+    /// by a move of the position it leads to rewarded by `next_reward`.
+    fn learn_two_moves(ai: &mut QLearning, reward: isize, next_reward: isize) -> String {
+        let mut traces = HashMap::new();
+        let mut board = Board::new();
+        let (state, symmetry) = canonical_hash(&board);
+        board.play_and_advance(2, 3).unwrap();
+        let (next_state, next_symmetry) = canonical_hash(&board);
+        let mut after = board.clone();
+        after.play_and_advance(2, 2).unwrap();
+        let (last_state, last_symmetry) = canonical_hash(&after);
+
+        ai.learn(
+            Transition {
+                state: state.clone(),
+                action: action_key(symmetry, (2, 3)),
+                mover: Cell::Black,
+                reward,
+                next: &board,
+                next_state: &next_state,
+                next_symmetry,
+                next_action: None,
+            },
+            &mut traces,
+        );
+        ai.learn(
+            Transition {
+                state: next_state,
+                action: action_key(next_symmetry, (2, 2)),
+                mover: Cell::White,
+                reward: next_reward,
+                next: &after,
+                next_state: &last_state,
+                next_symmetry: last_symmetry,
+                next_action: None,
+            },
+            &mut traces,
+        );
+        state
+    }
+
+    #[test]
+    fn test_td_lambda_credits_earlier_moves() {
+        let mut q_learning = create_test_ai();
+        let start = learn_two_moves(&mut q_learning, 0, 100);
+        assert_eq!(q_learning.get_q_table()[&start].values().sum::<isize>(), 0);
+
+        let mut td_lambda = create_test_ai();
+        td_lambda.set_hyperparameters(QLearningHyperparameters {
+            update_rule: UpdateRule::TdLambda,
+            learning_rate: 0.5,
+            discount: 1.0,
+            trace_decay: 0.5,
+            ..QLearningHyperparameters::default()
+        });
+        let start = learn_two_moves(&mut td_lambda, 0, 100);
+        // The error of the second move (100) reaches the first with a trace
+        // of 0.5, as a loss since the second move is the opponent's
+        assert_eq!(td_lambda.get_q_table()[&start].values().sum::<isize>(), -25);
+    }
+
+    #[test]
+    fn test_expected_sarsa_mixes_best_and_mean_values() {
+        let board = Board::new();
+        let (state, symmetry) = canonical_hash(&board);
+        let q_table = QTable::from([(
+            state.clone(),
+            HashMap::from([(action_key(symmetry, (2, 3)), 40)]),
+        )]);
+
+        // Four legal moves: 0.5 * 40 + 0.5 * (40 / 4)
+        let value = expected_value(q_table.get(&state), &board, symmetry, 0.5);
+        assert_eq!(value, 25.0);
+        assert_eq!(expected_value(None, &board, symmetry, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_double_q_learning_saves_and_plays_both_tables() {
+        let dir = temp_path("test_double_q_tables");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = format!("{}/double.bin", dir);
+        let mut ai = create_test_ai();
+        ai.set_hyperparameters(QLearningHyperparameters {
+            update_rule: UpdateRule::DoubleQLearning,
+            ..QLearningHyperparameters::default()
+        });
+        ai.q_learning();
+        ai.export_q_table(&file);
+
+        let mut loaded = create_test_ai();
+        let imported = loaded.import_q_table(&file);
+        let tables = list_q_tables(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        imported.unwrap();
+        assert_eq!(
+            loaded.get_hyperparameters().update_rule,
+            UpdateRule::DoubleQLearning
+        );
+        assert_eq!(loaded.double_q_table, ai.double_q_table);
+        assert_eq!(loaded.tables().len(), 2);
+        assert_eq!(tables.len(), 1);
+        assert!(tables[0].summary().ends_with("Double Q-learning"));
+    }
+
+    #[test]
+    fn test_greedy_action_sums_the_tables() {
+        let board = Board::new();
+        let (state, symmetry) = canonical_hash(&board);
+        let first = QTable::from([(
+            state.clone(),
+            HashMap::from([
+                (action_key(symmetry, (2, 3)), 10),
+                (action_key(symmetry, (3, 2)), 0),
+            ]),
+        )]);
+        let second = QTable::from([(state, HashMap::from([(action_key(symmetry, (3, 2)), 0)]))]);
+        let third = QTable::from([(
+            canonical_hash(&board).0,
+            HashMap::from([(action_key(symmetry, (3, 2)), 20)]),
+        )]);
+
+        assert_eq!(greedy_action(&[&first, &second], &board), Some((2, 3)));
+        assert_eq!(greedy_action(&[&first, &third], &board), Some((3, 2)));
+    }
+
+    #[test]
+    fn test_import_rejects_a_table_trained_for_the_other_colour() {
+        let mut ai = create_test_ai();
+        ai.set_hyperparameters(QLearningHyperparameters {
+            opponent: TrainingOpponent::Random,
+            ..QLearningHyperparameters::default()
+        });
+        let test_file = temp_path("test_q_table_black_only.json");
+        ai.export_q_table(&test_file);
+
+        let untagged_file = temp_path("untagged_q_table.bin");
+        save_q_table(&QTable::new(), &untagged_file).unwrap();
+
+        let result = create_alt_ai().import_q_table(&test_file);
+        let self_play = create_alt_ai().import_q_table(&untagged_file);
+        std::fs::remove_file(&test_file).ok();
+        std::fs::remove_file(QTableMetadata::path_for(&test_file)).ok();
+        std::fs::remove_file(&untagged_file).ok();
+
+        assert_eq!(
+            result,
+            Err("This Q-table was trained for BLACK".to_string())
+        );
+        // Tables without metadata can be played by either colour
+        assert!(self_play.is_ok());
+    }
+
+    #[test]
+    fn test_list_q_tables_reads_metadata() {
+        let dir = temp_path("test_list_q_tables");
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut ai = create_test_ai();
+        ai.set_epochs(42);
+        ai.export_q_table(&format!("{}/b.bin", dir));
+        std::fs::write(format!("{}/a.json", dir), "{}").unwrap();
+
+        let tables = list_q_tables(&dir);
+        std::fs::remove_dir_all(&dir).ok();
+
+        let paths: Vec<_> = tables.iter().map(|table| table.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                format!("{}/a.json", dir).as_str(),
+                format!("{}/b.bin", dir).as_str()
+            ]
+        );
+        assert!(tables[0].metadata.is_none());
+        let metadata = tables[1].metadata.as_ref().unwrap();
+        assert_eq!(metadata.epochs, 42);
+        assert_eq!(metadata.color, None);
+        assert!(metadata.trained_at.is_some());
+        assert!(tables[0].summary().ends_with("no training metadata"));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+    }
+
+    #[test]
+    fn test_set_color_moves_the_default_q_table_file() {
+        let mut ai = create_test_ai();
+        ai.set_color(Cell::White);
+        assert_eq!(
+            ai.get_q_table_file(),
+            Some(default_q_table_file(Cell::White))
+        );
+
+        ai.set_q_table_file(q_table_file_for("mine"));
+        ai.set_color(Cell::Black);
+        assert_eq!(ai.get_q_table_file(), Some(q_table_file_for("mine")));
+    }
+
+    #[test]
+    fn test_interrupted_training_resumes_where_it_stopped() {
+        let dir = temp_path("test_resume_q_tables");
+        let file = format!("{}/resume.json", dir);
+        let mut ai = QLearning::new(
+            100,
+            HeuristicType::Absolute,
+            AIHeuristicMatrix::A,
+            3,
+            Cell::Black,
+        );
+        ai.set_q_table_file(file.clone());
+
+        // Nobody listens to the progress, so the training stops after one epoch
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        ai.try_q_learning(tx);
+        let saved = QTableMetadata::load_for(&file).unwrap().unwrap();
+
+        let mut resumed = create_test_ai();
+        resumed.set_q_table_file(file.clone());
+        let resume = resumed.resume_training();
+        let (tx, rx) = mpsc::channel();
+        resumed.try_q_learning(tx);
+        let progress: Vec<usize> = rx.try_iter().map(|metrics| metrics.epoch).collect();
+        let finished = QTableMetadata::load_for(&file).unwrap().unwrap();
+        let mut again = create_test_ai();
+        again.set_q_table_file(file.clone());
+        let resume_finished = again.resume_training();
+        let log = std::fs::read_to_string(metrics_log_path(&file)).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(saved.epochs, 3);
+        assert_eq!(saved.completed_epochs, Some(1));
+        assert_eq!(saved.current_epsilon, Some(EPSILON));
+        resume.unwrap();
+        assert_eq!(resumed.get_epochs(), 3);
+        assert_eq!(progress, [2, 3]);
+        // The resumed training appends its epochs to the first run's log
+        let epochs: Vec<&str> = log
+            .lines()
+            .map(|line| line.split(',').next().unwrap())
+            .collect();
+        assert_eq!(epochs, ["epoch", "1", "2", "3"]);
+        // The last epoch is evaluated
+        assert!(log
+            .lines()
+            .last()
+            .unwrap()
+            .split(',')
+            .all(|field| !field.is_empty()));
+        assert_eq!(finished.completed_epochs, None);
+        assert!(resume_finished.unwrap_err().contains("already completed"));
+    }
+
+    #[test]
+    fn test_evaluation_plays_every_opponent_with_both_colours() {
+        let results = create_test_ai().evaluate(2);
+
+        let opponents: Vec<_> = results.iter().map(|result| result.opponent).collect();
+        assert_eq!(opponents, EVALUATION_OPPONENTS);
+        for result in &results {
+            assert_eq!(result.wins + result.draws + result.losses, 2);
+        }
+    }
+
+    #[test]
+    fn test_cancelled_training_saves_without_playing() {
+        let dir = temp_path("test_cancel_q_tables");
+        let file = format!("{}/cancel.json", dir);
+        let mut ai = create_test_ai();
+        ai.set_q_table_file(file.clone());
+        let cancel = CancelToken::new();
+        ai.set_cancel_token(cancel.clone());
+        cancel.cancel();
+
+        let (tx, rx) = mpsc::channel();
+        ai.try_q_learning(tx);
+        let saved = QTableMetadata::load_for(&file).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(rx.try_recv().is_err());
+        assert!(ai.get_q_table().is_empty());
+        assert_eq!(saved.unwrap().completed_epochs, Some(0));
+    }
+
+    #[test]
+    fn test_metrics_log_errors_are_sent_with_the_metrics() {
+        let dir = temp_path("test_log_error_q_tables");
+        let file = format!("{}/log_error.json", dir);
+        // A directory in place of the log cannot be opened as a file
+        std::fs::create_dir_all(metrics_log_path(&file)).unwrap();
+        let mut ai = QLearning::new(
+            100,
+            HeuristicType::Absolute,
+            AIHeuristicMatrix::A,
+            1,
+            Cell::Black,
+        );
+        ai.set_q_table_file(file.clone());
+
+        let (tx, rx) = mpsc::channel();
+        ai.try_q_learning(tx);
+        let saved = std::fs::metadata(&file).is_ok();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let metrics: Vec<_> = rx.try_iter().collect();
+        assert_eq!(metrics.len(), 1);
+        assert!(metrics[0]
+            .status
+            .as_ref()
+            .unwrap()
+            .starts_with("Could not open the metrics log"));
+        assert!(saved);
+    }
+
+    /// Returns a position a few empty squares before the end of a game,
+    /// reached by always playing the first legal move.
+    fn endgame(empty_squares: usize) -> Board {
+        let mut board = Board::new();
+        while board.to_hash().matches('0').count() > empty_squares {
+            let actions = board.has_legal_moves(board.get_player_turn()).unwrap();
+            board.play_and_advance(actions[0].0, actions[0].1).unwrap();
+        }
+        assert!(!board.check_game_over());
+        board
+    }
+
+    /// Returns the exact value of each legal action of a position, by
+    /// negamax over the whole game tree with the training rewards and no
+    /// discount.
+    fn true_values(ai: &QLearning, board: &Board) -> Vec<((usize, usize), f64)> {
+        let mover = board.get_player_turn();
+        board
+            .has_legal_moves(mover)
+            .unwrap()
+            .into_iter()
+            .map(|action| {
+                let mut next = board.clone();
+                next.try_play_move(action.0, action.1, mover).unwrap();
+                let reward = ai.reward(&mut next, mover) as f64;
+                if next.check_game_over() {
+                    return (action, reward);
+                }
+                next.next_turn();
+                let next_value = true_values(ai, &next)
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .fold(f64::NEG_INFINITY, f64::max);
+                if next.get_player_turn() == mover {
+                    (action, reward + next_value)
+                } else {
+                    (action, reward - next_value)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns an endgame where Black chooses between three moves, each
+    /// followed by forced moves only (passes included) until the end, four
+    /// plies later at most. Every policy thus gets the same action values,
+    /// so on-policy and off-policy rules must all learn the exact ones: with
+    /// a learning rate of 1, the targets are exact integers and no rounding
+    /// error builds up.
+    fn forced_endgame() -> Board {
+        Board::from_hash("B0222222222122222212121222212222222212122222212222222222220211002")
+            .unwrap()
+    }
+
+    /// Trains a self-play AI from `start` with uniformly random moves, and
+    /// checks the learned values of its first position against the exact ones.
+    fn assert_converges(
+        update_rule: UpdateRule,
+        start: &Board,
+        learning_rate: f64,
+        epochs: usize,
+        tolerance: f64,
+    ) {
+        let mut ai = create_test_ai();
+        ai.set_hyperparameters(QLearningHyperparameters {
+            learning_rate,
+            discount: 1.0,
+            epsilon: 1.0,
+            epsilon_floor: 1.0,
+            update_rule,
+            ..QLearningHyperparameters::default()
+        });
+        for _ in 0..epochs {
+            ai.q_learning_from(start.clone());
+        }
+
+        let (state, symmetry) = canonical_hash(start);
+        for (action, value) in true_values(&ai, start) {
+            let learned = ai
+                .tables()
+                .iter()
+                .map(|table| table[&state][&action_key(symmetry, action)] as f64)
+                .sum::<f64>()
+                / ai.tables().len() as f64;
+            assert!(
+                (learned - value).abs() <= tolerance,
+                "{}: {:?} learned {} instead of {}",
+                update_rule,
+                action,
+                learned,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_q_learning_converges_to_the_game_values_of_an_endgame() {
+        // Values are stored as integers, each level can lose one unit
+        assert_converges(UpdateRule::QLearning, &endgame(5), 0.5, 3000, 5.0);
+    }
+
+    #[test]
+    fn test_double_q_learning_converges_to_the_game_values_of_an_endgame() {
+        assert_converges(UpdateRule::DoubleQLearning, &endgame(5), 0.5, 6000, 5.0);
+    }
+
+    #[test]
+    fn test_forced_endgame_has_a_choice_then_forced_moves() {
+        let board = forced_endgame();
+        let ai = create_test_ai();
+        let values = true_values(&ai, &board);
+        assert_eq!(values.len(), 3);
+        // The choice matters
+        assert!(values.iter().any(|(_, value)| *value != values[0].1));
+        for ((row, col), _) in values {
+            let mut next = board.clone();
+            next.play_and_advance(row, col).unwrap();
+            let mut plies = 1;
+            while !next.is_game_over() {
+                let actions = next.has_legal_moves(next.get_player_turn()).unwrap();
+                assert_eq!(actions.len(), 1);
+                next.play_and_advance(actions[0].0, actions[0].1).unwrap();
+                plies += 1;
+            }
+            assert!(plies <= 4);
+        }
+    }
+
+    #[test]
+    fn test_q_learning_learns_the_exact_values_of_a_forced_endgame() {
+        assert_converges(UpdateRule::QLearning, &forced_endgame(), 1.0, 200, 0.0);
+    }
+
+    #[test]
+    fn test_sarsa_learns_the_exact_values_of_a_forced_endgame() {
+        assert_converges(UpdateRule::Sarsa, &forced_endgame(), 1.0, 200, 0.0);
+    }
+
+    #[test]
+    fn test_expected_sarsa_learns_the_exact_values_of_a_forced_endgame() {
+        assert_converges(UpdateRule::ExpectedSarsa, &forced_endgame(), 1.0, 200, 0.0);
+    }
+
+    #[test]
+    fn test_double_q_learning_learns_the_exact_values_of_a_forced_endgame() {
+        assert_converges(
+            UpdateRule::DoubleQLearning,
+            &forced_endgame(),
+            1.0,
+            400,
+            0.0,
+        );
+    }
+
+    #[test]
+    fn test_td_lambda_learns_the_exact_values_of_a_forced_endgame() {
+        assert_converges(UpdateRule::TdLambda, &forced_endgame(), 1.0, 200, 0.0);
+    }
+
+    #[test]
+    fn test_next_state_value_counts_against_the_mover_when_the_opponent_moves() {
+        let mut board = Board::new();
+        let (state, symmetry) = canonical_hash(&board);
+        board.play_and_advance(2, 3).unwrap();
+        let (next_state, next_symmetry) = canonical_hash(&board);
+
+        let mut learned = Vec::new();
+        for mover in [Cell::Black, Cell::White] {
+            let mut ai = create_test_ai();
+            ai.set_hyperparameters(QLearningHyperparameters {
+                learning_rate: 1.0,
+                discount: 1.0,
+                ..QLearningHyperparameters::default()
+            });
+            ai.set_q_table(next_state.clone(), ("2C".to_string(), 100));
+            ai.learn(
+                Transition {
+                    state: state.clone(),
+                    action: action_key(symmetry, (2, 3)),
+                    mover,
+                    reward: 10,
+                    next: &board,
+                    next_state: &next_state,
+                    next_symmetry,
+                    next_action: None,
+                },
+                &mut HashMap::new(),
+            );
+            learned.push(ai.get_q_table()[&state][&action_key(symmetry, (2, 3))]);
+        }
+
+        // White moves next: its 100 is lost by Black, and gained by White
+        // when it plays again after a pass
+        assert_eq!(learned, [10 - 100, 10 + 100]);
+    }
 }
@@ -0,0 +1,158 @@
+//! Multi-PV analysis of a position.
+//!
+//! The search AIs score every legal root move to choose the one they play.
+//! An `Analysis` keeps all of these scores, ranked from best to worst, so the
+//! alternatives to the played move can be inspected: the game screen overlays
+//! them on the candidate squares.
+
+use std::{cmp::Reverse, time::Duration};
+
+use crate::{
+    ai::{
+        action::Action,
+        search_info::{SearchCounters, SearchInfo},
+    },
+    game::{board::Board, cell::Cell, history_action::HistoryAction},
+};
+
+/// A root move with its score and the line the search expects after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnalyzedMove {
+    /// The root move and its score
+    pub action: Action,
+    /// Principal variation of the move, starting with the move itself
+    pub line: Vec<(usize, usize)>,
+}
+
+/// Scores of every legal move of a position, best first.
+///
+/// # Examples
+///
+/// ```rust
+/// let analysis = ai.analyze(&Board::new())?;
+/// for analyzed in &analysis.moves {
+///     println!("{:?}: {}", analyzed.action.pos, analyzed.action.score);
+/// }
+/// println!("{}", analysis.info);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    /// Every legal root move, ranked from best to worst
+    pub moves: Vec<AnalyzedMove>,
+    /// Statistics of the whole search, with the line of the best move
+    pub info: SearchInfo,
+}
+
+impl Analysis {
+    /// Creates the analysis of a search.
+    ///
+    /// # Arguments
+    ///
+    /// * `moves` - The root moves, already ranked from best to worst
+    /// * `counters` - Counters of all the search threads
+    /// * `time` - Time taken by the search
+    pub fn new(moves: Vec<AnalyzedMove>, counters: SearchCounters, time: Duration) -> Self {
+        let (best_score, pv) = match moves.first() {
+            Some(best) => (
+                best.action.score,
+                best.line
+                    .iter()
+                    .map(|pos| Board::coordinates_to_input(pos.0, pos.1))
+                    .collect(),
+            ),
+            None => (0, vec![]),
+        };
+        Self {
+            info: SearchInfo::new(counters.nodes, time, counters.max_ply, best_score, pv),
+            moves,
+        }
+    }
+
+    /// Ranks root moves by decreasing score.
+    ///
+    /// The sort is stable, so among equal scores the first generated move
+    /// stays first, as it did before the analysis existed.
+    pub fn rank_by_score(moves: &mut [AnalyzedMove]) {
+        moves.sort_by_key(|analyzed| Reverse(analyzed.action.score));
+    }
+
+    /// Returns the best ranked move, if the position has a legal move.
+    pub fn best(&self) -> Option<&AnalyzedMove> {
+        self.moves.first()
+    }
+
+    /// Plays the best ranked move on `board` for `color`.
+    ///
+    /// This is how the search AIs end their turn, and how the game screen
+    /// plays a move that was analyzed in advance while pondering.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The analyzed position, updated with the move
+    /// * `color` - The color of the player to move
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HistoryAction)` - The played move, with the statistics of the analysis
+    /// * `Err(String)` - If there is no legal move or the move cannot be played
+    pub fn play_best(self, board: &mut Board, color: Cell) -> Result<HistoryAction, String> {
+        let pos = match self.best() {
+            Some(best) => best.action.pos,
+            None => return Err("No legal move available".to_string()),
+        };
+        match board.try_play_move(pos.0, pos.1, color) {
+            Ok(gained_discs) => Ok(HistoryAction {
+                coordinates: Some(Board::coordinates_to_input(pos.0, pos.1)),
+                gained_discs: Some(gained_discs),
+                color,
+                player_turn: board.get_player_turn(),
+                move_number: board.get_turn_number(),
+                from_book: false,
+                search_info: Some(self.info),
+            }),
+            Err(e) => Err(format!("Error playing move: {}", e)),
+        }
+    }
+
+    /// Returns the score of a root move, if it was analyzed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The board position (row, column) of the move
+    pub fn score_of(&self, pos: (usize, usize)) -> Option<isize> {
+        self.moves
+            .iter()
+            .find(|analyzed| analyzed.action.pos == pos)
+            .map(|analyzed| analyzed.action.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyzed(pos: (usize, usize), score: isize) -> AnalyzedMove {
+        AnalyzedMove {
+            action: Action { pos, score },
+            line: vec![pos, (0, 0)],
+        }
+    }
+
+    #[test]
+    fn test_ranking_keeps_first_of_equal_scores() {
+        let mut moves = vec![
+            analyzed((2, 3), 4),
+            analyzed((3, 2), 9),
+            analyzed((4, 5), 9),
+        ];
+        Analysis::rank_by_score(&mut moves);
+        let analysis = Analysis::new(moves, SearchCounters::default(), Duration::ZERO);
+
+        assert_eq!(analysis.best().unwrap().action.pos, (3, 2));
+        assert_eq!(analysis.moves[1].action.pos, (4, 5));
+        assert_eq!(analysis.score_of((2, 3)), Some(4));
+        assert_eq!(analysis.score_of((7, 7)), None);
+        assert_eq!(analysis.info.best_score, 9);
+        assert_eq!(analysis.info.pv, vec!["3C".to_string(), "0A".to_string()]);
+    }
+}
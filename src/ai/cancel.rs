@@ -0,0 +1,65 @@
+//! Cancellation of running AI searches.
+//!
+//! A search started in the background can take a long time at high depth.
+//! The search AIs hold a `CancelToken` and check it while they visit the
+//! tree, so the interface can stop them within milliseconds when the player
+//! quits or leaves the game screen.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Error returned by a search stopped through its `CancelToken`.
+pub const SEARCH_CANCELLED: &str = "Search cancelled";
+
+/// Shared flag telling a running search to stop.
+///
+/// Clones share the same flag: the interface keeps one clone and gives the
+/// other to the AI, whose search threads clone it again.
+///
+/// # Examples
+///
+/// ```rust
+/// let token = CancelToken::new();
+/// let search_token = token.clone();
+/// token.cancel();
+/// assert!(search_token.is_cancelled());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    /// Set once the search must stop
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every search holding a clone of this token to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Tells whether the search must stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_flag() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert!(!CancelToken::new().is_cancelled());
+    }
+}
@@ -0,0 +1,533 @@
+//! Weighted evaluation built from named features.
+//!
+//! The fixed heuristics mix terms whose scales differ a lot: a matrix score
+//! runs in the hundreds while a mobility difference stays around ten. An
+//! evaluation profile gives every feature its own weight for the opening,
+//! the middle game and the endgame, and interpolates the weights linearly
+//! with the number of discs on the board, so the evaluation changes smoothly
+//! as the game goes on.
+//!
+//! Profiles are JSON files, so the evaluation can be tuned without
+//! recompiling:
+//!
+//! ```json
+//! {
+//!   "name": "My profile",
+//!   "weights": {
+//!     "matrix": { "opening": 1.0, "midgame": 0.5, "endgame": 0.0 },
+//!     "discs": { "opening": 0.0, "midgame": 0.0, "endgame": 20.0 }
+//!   }
+//! }
+//! ```
+//!
+//! A profile may also carry its own heuristic matrix, in the format of the
+//! matrix files, under `"matrix"`. The `Matrix` feature then reads it
+//! instead of the matrix chosen for the player, so a tuned profile keeps
+//! the matrix it was tuned with.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs::{self, File},
+    io::Write,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{
+        heuristic::{matrix_score, HeuristicType},
+        heuristic_matrix::{AIHeuristicMatrix, CustomMatrix},
+    },
+    consts::{EVAL_PROFILE_FILE, SIZE},
+    game::{board::Board, cell::Cell},
+};
+
+/// A term of the weighted evaluation.
+///
+/// Every feature is the difference between the player and the opponent
+/// computed by the matching `HeuristicType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// Disc count difference
+    Discs,
+    /// Sum of the heuristic matrix values of the player's discs
+    Matrix,
+    /// Legal move count difference
+    Mobility,
+    /// Empty cells next to opponent discs
+    PotentialMobility,
+    /// Frontier discs, fewer scoring higher
+    Frontier,
+    /// Stable disc difference
+    Stability,
+    /// Odd empty regions where the player should get the last move
+    Parity,
+}
+
+impl Display for Feature {
+    /// Formats the feature with its name in profile files.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Feature::Discs => write!(f, "discs"),
+            Feature::Matrix => write!(f, "matrix"),
+            Feature::Mobility => write!(f, "mobility"),
+            Feature::PotentialMobility => write!(f, "potential_mobility"),
+            Feature::Frontier => write!(f, "frontier"),
+            Feature::Stability => write!(f, "stability"),
+            Feature::Parity => write!(f, "parity"),
+        }
+    }
+}
+
+impl Feature {
+    /// Every feature, in profile order.
+    pub const ALL: [Feature; 7] = [
+        Feature::Discs,
+        Feature::Matrix,
+        Feature::Mobility,
+        Feature::PotentialMobility,
+        Feature::Frontier,
+        Feature::Stability,
+        Feature::Parity,
+    ];
+
+    /// Returns the heuristic computing this feature.
+    pub fn heuristic(&self) -> HeuristicType {
+        match self {
+            Feature::Discs => HeuristicType::Absolute,
+            Feature::Matrix => HeuristicType::Matrix,
+            Feature::Mobility => HeuristicType::Mobility,
+            Feature::PotentialMobility => HeuristicType::PotentialMobility,
+            Feature::Frontier => HeuristicType::Frontier,
+            Feature::Stability => HeuristicType::Stability,
+            Feature::Parity => HeuristicType::Parity,
+        }
+    }
+
+    /// Computes the feature for a player.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The current board state
+    /// * `player` - The player from whose perspective to evaluate
+    /// * `matrix` - The heuristic matrix, read by the `Matrix` feature only
+    pub fn value(&self, board: &Board, player: Cell, matrix: AIHeuristicMatrix) -> isize {
+        self.heuristic().evaluate(board, player, matrix)
+    }
+}
+
+/// Weights of a feature at the three reference points of the game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseWeights {
+    /// Weight with the 4 starting discs on the board
+    pub opening: f64,
+    /// Weight with the board half full
+    pub midgame: f64,
+    /// Weight with the board full
+    pub endgame: f64,
+}
+
+impl PhaseWeights {
+    /// Creates phase weights.
+    pub fn new(opening: f64, midgame: f64, endgame: f64) -> Self {
+        Self {
+            opening,
+            midgame,
+            endgame,
+        }
+    }
+
+    /// Returns the weight at a game phase.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - 0.0 at the start of the game, 1.0 with a full board
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let weights = PhaseWeights::new(0.0, 10.0, 30.0);
+    /// assert_eq!(weights.at(0.25), 5.0);
+    /// assert_eq!(weights.at(0.75), 20.0);
+    /// ```
+    pub fn at(&self, phase: f64) -> f64 {
+        let [opening, midgame, endgame] = Self::coefficients(phase);
+        opening * self.opening + midgame * self.midgame + endgame * self.endgame
+    }
+
+    /// Returns how much the opening, midgame and endgame weights count at a
+    /// game phase.
+    ///
+    /// The weight at a phase is linear in the three weights, with these
+    /// coefficients, which add up to 1.
+    pub fn coefficients(phase: f64) -> [f64; 3] {
+        let phase = phase.clamp(0.0, 1.0);
+        if phase < 0.5 {
+            [1.0 - phase * 2.0, phase * 2.0, 0.0]
+        } else {
+            [0.0, 2.0 - phase * 2.0, phase * 2.0 - 1.0]
+        }
+    }
+
+    /// Tells whether the feature counts at some point of the game.
+    pub fn is_zero(&self) -> bool {
+        self.opening == 0.0 && self.midgame == 0.0 && self.endgame == 0.0
+    }
+}
+
+/// A named set of feature weights, loaded from a JSON file.
+///
+/// # Examples
+///
+/// ```rust
+/// let profile = EvalProfile::default();
+/// let score = profile.evaluate(&Board::new(), Cell::Black, AIHeuristicMatrix::A);
+/// profile.save("eval_profile.json").unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvalProfile {
+    /// Name shown when the profile is used
+    pub name: String,
+    /// Phase weights of the features, missing features weigh nothing
+    pub weights: BTreeMap<Feature, PhaseWeights>,
+    /// Matrix of the `Matrix` feature, replacing the player's matrix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<CustomMatrix>,
+}
+
+impl Default for EvalProfile {
+    /// The built-in profile, used when no profile file is found.
+    ///
+    /// Position matters most in the opening, mobility in the middle game,
+    /// and discs, stability and parity in the endgame. The weights bring the
+    /// features to the scale of the matrix values.
+    fn default() -> Self {
+        let weights = BTreeMap::from([
+            (Feature::Discs, PhaseWeights::new(0.0, 1.0, 20.0)),
+            (Feature::Matrix, PhaseWeights::new(1.0, 0.5, 0.0)),
+            (Feature::Mobility, PhaseWeights::new(10.0, 15.0, 5.0)),
+            (Feature::PotentialMobility, PhaseWeights::new(5.0, 5.0, 0.0)),
+            (Feature::Frontier, PhaseWeights::new(5.0, 5.0, 0.0)),
+            (Feature::Stability, PhaseWeights::new(10.0, 20.0, 30.0)),
+            (Feature::Parity, PhaseWeights::new(0.0, 0.0, 30.0)),
+        ]);
+        Self {
+            name: "Default".to_string(),
+            weights,
+            matrix: None,
+        }
+    }
+}
+
+impl EvalProfile {
+    /// Loads a profile from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the JSON file containing the profile
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(EvalProfile)` - If the profile was successfully loaded
+    /// * `Err(String)` - If there was an error loading or parsing the file
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        match File::open(file_path) {
+            Ok(file) => serde_json::from_reader(file)
+                .map_err(|e| format!("Could not deserialize evaluation profile: {}", e)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Saves the profile to a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path where the profile should be saved
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the profile was written
+    /// * `Err(String)` - If the file could not be created or written
+    pub fn save(&self, file_path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize evaluation profile: {}", e))?;
+        let mut file = File::create(file_path).map_err(|e| e.to_string())?;
+        file.write_all(json.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Returns the game phase of a position, from the number of discs.
+    ///
+    /// The phase goes from 0.0 with the 4 starting discs to 1.0 with a full
+    /// board. Passes do not move the phase, unlike the turn number.
+    pub fn phase(board: &Board) -> f64 {
+        let discs = board.get_nb_discs(Cell::Black).unwrap_or(0)
+            + board.get_nb_discs(Cell::White).unwrap_or(0);
+        (discs.saturating_sub(4)) as f64 / (SIZE * SIZE - 4) as f64
+    }
+
+    /// Tells whether the evaluation reads the heuristic matrix of the player.
+    ///
+    /// A profile carrying its own matrix never does.
+    pub fn uses_matrix(&self) -> bool {
+        self.matrix.is_none()
+            && self
+                .weights
+                .get(&Feature::Matrix)
+                .is_some_and(|weights| !weights.is_zero())
+    }
+
+    /// Evaluates a position as the weighted sum of the features.
+    ///
+    /// Features weighing nothing at the current phase are not computed.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The current board state to evaluate
+    /// * `player` - The player from whose perspective to evaluate the position
+    /// * `matrix` - The heuristic matrix used by the `Matrix` feature, unless
+    ///   the profile has its own
+    ///
+    /// # Returns
+    ///
+    /// The rounded score, positive values favoring the player.
+    pub fn evaluate(&self, board: &Board, player: Cell, matrix: AIHeuristicMatrix) -> isize {
+        let phase = Self::phase(board);
+        let score: f64 = self
+            .weights
+            .iter()
+            .map(|(feature, weights)| (feature, weights.at(phase)))
+            .filter(|(_, weight)| *weight != 0.0)
+            .map(|(feature, weight)| {
+                weight * self.feature_value(*feature, board, player, &matrix) as f64
+            })
+            .sum();
+        score.round() as isize
+    }
+
+    /// Computes a feature as the evaluation sees it.
+    ///
+    /// This is `Feature::value`, except that the `Matrix` feature reads the
+    /// matrix of the profile when it has one.
+    pub fn feature_value(
+        &self,
+        feature: Feature,
+        board: &Board,
+        player: Cell,
+        matrix: &AIHeuristicMatrix,
+    ) -> isize {
+        match (feature, &self.matrix) {
+            (Feature::Matrix, Some(own)) => matrix_score(board, player, &own.values),
+            _ => feature.value(board, player, matrix.clone()),
+        }
+    }
+}
+
+/// Returns the profile loaded from `EVAL_PROFILE_FILE`.
+///
+/// The file is read once, the first time a weighted evaluation is selected.
+/// When it is missing or invalid the built-in profile is used instead.
+pub fn shared_profile() -> Arc<EvalProfile> {
+    static PROFILE: OnceLock<Arc<EvalProfile>> = OnceLock::new();
+    PROFILE
+        .get_or_init(|| Arc::new(EvalProfile::load(EVAL_PROFILE_FILE).unwrap_or_default()))
+        .clone()
+}
+
+/// Returns the profile of a file, loaded once per path.
+///
+/// Every call with the same path gives the same `Arc`, so a profile picked
+/// on the setup screens is recognised by its file, even when two files hold
+/// equal profiles.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the JSON file of the profile
+///
+/// # Returns
+///
+/// * `Ok(Arc<EvalProfile>)` - The profile of the file
+/// * `Err(String)` - If the file could not be read or is not a valid profile
+pub fn load_profile_file(file_path: &str) -> Result<Arc<EvalProfile>, String> {
+    static PROFILES: OnceLock<Mutex<HashMap<String, Arc<EvalProfile>>>> = OnceLock::new();
+    let mut profiles = PROFILES
+        .get_or_init(Default::default)
+        .lock()
+        .map_err(|e| e.to_string())?;
+    if let Some(profile) = profiles.get(file_path) {
+        return Ok(profile.clone());
+    }
+    let profile = Arc::new(EvalProfile::load(file_path)?);
+    profiles.insert(file_path.to_string(), profile.clone());
+    Ok(profile)
+}
+
+/// A profile a player can pick, with the file it was loaded from.
+#[derive(Clone, Debug)]
+pub struct ProfileFile {
+    /// Path of the profile file
+    pub path: String,
+    /// The profile, shared by every player that picked this file
+    pub profile: Arc<EvalProfile>,
+}
+
+/// Lists the profiles a player can pick for the `Weighted` heuristic.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the profile files
+///
+/// # Returns
+///
+/// The shared profile (see [`shared_profile`]), then the profile of every
+/// `.json` file of `dir` in file name order. Files that are not valid
+/// profiles are skipped, and a missing directory adds no profile.
+pub fn list_profiles(dir: &str) -> Vec<ProfileFile> {
+    let mut paths: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .map(|path| path.to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    let mut profiles = vec![ProfileFile {
+        path: EVAL_PROFILE_FILE.to_string(),
+        profile: shared_profile(),
+    }];
+    profiles.extend(paths.into_iter().filter_map(|path| {
+        load_profile_file(&path)
+            .ok()
+            .map(|profile| ProfileFile { path, profile })
+    }));
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_weights_interpolation() {
+        let weights = PhaseWeights::new(10.0, 20.0, 0.0);
+        assert_eq!(weights.at(0.0), 10.0);
+        assert_eq!(weights.at(0.25), 15.0);
+        assert_eq!(weights.at(0.5), 20.0);
+        assert_eq!(weights.at(0.75), 10.0);
+        assert_eq!(weights.at(1.0), 0.0);
+        assert_eq!(weights.at(2.0), 0.0);
+    }
+
+    #[test]
+    fn test_phase_follows_disc_count() {
+        let mut board = Board::new();
+        assert_eq!(EvalProfile::phase(&board), 0.0);
+        board.play_and_advance(2, 3).unwrap();
+        assert_eq!(EvalProfile::phase(&board), 1.0 / 60.0);
+    }
+
+    #[test]
+    fn test_single_feature_profile_matches_heuristic() {
+        let board = {
+            let mut board = Board::new();
+            board.play_and_advance(2, 3).unwrap();
+            board.play_and_advance(2, 2).unwrap();
+            board
+        };
+        let profile = EvalProfile {
+            name: "Mobility".to_string(),
+            weights: BTreeMap::from([(Feature::Mobility, PhaseWeights::new(3.0, 3.0, 3.0))]),
+            matrix: None,
+        };
+        assert!(!profile.uses_matrix());
+        for player in [Cell::Black, Cell::White] {
+            assert_eq!(
+                profile.evaluate(&board, player, AIHeuristicMatrix::A),
+                3 * HeuristicType::Mobility.evaluate(&board, player, AIHeuristicMatrix::A)
+            );
+        }
+    }
+
+    #[test]
+    fn test_profile_file_round_trip() {
+        let path = std::env::temp_dir().join("othello_test_eval_profile.json");
+        let path = path.to_str().unwrap();
+        let profile = EvalProfile::default();
+        profile.save(path).unwrap();
+        assert_eq!(EvalProfile::load(path).unwrap(), profile);
+        std::fs::remove_file(path).unwrap();
+
+        assert!(EvalProfile::load("nonexistent_profile.json").is_err());
+        let partial: EvalProfile =
+            serde_json::from_str(r#"{"name": "Discs", "weights": {"discs": {"opening": 0.0, "midgame": 1.0, "endgame": 1.0}}}"#)
+                .unwrap();
+        assert_eq!(partial.weights.len(), 1);
+        assert!(serde_json::from_str::<EvalProfile>(r#"{"name": "Bad", "weights": {"corners": {"opening": 1.0, "midgame": 1.0, "endgame": 1.0}}}"#).is_err());
+    }
+
+    #[test]
+    fn test_list_profiles_of_directory() {
+        let dir = std::env::temp_dir().join("othello_test_profiles");
+        let dir = dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(dir);
+        let profiles = list_profiles(dir);
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].path, EVAL_PROFILE_FILE);
+
+        fs::create_dir_all(dir).unwrap();
+        let profile = |name: &str| EvalProfile {
+            name: name.to_string(),
+            ..EvalProfile::default()
+        };
+        profile("Second").save(&format!("{}/b.json", dir)).unwrap();
+        profile("First").save(&format!("{}/a.json", dir)).unwrap();
+        profile("First").save(&format!("{}/c.json", dir)).unwrap();
+        fs::write(format!("{}/broken.json", dir), "{\"name\": \"Broken\"}").unwrap();
+
+        let profiles = list_profiles(dir);
+        let names: Vec<&str> = profiles[1..]
+            .iter()
+            .map(|file| file.profile.name.as_str())
+            .collect();
+        assert_eq!(names, ["First", "Second", "First"]);
+
+        // Equal profiles of different files stay apart, the same file gives the same profile
+        assert_eq!(profiles[1].profile, profiles[3].profile);
+        assert!(!Arc::ptr_eq(&profiles[1].profile, &profiles[3].profile));
+        let again = list_profiles(dir);
+        assert!(Arc::ptr_eq(&profiles[3].profile, &again[3].profile));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_profile_with_its_own_matrix() {
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+        let mut profile = EvalProfile {
+            name: "Matrix B".to_string(),
+            weights: BTreeMap::from([(Feature::Matrix, PhaseWeights::new(1.0, 1.0, 1.0))]),
+            matrix: None,
+        };
+        assert!(profile.uses_matrix());
+
+        let values = AIHeuristicMatrix::B
+            .value()
+            .map(|row| row.to_vec())
+            .to_vec();
+        profile.matrix = Some(CustomMatrix::new("B", values).unwrap());
+        assert!(!profile.uses_matrix());
+        assert_eq!(
+            profile.evaluate(&board, Cell::Black, AIHeuristicMatrix::A),
+            HeuristicType::Matrix.evaluate(&board, Cell::Black, AIHeuristicMatrix::B)
+        );
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(serde_json::from_str::<EvalProfile>(&json).unwrap(), profile);
+        let asymmetric = json.replacen("500", "499", 1);
+        assert!(serde_json::from_str::<EvalProfile>(&asymmetric).is_err());
+    }
+}
@@ -5,10 +5,13 @@
 //! aspects of the game such as piece count, position value, mobility, or
 //! combinations thereof.
 
-use std::fmt::Display;
+use std::{fmt::Display, sync::Arc};
 
+use crate::ai::eval_profile::{shared_profile, EvalProfile};
 use crate::ai::heuristic_matrix::AIHeuristicMatrix;
-use crate::consts::SIZE;
+use crate::ai::network::{shared_network, ValueNetwork};
+use crate::ai::pattern::{shared_pattern_weights, PatternWeights};
+use crate::consts::{SIZE, STABILITY_WEIGHT};
 use crate::game::{board::Board, cell::Cell};
 
 /// Enumeration of available heuristic evaluation functions.
@@ -19,6 +22,13 @@ use crate::game::{board::Board, cell::Cell};
 /// - **Absolute**: Simple piece count difference
 /// - **Matrix**: Position-based evaluation using strategic matrices
 /// - **Mobility**: Move availability and tactical flexibility
+/// - **Stability**: Discs that can never be flipped again
+/// - **Frontier**: Discs next to empty cells, which give the opponent moves
+/// - **PotentialMobility**: Empty cells next to opponent discs
+/// - **Parity**: Which player gets the last move in each empty region
+/// - **Weighted**: Features weighted by game phase, from an evaluation profile
+/// - **Pattern**: Learned weights of edge, corner, diagonal and row patterns
+/// - **Network**: Small neural network trained by self-play
 /// - **Mixte**: Adaptive strategy that changes based on game phase
 /// - **Global**: Combined evaluation using multiple heuristics
 ///
@@ -45,6 +55,20 @@ pub enum HeuristicType {
     Mixte,
     /// Global evaluation combining all heuristic types
     Global,
+    /// Stable disc difference (discs that can never be flipped)
+    Stability,
+    /// Frontier disc difference, fewer frontier discs scoring higher
+    Frontier,
+    /// Potential mobility difference (empty cells next to opponent discs)
+    PotentialMobility,
+    /// Region parity, meant for the endgame
+    Parity,
+    /// Phase-weighted sum of features, see `EvalProfile`
+    Weighted(Arc<EvalProfile>),
+    /// Learned pattern weights predicting the final disc difference
+    Pattern(Arc<PatternWeights>),
+    /// Neural network predicting the expected score of the game
+    Network(Arc<ValueNetwork>),
 }
 
 impl Display for HeuristicType {
@@ -67,6 +91,13 @@ impl Display for HeuristicType {
             HeuristicType::Mobility => write!(f, "Mobility"),
             HeuristicType::Mixte => write!(f, "Mixte"),
             HeuristicType::Global => write!(f, "Global"),
+            HeuristicType::Stability => write!(f, "Stability"),
+            HeuristicType::Frontier => write!(f, "Frontier"),
+            HeuristicType::PotentialMobility => write!(f, "Potential mobility"),
+            HeuristicType::Parity => write!(f, "Parity"),
+            HeuristicType::Weighted(_) => write!(f, "Weighted"),
+            HeuristicType::Pattern(_) => write!(f, "Pattern"),
+            HeuristicType::Network(_) => write!(f, "Network"),
         }
     }
 }
@@ -110,7 +141,33 @@ impl HeuristicType {
                 heuristic_absolute(board, player)
                     + heuristic_matrix(board, player, &matrix)
                     + heuristic_mobility(board, player)
+                    + STABILITY_WEIGHT * heuristic_stability(board, player)
             }
+            HeuristicType::Stability => heuristic_stability(board, player),
+            HeuristicType::Frontier => heuristic_frontier(board, player),
+            HeuristicType::PotentialMobility => heuristic_potential_mobility(board, player),
+            HeuristicType::Parity => heuristic_parity(board, player),
+            HeuristicType::Weighted(profile) => profile.evaluate(board, player, matrix),
+            HeuristicType::Pattern(weights) => weights.evaluate(board, player),
+            HeuristicType::Network(network) => network.evaluate(board, player),
+        }
+    }
+
+    /// Tells whether the evaluation reads the heuristic matrix.
+    ///
+    /// The setup screens gray out the matrix choice for the other heuristics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert!(HeuristicType::Mixte.uses_matrix());
+    /// assert!(!HeuristicType::Stability.uses_matrix());
+    /// ```
+    pub fn uses_matrix(&self) -> bool {
+        match self {
+            HeuristicType::Matrix | HeuristicType::Mixte | HeuristicType::Global => true,
+            HeuristicType::Weighted(profile) => profile.uses_matrix(),
+            _ => false,
         }
     }
 
@@ -133,7 +190,14 @@ impl HeuristicType {
             HeuristicType::Matrix => HeuristicType::Mobility,
             HeuristicType::Mobility => HeuristicType::Mixte,
             HeuristicType::Mixte => HeuristicType::Global,
-            HeuristicType::Global => HeuristicType::Absolute,
+            HeuristicType::Global => HeuristicType::Stability,
+            HeuristicType::Stability => HeuristicType::Frontier,
+            HeuristicType::Frontier => HeuristicType::PotentialMobility,
+            HeuristicType::PotentialMobility => HeuristicType::Parity,
+            HeuristicType::Parity => HeuristicType::Weighted(shared_profile()),
+            HeuristicType::Weighted(_) => HeuristicType::Pattern(shared_pattern_weights()),
+            HeuristicType::Pattern(_) => HeuristicType::Network(shared_network()),
+            HeuristicType::Network(_) => HeuristicType::Absolute,
         }
     }
 
@@ -147,16 +211,23 @@ impl HeuristicType {
     ///
     /// ```rust
     /// let heuristic = HeuristicType::Absolute;
-    /// assert_eq!(heuristic.previous(), HeuristicType::Global);
-    /// assert_eq!(heuristic.previous().previous(), HeuristicType::Mixte);
+    /// assert_eq!(heuristic.previous().to_string(), "Network");
+    /// assert_eq!(heuristic.previous().previous().to_string(), "Pattern");
     /// ```
     pub fn previous(&self) -> HeuristicType {
         match self {
-            HeuristicType::Absolute => HeuristicType::Global,
+            HeuristicType::Absolute => HeuristicType::Network(shared_network()),
             HeuristicType::Matrix => HeuristicType::Absolute,
             HeuristicType::Mobility => HeuristicType::Matrix,
             HeuristicType::Mixte => HeuristicType::Mobility,
             HeuristicType::Global => HeuristicType::Mixte,
+            HeuristicType::Stability => HeuristicType::Global,
+            HeuristicType::Frontier => HeuristicType::Stability,
+            HeuristicType::PotentialMobility => HeuristicType::Frontier,
+            HeuristicType::Parity => HeuristicType::PotentialMobility,
+            HeuristicType::Weighted(_) => HeuristicType::Parity,
+            HeuristicType::Pattern(_) => HeuristicType::Weighted(shared_profile()),
+            HeuristicType::Network(_) => HeuristicType::Pattern(shared_pattern_weights()),
         }
     }
 }
@@ -214,11 +285,22 @@ fn heuristic_absolute(board: &Board, player: Cell) -> isize {
 /// - Corner adjacent (-20/-150): Give opponent access to corners
 /// - Center (varies): Different values based on matrix type
 fn heuristic_matrix(board: &Board, player: Cell, matrix: &AIHeuristicMatrix) -> isize {
+    matrix_score(board, player, &matrix.value())
+}
+
+/// Sums the matrix values of the squares holding a disc of the player.
+///
+/// # Arguments
+///
+/// * `board` - The current board state
+/// * `player` - The player whose discs are counted
+/// * `values` - The weight of every square
+pub fn matrix_score(board: &Board, player: Cell, values: &[[isize; SIZE]; SIZE]) -> isize {
     let mut score = 0;
-    for row in 0..SIZE {
-        for col in 0..SIZE {
+    for (row, line) in values.iter().enumerate() {
+        for (col, value) in line.iter().enumerate() {
             if board.get_cell(row, col).unwrap() == player {
-                score += matrix.value()[row][col];
+                score += value;
             }
         }
     }
@@ -254,6 +336,110 @@ fn heuristic_mobility(board: &Board, player: Cell) -> isize {
     nb_moves_player - nb_moves_opponent
 }
 
+/// Stability heuristic: evaluates based on discs that can never be flipped.
+///
+/// Stable discs are a permanent gain: they count in the final score whatever
+/// the opponent plays. They grow from the corners along the edges, which is
+/// why corners are so valuable.
+///
+/// # Arguments
+///
+/// * `board` - The current board state
+/// * `player` - The player from whose perspective to evaluate
+///
+/// # Returns
+///
+/// The stable disc difference (player stable discs - opponent stable discs)
+fn heuristic_stability(board: &Board, player: Cell) -> isize {
+    board.get_nb_stable_discs(player) as isize
+        - board.get_nb_stable_discs(player.get_opponent()) as isize
+}
+
+/// Frontier heuristic: evaluates based on discs next to empty cells.
+///
+/// Frontier discs can be flipped by moves played on the empty cells around
+/// them, and they open lines for the opponent. Keeping few of them, while the
+/// opponent has many, usually leads to a mobility advantage later.
+///
+/// # Arguments
+///
+/// * `board` - The current board state
+/// * `player` - The player from whose perspective to evaluate
+///
+/// # Returns
+///
+/// The frontier disc difference (opponent frontier discs - player frontier discs)
+fn heuristic_frontier(board: &Board, player: Cell) -> isize {
+    board.get_nb_frontier_discs(player.get_opponent()) as isize
+        - board.get_nb_frontier_discs(player) as isize
+}
+
+/// Potential mobility heuristic: evaluates based on future moves.
+///
+/// Unlike `heuristic_mobility`, which counts the legal moves of the current
+/// position, this heuristic counts the empty cells next to opponent discs,
+/// where moves may become legal later in the game.
+///
+/// # Arguments
+///
+/// * `board` - The current board state
+/// * `player` - The player from whose perspective to evaluate
+///
+/// # Returns
+///
+/// The potential mobility difference (player - opponent)
+fn heuristic_potential_mobility(board: &Board, player: Cell) -> isize {
+    board.get_potential_mobility(player) as isize
+        - board.get_potential_mobility(player.get_opponent()) as isize
+}
+
+/// Parity heuristic: evaluates who gets the last move in each region.
+///
+/// In the endgame the empty cells split into separate regions. The player
+/// who plays first in a region with an odd number of empty cells usually
+/// also plays last in it, and the last move of a region cannot be answered
+/// there. Each odd region counts for the player to move when they can play
+/// in it, otherwise for the opponent when the opponent can.
+///
+/// # Arguments
+///
+/// * `board` - The current board state
+/// * `player` - The player from whose perspective to evaluate
+///
+/// # Returns
+///
+/// The odd region difference (player regions - opponent regions)
+///
+/// # Strategy
+///
+/// - Meaningful once the board is split, in the last 15-20 moves
+/// - Early in the game there is a single region, so only the global parity counts
+fn heuristic_parity(board: &Board, player: Cell) -> isize {
+    let to_move = board.get_player_turn();
+    let moves_to_move = board.has_legal_moves(to_move).unwrap_or_default();
+    let moves_opponent = board
+        .has_legal_moves(to_move.get_opponent())
+        .unwrap_or_default();
+
+    let mut score = 0;
+    for region in board
+        .get_empty_regions()
+        .iter()
+        .filter(|region| region.len() % 2 == 1)
+    {
+        if region.iter().any(|cell| moves_to_move.contains(cell)) {
+            score += 1;
+        } else if region.iter().any(|cell| moves_opponent.contains(cell)) {
+            score -= 1;
+        }
+    }
+    if to_move == player {
+        score
+    } else {
+        -score
+    }
+}
+
 /// Mixed heuristic: adaptive strategy based on game phase.
 ///
 /// This heuristic changes its evaluation strategy based on the current turn number,
@@ -278,12 +464,17 @@ fn heuristic_mobility(board: &Board, player: Cell) -> isize {
 /// 1. **Opening** (< 20 moves): Secure good positions, avoid bad ones
 /// 2. **Middle game** (20-40 moves): Maintain mobility, control opponent options
 /// 3. **Endgame** (> 40 moves): Maximize piece count for final advantage
+///
+/// Every phase also rewards stable discs. In the opening the stable disc
+/// difference is scaled by `STABILITY_WEIGHT` to match the matrix values;
+/// later it is added as is, on the scale of move and disc counts.
 fn heuristic_mixte(board: &Board, player: Cell, matrix: &AIHeuristicMatrix) -> isize {
+    let stability = heuristic_stability(board, player);
     if board.get_turn_number() < 20 {
-        heuristic_matrix(board, player, matrix)
+        heuristic_matrix(board, player, matrix) + STABILITY_WEIGHT * stability
     } else if board.get_turn_number() < 40 {
-        heuristic_mobility(board, player)
+        heuristic_mobility(board, player) + stability
     } else {
-        heuristic_absolute(board, player)
+        heuristic_absolute(board, player) + stability
     }
 }
@@ -4,10 +4,32 @@
 //! board positions in Othello. These matrices are used by AI algorithms to
 //! evaluate the strategic value of different board positions, with corners
 //! typically having high values and adjacent positions having negative values.
+//!
+//! Besides the built-in matrices A and B, any number of named matrices can be
+//! loaded from JSON files in `MATRICES_DIR`:
+//!
+//! ```json
+//! { "name": "Corners", "values": [[100, -20, ...], ...] }
+//! ```
+//!
+//! Every matrix must be 8x8 and symmetric under the eight board symmetries,
+//! like the built-in ones, so the evaluation does not depend on the
+//! orientation of the board.
+
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+    sync::{Arc, OnceLock, RwLock},
+};
 
-use std::fmt::Display;
+use serde::{Deserialize, Serialize};
 
-use crate::consts::SIZE;
+use crate::{
+    consts::{MATRICES_DIR, SIZE},
+    game::symmetry::SYMMETRIES,
+};
 
 /// Enumeration of available heuristic matrices for position evaluation.
 ///
@@ -31,6 +53,8 @@ pub enum AIHeuristicMatrix {
     A,
     /// Matrix B - Aggressive position evaluation with higher central values
     B,
+    /// User-defined matrix loaded from `MATRICES_DIR`
+    Custom(Arc<CustomMatrix>),
 }
 
 impl Display for AIHeuristicMatrix {
@@ -49,6 +73,7 @@ impl Display for AIHeuristicMatrix {
         match self {
             AIHeuristicMatrix::A => write!(f, "Matrix A"),
             AIHeuristicMatrix::B => write!(f, "Matrix B"),
+            AIHeuristicMatrix::Custom(matrix) => write!(f, "{}", matrix.name),
         }
     }
 }
@@ -104,14 +129,43 @@ impl AIHeuristicMatrix {
                 [-150, -250, 0, 0, 0, 0, -250, -150],
                 [500, -150, 30, 10, 10, 30, -150, 500],
             ],
+            AIHeuristicMatrix::Custom(matrix) => matrix.values,
         }
     }
 
+    /// Returns every available matrix: A, B, then the user-defined ones.
+    pub fn all() -> Vec<AIHeuristicMatrix> {
+        let mut matrices = vec![AIHeuristicMatrix::A, AIHeuristicMatrix::B];
+        matrices.extend(custom_matrices().into_iter().map(AIHeuristicMatrix::Custom));
+        matrices
+    }
+
+    /// Tells whether two values are the same matrix.
+    ///
+    /// Built-in matrices are compared by variant. A user-defined matrix is
+    /// the same as another when they share their `Arc` or their file, so a
+    /// matrix saved again from the editor is still found in the cyclers.
+    fn is_same(&self, other: &AIHeuristicMatrix) -> bool {
+        match (self, other) {
+            (AIHeuristicMatrix::A, AIHeuristicMatrix::A) => true,
+            (AIHeuristicMatrix::B, AIHeuristicMatrix::B) => true,
+            (AIHeuristicMatrix::Custom(matrix), AIHeuristicMatrix::Custom(other)) => {
+                Arc::ptr_eq(matrix, other) || matrix.file_name() == other.file_name()
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the position of the matrix in `all`.
+    fn index_in(&self, matrices: &[AIHeuristicMatrix]) -> Option<usize> {
+        matrices.iter().position(|matrix| matrix.is_same(self))
+    }
+
     /// Returns the next matrix in the cycle.
     ///
     /// This method allows cycling between different heuristic matrices,
     /// useful for UI controls that let users switch between evaluation
-    /// strategies.
+    /// strategies. The user-defined matrices come after A and B.
     ///
     /// # Examples
     ///
@@ -121,9 +175,10 @@ impl AIHeuristicMatrix {
     /// assert_eq!(matrix.next().next(), AIHeuristicMatrix::A);
     /// ```
     pub fn next(&self) -> AIHeuristicMatrix {
-        match self {
-            AIHeuristicMatrix::A => AIHeuristicMatrix::B,
-            AIHeuristicMatrix::B => AIHeuristicMatrix::A,
+        let matrices = Self::all();
+        match self.index_in(&matrices) {
+            Some(index) => matrices[(index + 1) % matrices.len()].clone(),
+            None => AIHeuristicMatrix::A,
         }
     }
 
@@ -137,13 +192,403 @@ impl AIHeuristicMatrix {
     ///
     /// ```rust
     /// let matrix = AIHeuristicMatrix::A;
+    /// // Without user-defined matrices, A and B alternate
     /// assert_eq!(matrix.previous(), AIHeuristicMatrix::B);
     /// assert_eq!(matrix.previous().previous(), AIHeuristicMatrix::A);
     /// ```
     pub fn previous(&self) -> AIHeuristicMatrix {
-        match self {
-            AIHeuristicMatrix::A => AIHeuristicMatrix::B,
-            AIHeuristicMatrix::B => AIHeuristicMatrix::A,
+        let matrices = Self::all();
+        match self.index_in(&matrices) {
+            Some(index) => matrices[(index + matrices.len() - 1) % matrices.len()].clone(),
+            None => AIHeuristicMatrix::A,
+        }
+    }
+}
+
+/// A named matrix defined by the user.
+///
+/// # Examples
+///
+/// ```rust
+/// let values = AIHeuristicMatrix::A.value().map(|row| row.to_vec()).to_vec();
+/// let matrix = CustomMatrix::new("Copy of A", values).unwrap();
+/// matrix.save("matrices").unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "MatrixFile", into = "MatrixFile")]
+pub struct CustomMatrix {
+    /// Name shown in the matrix cyclers
+    pub name: String,
+    /// Weight of every square
+    pub values: [[isize; SIZE]; SIZE],
+}
+
+/// Layout of a matrix file, checked by `CustomMatrix::new` when loaded.
+#[derive(Serialize, Deserialize)]
+struct MatrixFile {
+    name: String,
+    values: Vec<Vec<isize>>,
+}
+
+impl TryFrom<MatrixFile> for CustomMatrix {
+    type Error = String;
+
+    fn try_from(file: MatrixFile) -> Result<Self, String> {
+        Self::new(&file.name, file.values)
+    }
+}
+
+impl From<CustomMatrix> for MatrixFile {
+    fn from(matrix: CustomMatrix) -> Self {
+        Self {
+            name: matrix.name,
+            values: matrix.values.iter().map(|row| row.to_vec()).collect(),
+        }
+    }
+}
+
+impl CustomMatrix {
+    /// Creates a matrix, checking its size and symmetry.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the matrix, which must not be empty
+    /// * `values` - The weights, one row per board row
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CustomMatrix)` - If the matrix is 8x8 and symmetric
+    /// * `Err(String)` - Describing the first problem found
+    pub fn new(name: &str, values: Vec<Vec<isize>>) -> Result<Self, String> {
+        if name.trim().is_empty() {
+            return Err("The matrix has no name".to_string());
+        }
+        if values.len() != SIZE || values.iter().any(|row| row.len() != SIZE) {
+            return Err(format!("Matrix '{}' is not {}x{}", name, SIZE, SIZE));
+        }
+        let mut grid = [[0; SIZE]; SIZE];
+        for (row, line) in values.iter().enumerate() {
+            grid[row].copy_from_slice(line);
+        }
+        if !is_symmetric(&grid) {
+            return Err(format!("Matrix '{}' is not symmetric", name));
+        }
+        Ok(Self {
+            name: name.trim().to_string(),
+            values: grid,
+        })
+    }
+
+    /// Loads a matrix from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the JSON file containing the matrix
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CustomMatrix)` - If the file holds a valid matrix
+    /// * `Err(String)` - If the file cannot be read or the matrix is invalid
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        let file = File::open(file_path).map_err(|e| format!("{}: {}", file_path, e))?;
+        let content: MatrixFile = serde_json::from_reader(file)
+            .map_err(|e| format!("{}: could not deserialize matrix: {}", file_path, e))?;
+        Self::try_from(content).map_err(|e| format!("{}: {}", file_path, e))
+    }
+
+    /// Returns the name of the matrix file, derived from the matrix name.
+    ///
+    /// Names that differ only by case or punctuation share a file name,
+    /// which `save` refuses to overwrite.
+    pub fn file_name(&self) -> String {
+        let stem: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}.json", stem)
+    }
+
+    /// Saves the matrix as a JSON file in a directory, creating it if needed.
+    ///
+    /// The file of a matrix with the same name is replaced, but the file of
+    /// another matrix whose name gives the same file name is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory of the matrix files
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The path of the written file
+    /// * `Err(String)` - If the file holds another matrix, or the directory
+    ///   or the file could not be written
+    pub fn save(&self, dir: &str) -> Result<String, String> {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let path = Path::new(dir).join(self.file_name());
+        if let Ok(existing) = Self::load(&path.to_string_lossy()) {
+            if existing.name != self.name {
+                return Err(format!(
+                    "{} already holds matrix '{}', rename matrix '{}'",
+                    path.display(),
+                    existing.name,
+                    self.name
+                ));
+            }
         }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize matrix: {}", e))?;
+        let mut file = File::create(&path).map_err(|e| e.to_string())?;
+        file.write_all(json.as_bytes()).map_err(|e| e.to_string())?;
+        Ok(path.to_string_lossy().into_owned())
+    }
+}
+
+/// Tells whether a matrix is unchanged by every board symmetry.
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(is_symmetric(&AIHeuristicMatrix::B.value()));
+/// ```
+pub fn is_symmetric(values: &[[isize; SIZE]; SIZE]) -> bool {
+    (0..SIZE)
+        .flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+        .all(|pos| {
+            SYMMETRIES.iter().all(|symmetry| {
+                let (row, col) = symmetry.apply(pos);
+                values[row][col] == values[pos.0][pos.1]
+            })
+        })
+}
+
+/// Sets the weight of a square and of every square symmetric to it.
+///
+/// Editing a symmetric matrix this way keeps it symmetric.
+///
+/// # Arguments
+///
+/// * `values` - The matrix to edit
+/// * `pos` - The (row, col) coordinates of the edited square
+/// * `value` - The new weight
+pub fn set_symmetric(values: &mut [[isize; SIZE]; SIZE], pos: (usize, usize), value: isize) {
+    for symmetry in SYMMETRIES {
+        let (row, col) = symmetry.apply(pos);
+        values[row][col] = value;
+    }
+}
+
+/// Loads every matrix file of a directory, in file name order.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the matrix files
+///
+/// # Returns
+///
+/// One result per `.json` file: the matrix, or why it was rejected. A
+/// missing directory gives no matrix.
+pub fn load_matrices(dir: &str) -> Vec<Result<CustomMatrix, String>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| CustomMatrix::load(&path.to_string_lossy()))
+        .collect()
+}
+
+/// User-defined matrices and the files rejected when they were loaded.
+#[derive(Default)]
+struct MatrixRegistry {
+    matrices: Vec<Arc<CustomMatrix>>,
+    errors: Vec<String>,
+}
+
+/// Returns the registry, loading `MATRICES_DIR` the first time.
+fn registry() -> &'static RwLock<MatrixRegistry> {
+    static REGISTRY: OnceLock<RwLock<MatrixRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut registry = MatrixRegistry::default();
+        for result in load_matrices(MATRICES_DIR) {
+            match result {
+                Ok(matrix) if registry.matrices.iter().any(|m| m.name == matrix.name) => {
+                    registry
+                        .errors
+                        .push(format!("Matrix '{}' is defined twice", matrix.name));
+                }
+                Ok(matrix) => registry.matrices.push(Arc::new(matrix)),
+                Err(e) => registry.errors.push(e),
+            }
+        }
+        RwLock::new(registry)
+    })
+}
+
+/// Returns the user-defined matrices, loaded once from `MATRICES_DIR`.
+pub fn custom_matrices() -> Vec<Arc<CustomMatrix>> {
+    registry()
+        .read()
+        .map(|registry| registry.matrices.clone())
+        .unwrap_or_default()
+}
+
+/// Returns why matrix files of `MATRICES_DIR` were rejected.
+pub fn matrix_errors() -> Vec<String> {
+    registry()
+        .read()
+        .map(|registry| registry.errors.clone())
+        .unwrap_or_default()
+}
+
+/// Saves a user-defined matrix to `MATRICES_DIR` and makes it available.
+///
+/// A matrix with the same name is replaced, in the files and in the
+/// cyclers; AIs already using the old version keep it until it is selected
+/// again. A matrix whose file name is taken by another matrix is rejected.
+///
+/// # Returns
+///
+/// * `Ok(Arc<CustomMatrix>)` - The saved matrix, as used by the cyclers
+/// * `Err(String)` - If the file name is taken or the file could not be
+///   written
+pub fn save_custom_matrix(matrix: CustomMatrix) -> Result<Arc<CustomMatrix>, String> {
+    matrix.save(MATRICES_DIR)?;
+    let matrix = Arc::new(matrix);
+    let mut registry = registry()
+        .write()
+        .map_err(|_| "The matrix registry is unavailable".to_string())?;
+    match registry.matrices.iter().position(|m| m.name == matrix.name) {
+        Some(index) => registry.matrices[index] = matrix.clone(),
+        None => registry.matrices.push(matrix.clone()),
+    }
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(values: [[isize; SIZE]; SIZE]) -> Vec<Vec<isize>> {
+        values.iter().map(|row| row.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_builtin_matrices_are_symmetric() {
+        assert!(is_symmetric(&AIHeuristicMatrix::A.value()));
+        assert!(is_symmetric(&AIHeuristicMatrix::B.value()));
+    }
+
+    #[test]
+    fn test_custom_matrix_checks() {
+        let values = rows(AIHeuristicMatrix::B.value());
+        assert!(CustomMatrix::new("B copy", values.clone()).is_ok());
+        assert!(CustomMatrix::new(" ", values.clone()).is_err());
+
+        let mut short = values.clone();
+        short.pop();
+        assert!(CustomMatrix::new("Short", short).is_err());
+
+        let mut narrow = values.clone();
+        narrow[3].pop();
+        assert!(CustomMatrix::new("Narrow", narrow).is_err());
+
+        // Changing a corner alone breaks the symmetry
+        let mut lopsided = values;
+        lopsided[0][0] = 1;
+        assert_eq!(
+            CustomMatrix::new("Lopsided", lopsided),
+            Err("Matrix 'Lopsided' is not symmetric".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_symmetric_keeps_symmetry() {
+        let mut values = AIHeuristicMatrix::A.value();
+        set_symmetric(&mut values, (0, 1), 7);
+        assert!(is_symmetric(&values));
+        // B1 has eight images: the squares next to the corners
+        let changed = values.iter().flatten().filter(|&&value| value == 7).count();
+        assert_eq!(changed, 8);
+
+        set_symmetric(&mut values, (3, 3), 40);
+        assert!(is_symmetric(&values));
+        assert_eq!(values[4][4], 40);
+    }
+
+    #[test]
+    fn test_save_and_load_directory() {
+        let dir = std::env::temp_dir().join("othello_test_matrices");
+        let dir = dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(dir);
+        assert!(load_matrices(dir).is_empty());
+
+        let matrix = CustomMatrix::new("My Matrix!", rows(AIHeuristicMatrix::A.value())).unwrap();
+        let path = matrix.save(dir).unwrap();
+        assert!(path.ends_with("my_matrix_.json"));
+        fs::write(Path::new(dir).join("broken.json"), "{\"name\": \"Broken\"}").unwrap();
+
+        let loaded = load_matrices(dir);
+        assert_eq!(loaded.len(), 2);
+        assert!(loaded[0].is_err());
+        assert_eq!(loaded[1], Ok(matrix));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cycle_keeps_builtins_first() {
+        let matrices = AIHeuristicMatrix::all();
+        assert_eq!(matrices[0].to_string(), "Matrix A");
+        assert_eq!(matrices[1].to_string(), "Matrix B");
+        assert_eq!(AIHeuristicMatrix::A.next().to_string(), "Matrix B");
+        assert_eq!(
+            AIHeuristicMatrix::A.previous().to_string(),
+            matrices[matrices.len() - 1].to_string()
+        );
+        assert_eq!(matrices[matrices.len() - 1].next().to_string(), "Matrix A");
+    }
+
+    #[test]
+    fn test_save_keeps_the_file_of_another_matrix() {
+        let dir = std::env::temp_dir().join("othello_test_matrix_collision");
+        let dir = dir.to_str().unwrap();
+        let _ = fs::remove_dir_all(dir);
+
+        let first = CustomMatrix::new("My Matrix!", rows(AIHeuristicMatrix::A.value())).unwrap();
+        let second = CustomMatrix::new("my matrix?", rows(AIHeuristicMatrix::B.value())).unwrap();
+        assert_eq!(first.file_name(), second.file_name());
+        first.save(dir).unwrap();
+        assert!(second.save(dir).is_err());
+        // Saving a matrix again replaces its own file
+        assert!(first.save(dir).is_ok());
+        assert_eq!(load_matrices(dir), vec![Ok(first)]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_matrix_named_like_a_builtin() {
+        let custom = |values| {
+            AIHeuristicMatrix::Custom(Arc::new(
+                CustomMatrix::new("Matrix A", rows(values)).unwrap(),
+            ))
+        };
+        let fake_a = custom(AIHeuristicMatrix::B.value());
+        let matrices = [AIHeuristicMatrix::A, AIHeuristicMatrix::B, fake_a.clone()];
+        assert_eq!(AIHeuristicMatrix::A.index_in(&matrices), Some(0));
+        assert_eq!(fake_a.index_in(&matrices), Some(2));
+        // A new version of a saved matrix is found by its file
+        let edited = custom(AIHeuristicMatrix::A.value());
+        assert_eq!(edited.index_in(&matrices), Some(2));
     }
 }
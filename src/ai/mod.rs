@@ -1,5 +1,18 @@
 pub mod action;
 pub mod ai_type;
 pub mod algo;
+pub mod analysis;
+pub mod cancel;
+pub mod eval_profile;
 pub mod heuristic;
 pub mod heuristic_matrix;
+pub mod network;
+pub mod opening_book;
+pub mod pattern;
+pub mod ponder;
+pub mod q_table;
+pub mod search_info;
+pub mod texel;
+pub mod training_metrics;
+pub mod tuner;
+pub mod worker_pool;
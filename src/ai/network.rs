@@ -0,0 +1,452 @@
+//! Neural network value function, evaluated on the CPU.
+//!
+//! A small multilayer perceptron predicts the expected score of a position:
+//! 1 when Black wins, -1 when White wins, 0 for a draw. Its inputs are the
+//! Black disc plane, the White disc plane (64 squares each) and the player
+//! to move, and it has one hidden layer of rectified linear units and a
+//! `tanh` output.
+//!
+//! Every input is 0 or 1, so a position is stored as the list of its active
+//! inputs and only their weights are read or updated, which keeps both the
+//! evaluation and the training fast without any GPU or linear algebra crate.
+//!
+//! The network is trained by self-play (see the `train-network` command):
+//! games are played by Alpha-Beta searches using the network itself, every
+//! position is labelled with the result of its game, and the weights are
+//! fitted by stochastic gradient descent. The weights are stored in a compact
+//! binary file.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{Read, Write},
+    sync::{Arc, OnceLock},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    consts::{NETWORK_HIDDEN, NETWORK_WEIGHTS_FILE, SIZE},
+    game::{board::Board, cell::Cell, symmetry::SYMMETRIES},
+};
+
+/// Magic bytes at the start of a network weights file.
+const MAGIC: &[u8; 4] = b"OTHN";
+
+/// Version of the network weights file format.
+const VERSION: u8 = 1;
+
+/// Number of inputs: two disc planes and the player to move.
+const NB_INPUTS: usize = 2 * SIZE * SIZE + 1;
+
+/// Index of the input set when Black is to move.
+const BLACK_TO_MOVE: usize = 2 * SIZE * SIZE;
+
+/// Evaluation of a sure win, the expected score being scaled to it.
+const EVAL_SCALE: f32 = 1000.0;
+
+/// A training position: its active inputs and the result of its game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkSample {
+    /// Indices of the inputs set to 1
+    active: Vec<usize>,
+    /// Result of the game for Black, from -1 to 1
+    target: f32,
+}
+
+/// Returns the active inputs of a position.
+///
+/// Input `row * 8 + col` is a Black disc, `64 + row * 8 + col` a White disc,
+/// and the last input is set when Black is to move.
+fn active_inputs(board: &Board) -> Vec<usize> {
+    let mut active = Vec::with_capacity(SIZE * SIZE + 1);
+    for row in 0..SIZE {
+        for col in 0..SIZE {
+            match board.get_cell(row, col) {
+                Ok(Cell::Black) => active.push(row * SIZE + col),
+                Ok(Cell::White) => active.push(SIZE * SIZE + row * SIZE + col),
+                _ => {}
+            }
+        }
+    }
+    if board.get_player_turn() == Cell::Black {
+        active.push(BLACK_TO_MOVE);
+    }
+    active
+}
+
+/// Multilayer perceptron predicting the expected score for Black.
+///
+/// # Examples
+///
+/// ```rust
+/// let network = ValueNetwork::load("network_weights.bin").unwrap_or_default();
+/// let expected = network.predict(&Board::new());
+/// let score = network.evaluate(&Board::new(), Cell::White);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct ValueNetwork {
+    /// Number of hidden units
+    hidden: usize,
+    /// Input weights, `hidden` values per input
+    input_weights: Vec<f32>,
+    /// Biases of the hidden units
+    hidden_biases: Vec<f32>,
+    /// Weight of every hidden unit in the output
+    output_weights: Vec<f32>,
+    /// Bias of the output
+    output_bias: f32,
+}
+
+impl fmt::Debug for ValueNetwork {
+    /// Shows the shape of the network instead of its weights.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ValueNetwork({}-{}-1)", NB_INPUTS, self.hidden)
+    }
+}
+
+impl Default for ValueNetwork {
+    /// An untrained network of `NETWORK_HIDDEN` units, always initialized
+    /// the same way.
+    fn default() -> Self {
+        Self::new(NETWORK_HIDDEN, 0)
+    }
+}
+
+impl ValueNetwork {
+    /// Creates an untrained network with small random weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `hidden` - Number of hidden units, at least 1
+    /// * `seed` - Seed of the initial weights
+    pub fn new(hidden: usize, seed: u64) -> Self {
+        let hidden = hidden.max(1);
+        let mut rng = StdRng::seed_from_u64(seed);
+        // Uniform initialization scaled to the number of inputs of each layer
+        let input_range = (6.0 / (NB_INPUTS + hidden) as f32).sqrt();
+        let output_range = (6.0 / (hidden + 1) as f32).sqrt();
+        Self {
+            hidden,
+            input_weights: (0..NB_INPUTS * hidden)
+                .map(|_| rng.random_range(-input_range..input_range))
+                .collect(),
+            hidden_biases: vec![0.0; hidden],
+            output_weights: (0..hidden)
+                .map(|_| rng.random_range(-output_range..output_range))
+                .collect(),
+            output_bias: 0.0,
+        }
+    }
+
+    /// Returns the number of hidden units.
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// Computes the hidden layer before activation, and the output.
+    fn forward(&self, active: &[usize]) -> (Vec<f32>, f32) {
+        let mut hidden = self.hidden_biases.clone();
+        for &input in active {
+            let weights = &self.input_weights[input * self.hidden..(input + 1) * self.hidden];
+            for (unit, weight) in hidden.iter_mut().zip(weights) {
+                *unit += weight;
+            }
+        }
+        let output = hidden
+            .iter()
+            .zip(&self.output_weights)
+            .map(|(unit, weight)| unit.max(0.0) * weight)
+            .sum::<f32>()
+            + self.output_bias;
+        (hidden, output.tanh())
+    }
+
+    /// Predicts the expected score of a position for Black, from -1 to 1.
+    pub fn predict(&self, board: &Board) -> f32 {
+        self.forward(&active_inputs(board)).1
+    }
+
+    /// Evaluates a position for a player.
+    ///
+    /// # Returns
+    ///
+    /// The expected score for `player`, in thousandths.
+    pub fn evaluate(&self, board: &Board, player: Cell) -> isize {
+        let score = (self.predict(board) * EVAL_SCALE).round() as isize;
+        if player == Cell::White {
+            -score
+        } else {
+            score
+        }
+    }
+
+    /// Fits the network to samples by stochastic gradient descent.
+    ///
+    /// The squared error between the predicted expected score and the game
+    /// result is minimized.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The training positions
+    /// * `epochs` - Number of passes over the samples
+    /// * `learning_rate` - Step of each update
+    /// * `seed` - Seed of the order the samples are visited in
+    /// * `progress` - Called after every epoch with its number and its error
+    pub fn train(
+        &mut self,
+        samples: &[NetworkSample],
+        epochs: usize,
+        learning_rate: f32,
+        seed: u64,
+        mut progress: impl FnMut(usize, f64),
+    ) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut order: Vec<usize> = (0..samples.len()).collect();
+        for epoch in 0..epochs {
+            for i in (1..order.len()).rev() {
+                order.swap(i, rng.random_range(0..=i));
+            }
+            let mut squared_error = 0.0;
+            for &i in &order {
+                let sample = &samples[i];
+                let (hidden, output) = self.forward(&sample.active);
+                let error = output - sample.target;
+                squared_error += (error * error) as f64;
+
+                // Gradient of the squared error through tanh
+                let delta = 2.0 * error * (1.0 - output * output);
+                let hidden_deltas: Vec<f32> = hidden
+                    .iter()
+                    .zip(&self.output_weights)
+                    .map(|(&unit, &weight)| if unit > 0.0 { delta * weight } else { 0.0 })
+                    .collect();
+                for (weight, &unit) in self.output_weights.iter_mut().zip(&hidden) {
+                    *weight -= learning_rate * delta * unit.max(0.0);
+                }
+                self.output_bias -= learning_rate * delta;
+                for (bias, hidden_delta) in self.hidden_biases.iter_mut().zip(&hidden_deltas) {
+                    *bias -= learning_rate * hidden_delta;
+                }
+                for &input in &sample.active {
+                    let weights =
+                        &mut self.input_weights[input * self.hidden..(input + 1) * self.hidden];
+                    for (weight, hidden_delta) in weights.iter_mut().zip(&hidden_deltas) {
+                        *weight -= learning_rate * hidden_delta;
+                    }
+                }
+            }
+            progress(
+                epoch + 1,
+                (squared_error / samples.len().max(1) as f64).sqrt(),
+            );
+        }
+    }
+
+    /// Loads weights from a binary file written by `save`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ValueNetwork)` - If the file holds a network
+    /// * `Err(String)` - If the file cannot be read or is not a network file
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        let mut bytes = Vec::new();
+        File::open(file_path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .map_err(|e| e.to_string())?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Saves the weights to a binary file.
+    ///
+    /// The file holds a header (magic, version, input and hidden unit
+    /// counts) followed by every weight as a little-endian `f32`: the input
+    /// weights, the hidden biases, the output weights and the output bias.
+    pub fn save(&self, file_path: &str) -> Result<(), String> {
+        let mut file = File::create(file_path).map_err(|e| e.to_string())?;
+        file.write_all(&self.to_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Encodes the weights in the binary file format.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend((NB_INPUTS as u32).to_le_bytes());
+        bytes.extend((self.hidden as u32).to_le_bytes());
+        let weights = self
+            .input_weights
+            .iter()
+            .chain(&self.hidden_biases)
+            .chain(&self.output_weights)
+            .chain([&self.output_bias]);
+        for weight in weights {
+            bytes.extend(weight.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes weights from the binary file format.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let header_len = MAGIC.len() + 1 + 8;
+        if bytes.len() < header_len || &bytes[..MAGIC.len()] != MAGIC {
+            return Err("Not a network weights file".to_string());
+        }
+        let header = &bytes[MAGIC.len()..];
+        if header[0] != VERSION {
+            return Err(format!("Unsupported network weights version {}", header[0]));
+        }
+        let inputs = u32::from_le_bytes(header[1..5].try_into().unwrap()) as usize;
+        let hidden = u32::from_le_bytes(header[5..9].try_into().unwrap()) as usize;
+        if inputs != NB_INPUTS {
+            return Err(format!(
+                "Network made for {} inputs instead of {}",
+                inputs, NB_INPUTS
+            ));
+        }
+        if hidden == 0 {
+            return Err("Network without hidden units".to_string());
+        }
+
+        let values: Vec<f32> = bytes[header_len..]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        let expected = NB_INPUTS * hidden + 2 * hidden + 1;
+        if bytes.len() != header_len + 4 * expected {
+            return Err(format!(
+                "Network weights file holds {} weights instead of {}",
+                values.len(),
+                expected
+            ));
+        }
+        let (input_weights, rest) = values.split_at(NB_INPUTS * hidden);
+        let (hidden_biases, rest) = rest.split_at(hidden);
+        let (output_weights, rest) = rest.split_at(hidden);
+        Ok(Self {
+            hidden,
+            input_weights: input_weights.to_vec(),
+            hidden_biases: hidden_biases.to_vec(),
+            output_weights: output_weights.to_vec(),
+            output_bias: rest[0],
+        })
+    }
+}
+
+/// Returns the network loaded from `NETWORK_WEIGHTS_FILE`.
+///
+/// The file is read once, the first time the network evaluation is selected.
+/// Without a file an untrained network is used, until one is trained.
+pub fn shared_network() -> Arc<ValueNetwork> {
+    static NETWORK: OnceLock<Arc<ValueNetwork>> = OnceLock::new();
+    NETWORK
+        .get_or_init(|| Arc::new(ValueNetwork::load(NETWORK_WEIGHTS_FILE).unwrap_or_default()))
+        .clone()
+}
+
+/// Turns the positions of a finished game into training samples.
+///
+/// Every position is added with its 8 symmetric images, which have the same
+/// result, so the network learns the symmetries of the board.
+///
+/// # Arguments
+///
+/// * `positions` - The positions of the game, final position included
+///
+/// # Returns
+///
+/// The samples, or none if the last position is not the end of the game.
+pub fn network_samples(positions: &[Board]) -> Vec<NetworkSample> {
+    let Some(last) = positions.last() else {
+        return Vec::new();
+    };
+    if !last.is_game_over() {
+        return Vec::new();
+    }
+    let difference = last.get_nb_discs(Cell::Black).unwrap_or(0) as isize
+        - last.get_nb_discs(Cell::White).unwrap_or(0) as isize;
+    let target = difference.signum() as f32;
+    positions
+        .iter()
+        .flat_map(|board| {
+            let active = active_inputs(board);
+            SYMMETRIES.iter().map(move |symmetry| {
+                let active = active
+                    .iter()
+                    .map(|&input| {
+                        if input == BLACK_TO_MOVE {
+                            return input;
+                        }
+                        let (plane, square) = (input / (SIZE * SIZE), input % (SIZE * SIZE));
+                        let (row, col) = symmetry.apply((square / SIZE, square % SIZE));
+                        plane * SIZE * SIZE + row * SIZE + col
+                    })
+                    .collect();
+                NetworkSample { active, target }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{heuristic::HeuristicType, pattern::self_play_game};
+
+    #[test]
+    fn test_inputs_of_start_position() {
+        let board = Board::new();
+        let active = active_inputs(&board);
+        assert_eq!(active.len(), 5);
+        assert!(active.contains(&(3 * SIZE + 4)));
+        assert!(active.contains(&(SIZE * SIZE + 3 * SIZE + 3)));
+        assert!(active.contains(&BLACK_TO_MOVE));
+
+        let network = ValueNetwork::default();
+        assert_eq!(network, ValueNetwork::default());
+        assert_eq!(
+            network.evaluate(&board, Cell::Black),
+            -network.evaluate(&board, Cell::White)
+        );
+        assert!(network.predict(&board).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let network = ValueNetwork::new(4, 3);
+        let bytes = network.to_bytes();
+        assert_eq!(ValueNetwork::from_bytes(&bytes).unwrap(), network);
+        assert!(ValueNetwork::from_bytes(&bytes[..bytes.len() - 4]).is_err());
+        assert!(ValueNetwork::from_bytes(b"OTHP").is_err());
+
+        let path = std::env::temp_dir().join("othello_test_network.bin");
+        let path = path.to_str().unwrap();
+        network.save(path).unwrap();
+        assert_eq!(ValueNetwork::load(path).unwrap(), network);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_training_learns_game_results() {
+        let positions = self_play_game(&HeuristicType::Mixte, 4, 1, 0).unwrap();
+        let samples = network_samples(&positions);
+        assert_eq!(samples.len(), SYMMETRIES.len() * positions.len());
+        assert!(network_samples(&positions[..positions.len() - 1]).is_empty());
+
+        let mut network = ValueNetwork::new(8, 1);
+        let mut errors = Vec::new();
+        network.train(&samples, 10, 0.01, 0, |_, error| errors.push(error));
+        assert_eq!(errors.len(), 10);
+        assert!(errors[9] < errors[0]);
+
+        // The final position is predicted with the sign of its result
+        let last = positions.last().unwrap();
+        let result = last.get_nb_discs(Cell::Black).unwrap() as isize
+            - last.get_nb_discs(Cell::White).unwrap() as isize;
+        if result != 0 {
+            assert_eq!(
+                network.evaluate(last, Cell::Black).signum(),
+                result.signum()
+            );
+        }
+    }
+}
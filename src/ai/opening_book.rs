@@ -0,0 +1,462 @@
+//! Opening book for the search AIs.
+//!
+//! The opening book maps early positions to weighted moves. Positions are
+//! stored under their canonical hash (see `game::symmetry`), so one entry
+//! covers the eight symmetric variants of a position. The search AIs look
+//! up the current position before searching and play a book move while the
+//! game is still in book, which saves time and varies the openings.
+//!
+//! Books are JSON files built with the `build-book` command, either from
+//! saved games or from deep searches of the first plies.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs::File,
+    io::Write,
+    sync::OnceLock,
+};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{
+        algo::alphabeta::AIAlphaBeta, heuristic::HeuristicType, heuristic_matrix::AIHeuristicMatrix,
+    },
+    consts::{BOOK_RANDOM_THRESHOLD, OPENING_BOOK_FILE},
+    game::{
+        board::Board, cell::Cell, history_action::HistoryAction, record, symmetry::canonical_hash,
+    },
+};
+
+/// How an AI uses the opening book.
+///
+/// # Examples
+///
+/// ```rust
+/// let mode = BookMode::Best;
+/// assert_eq!(mode.next(), BookMode::Random);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BookMode {
+    /// Never use the book, always search
+    Off,
+    /// Play the book move with the highest weight
+    Best,
+    /// Pick a book move at random among the good ones, by weight
+    Random,
+}
+
+impl Display for BookMode {
+    /// Formats the book mode for display purposes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// assert_eq!(format!("{}", BookMode::Off), "Off");
+    /// assert_eq!(format!("{}", BookMode::Random), "Random");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookMode::Off => write!(f, "Off"),
+            BookMode::Best => write!(f, "Best"),
+            BookMode::Random => write!(f, "Random"),
+        }
+    }
+}
+
+impl BookMode {
+    /// Returns the next book mode in the cycle Off → Best → Random → Off.
+    pub fn next(&self) -> BookMode {
+        match self {
+            BookMode::Off => BookMode::Best,
+            BookMode::Best => BookMode::Random,
+            BookMode::Random => BookMode::Off,
+        }
+    }
+
+    /// Returns the previous book mode in the cycle.
+    pub fn previous(&self) -> BookMode {
+        match self {
+            BookMode::Off => BookMode::Random,
+            BookMode::Best => BookMode::Off,
+            BookMode::Random => BookMode::Best,
+        }
+    }
+}
+
+/// A move stored in the book for a position.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BookMove {
+    /// Coordinates of the move on the canonical board (e.g. "2D")
+    pub coordinates: String,
+    /// Weight of the move, higher is better
+    pub weight: u32,
+}
+
+/// Positions mapped to weighted moves.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut book = OpeningBook::new();
+/// book.add_move(&Board::new(), (2, 3), 1);
+/// assert_eq!(book.lookup(&Board::new()), vec![((2, 3), 1)]);
+/// book.save("opening_book.json").unwrap();
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OpeningBook {
+    /// Book moves indexed by canonical position hash
+    positions: HashMap<String, Vec<BookMove>>,
+}
+
+impl OpeningBook {
+    /// Creates an empty opening book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads an opening book from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path to the JSON file containing the book
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(OpeningBook)` - If the book was successfully loaded
+    /// * `Err(String)` - If there was an error loading or parsing the file
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        match File::open(file_path) {
+            Ok(file) => serde_json::from_reader(file)
+                .map_err(|e| format!("Could not deserialize opening book: {}", e)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Saves the opening book to a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Path where the book should be saved
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the book was written
+    /// * `Err(String)` - If the file could not be created or written
+    pub fn save(&self, file_path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize opening book: {}", e))?;
+        let mut file = File::create(file_path).map_err(|e| e.to_string())?;
+        file.write_all(json.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Returns the number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true when the book has no position.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Adds weight to a move of a position, inserting it if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position where the move is played
+    /// * `pos` - The (row, col) coordinates of the move on `board`
+    /// * `weight` - The weight added to the move
+    pub fn add_move(&mut self, board: &Board, pos: (usize, usize), weight: u32) {
+        let (hash, symmetry) = canonical_hash(board);
+        let canonical = symmetry.apply(pos);
+        let coordinates = Board::coordinates_to_input(canonical.0, canonical.1);
+        let moves = self.positions.entry(hash).or_default();
+        match moves
+            .iter_mut()
+            .find(|book_move| book_move.coordinates == coordinates)
+        {
+            Some(book_move) => book_move.weight += weight,
+            None => moves.push(BookMove {
+                coordinates,
+                weight,
+            }),
+        }
+    }
+
+    /// Returns the legal book moves of a position, best first.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to look up
+    ///
+    /// # Returns
+    ///
+    /// The (row, col) coordinates on `board` and the weight of every book
+    /// move, sorted by decreasing weight. Empty when the position is out of book.
+    pub fn lookup(&self, board: &Board) -> Vec<((usize, usize), u32)> {
+        let (hash, symmetry) = canonical_hash(board);
+        let inverse = symmetry.inverse();
+        let color = board.get_player_turn();
+        let mut moves: Vec<((usize, usize), u32)> = self
+            .positions
+            .get(&hash)
+            .map(|moves| {
+                moves
+                    .iter()
+                    .filter(|book_move| book_move.weight > 0)
+                    .filter_map(|book_move| {
+                        Board::input_to_coordinates(&book_move.coordinates)
+                            .map(|pos| (inverse.apply(pos), book_move.weight))
+                    })
+                    .filter(|(pos, _)| board.can_play(pos.0, pos.1, color).is_ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        moves.sort_by_key(|book_move| Reverse(book_move.1));
+        moves
+    }
+
+    /// Chooses a book move for a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The position to look up
+    /// * `mode` - How to choose among the book moves
+    ///
+    /// # Returns
+    ///
+    /// The (row, col) coordinates of the chosen move, or None when the mode
+    /// is `BookMode::Off` or the position is out of book.
+    pub fn choose(&self, board: &Board, mode: BookMode) -> Option<(usize, usize)> {
+        let moves = self.lookup(board);
+        let best_weight = moves.first()?.1;
+        match mode {
+            BookMode::Off => None,
+            BookMode::Best => Some(moves[0].0),
+            BookMode::Random => {
+                let good_moves: Vec<&((usize, usize), u32)> = moves
+                    .iter()
+                    .filter(|(_, weight)| {
+                        *weight as f64 >= best_weight as f64 * BOOK_RANDOM_THRESHOLD
+                    })
+                    .collect();
+                let total: u32 = good_moves.iter().map(|(_, weight)| weight).sum();
+                let mut draw = rand::rng().random_range(0..total);
+                for (pos, weight) in good_moves {
+                    if draw < *weight {
+                        return Some(*pos);
+                    }
+                    draw -= weight;
+                }
+                Some(moves[0].0)
+            }
+        }
+    }
+
+    /// Adds the opening moves of a saved game.
+    ///
+    /// Every move of the first `max_plies` plies gets a weight of 1, plus 1
+    /// when the player who made it won the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `moves` - The moves of the game, as read by `record::load_games`
+    /// * `max_plies` - Number of moves recorded from the start of the game
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the game was added
+    /// * `Err(String)` - If the game contains an illegal move
+    pub fn add_game(
+        &mut self,
+        moves: &[Option<(usize, usize)>],
+        max_plies: usize,
+    ) -> Result<(), String> {
+        let positions = record::replay(moves)?;
+        let winner = positions.last().and_then(|board| board.get_winner());
+        for (board, pos) in positions.iter().zip(moves.iter().flatten()).take(max_plies) {
+            let bonus = if winner == Some(board.get_player_turn()) {
+                1
+            } else {
+                0
+            };
+            self.add_move(board, *pos, 1 + bonus);
+        }
+        Ok(())
+    }
+
+    /// Adds the best lines found by Alpha-Beta searches of the first plies.
+    ///
+    /// Every root move of a position is scored with an Alpha-Beta search.
+    /// The best move gets a weight of 2 and the second best a weight of 1,
+    /// then the positions reached by both moves are searched in turn, until
+    /// `max_plies` plies. Symmetric positions are searched once.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_plies` - Number of plies covered by the book
+    /// * `depth` - Depth of every Alpha-Beta search
+    /// * `heuristic` - Heuristic used by the searches
+    pub fn add_search_lines(&mut self, max_plies: usize, depth: usize, heuristic: HeuristicType) {
+        let mut frontier = vec![Board::new()];
+        let mut seen = HashSet::new();
+        for _ in 0..max_plies {
+            let mut next_frontier = vec![];
+            for board in frontier {
+                if board.is_game_over() || !seen.insert(canonical_hash(&board).0) {
+                    continue;
+                }
+                let color = board.get_player_turn();
+                let ai = AIAlphaBeta::new(depth, heuristic.clone(), color, AIHeuristicMatrix::A);
+                let mut scored: Vec<((usize, usize), isize, Board)> = board
+                    .has_legal_moves(color)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|pos| {
+                        let mut child = board.clone();
+                        child.play_and_advance(pos.0, pos.1).ok()?;
+                        let score = ai.init_tree(&child, depth);
+                        Some((pos, score, child))
+                    })
+                    .collect();
+                scored.sort_by_key(|scored_move| Reverse(scored_move.1));
+
+                for (rank, (pos, _, child)) in scored.into_iter().take(2).enumerate() {
+                    self.add_move(&board, pos, 2 - rank as u32);
+                    next_frontier.push(child);
+                }
+            }
+            frontier = next_frontier;
+        }
+    }
+}
+
+/// Returns the opening book shared by all AIs.
+///
+/// The book is loaded from `OPENING_BOOK_FILE` the first time it is needed.
+/// Returns None when the file is missing or invalid, in which case the AIs
+/// always search.
+pub fn shared_book() -> Option<&'static OpeningBook> {
+    static BOOK: OnceLock<Option<OpeningBook>> = OnceLock::new();
+    BOOK.get_or_init(|| OpeningBook::load(OPENING_BOOK_FILE).ok())
+        .as_ref()
+}
+
+/// Tells whether an AI using the book in `mode` would play a book move on `board`.
+///
+/// # Arguments
+///
+/// * `board` - The position, with the AI to move
+/// * `mode` - How the AI uses the book
+pub fn is_in_book(board: &Board, mode: BookMode) -> bool {
+    mode != BookMode::Off && shared_book().is_some_and(|book| !book.lookup(board).is_empty())
+}
+
+/// Plays a move from the shared opening book, if the position is in book.
+///
+/// The search AIs call this at the start of their turn and only search when
+/// it returns None.
+///
+/// # Arguments
+///
+/// * `board` - The current board, updated when a book move is played
+/// * `mode` - How the AI uses the book
+/// * `color` - The color of the AI
+///
+/// # Returns
+///
+/// * `None` - Book disabled, no book file or position out of book
+/// * `Some(Ok(HistoryAction))` - The book move that was played
+/// * `Some(Err(String))` - The book move could not be played
+pub fn play_book_move(
+    board: &mut Board,
+    mode: BookMode,
+    color: Cell,
+) -> Option<Result<HistoryAction, String>> {
+    if mode == BookMode::Off {
+        return None;
+    }
+    let (row, col) = shared_book()?.choose(board, mode)?;
+    Some(match board.try_play_move(row, col, color) {
+        Ok(gained_discs) => Ok(HistoryAction {
+            coordinates: Some(Board::coordinates_to_input(row, col)),
+            gained_discs: Some(gained_discs),
+            color,
+            player_turn: board.get_player_turn(),
+            move_number: board.get_turn_number(),
+            from_book: true,
+            search_info: None,
+        }),
+        Err(e) => Err(format!("Error playing book move: {}", e)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board_after(moves: &[(usize, usize)]) -> Board {
+        let mut board = Board::new();
+        for (row, col) in moves {
+            let color = board.get_player_turn();
+            board.try_play_move(*row, *col, color).unwrap();
+            board.next_turn();
+        }
+        board
+    }
+
+    #[test]
+    fn test_lookup_uses_symmetries() {
+        let mut book = OpeningBook::new();
+        // Diagonal opening after 2D, answered by 2C
+        book.add_move(&board_after(&[(2, 3)]), (2, 2), 3);
+
+        // 5E is the half-turn of 2D, so the answer is the half-turn of 2C
+        let symmetric = board_after(&[(5, 4)]);
+        assert_eq!(book.lookup(&symmetric), vec![((5, 5), 3)]);
+        assert_eq!(book.len(), 1);
+    }
+
+    #[test]
+    fn test_weights_accumulate_and_best_first() {
+        let mut book = OpeningBook::new();
+        let board = board_after(&[(2, 3)]);
+        book.add_move(&board, (2, 2), 1);
+        book.add_move(&board, (2, 4), 1);
+        book.add_move(&board, (2, 4), 2);
+        assert_eq!(book.lookup(&board), vec![((2, 4), 3), ((2, 2), 1)]);
+        assert_eq!(book.choose(&board, BookMode::Best), Some((2, 4)));
+        assert_eq!(book.choose(&board, BookMode::Off), None);
+        assert_eq!(
+            book.choose(&board_after(&[(2, 3), (2, 2)]), BookMode::Best),
+            None
+        );
+    }
+
+    #[test]
+    fn test_random_ignores_weak_moves() {
+        let mut book = OpeningBook::new();
+        let board = board_after(&[(2, 3)]);
+        book.add_move(&board, (2, 2), 1);
+        book.add_move(&board, (2, 4), 10);
+        for _ in 0..20 {
+            assert_eq!(book.choose(&board, BookMode::Random), Some((2, 4)));
+        }
+    }
+
+    #[test]
+    fn test_add_game() {
+        let mut book = OpeningBook::new();
+        let moves = record::line_to_moves("2D 2C 3C").unwrap();
+        book.add_game(&moves, 2).unwrap();
+        assert_eq!(book.len(), 2);
+        assert!(book.lookup(&board_after(&[(2, 3), (2, 2)])).is_empty());
+        assert!(book
+            .add_game(&record::line_to_moves("0A").unwrap(), 2)
+            .is_err());
+    }
+}
@@ -0,0 +1,572 @@
+//! Pattern-based evaluation, in the style of Logistello.
+//!
+//! A pattern is a fixed set of squares, like an edge or a corner block. Each
+//! configuration of its squares (empty, own disc or opponent disc on every
+//! square) has a learned weight, and a position is evaluated as the sum of
+//! the weights of the configurations found on the board. Every pattern is
+//! read at all its symmetric places, sharing the same weights, and the game
+//! is split into stages by disc count, each stage with its own weights.
+//!
+//! The weights are fitted by least squares regression on positions labelled
+//! with the final disc difference of their game, taken from saved games or
+//! from self-play (see the `train-patterns` command), and stored in a compact
+//! binary file.
+
+use std::{
+    collections::HashSet,
+    fmt,
+    fs::File,
+    io::{Read, Write},
+    sync::{Arc, OnceLock},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    ai::{
+        algo::alphabeta::AIAlphaBeta, heuristic::HeuristicType,
+        heuristic_matrix::AIHeuristicMatrix, opening_book::BookMode,
+    },
+    consts::{PATTERN_STAGES, PATTERN_WEIGHTS_FILE, SIZE},
+    game::{board::Board, cell::Cell, player::Player, symmetry::SYMMETRIES},
+};
+
+/// Magic bytes at the start of a pattern weights file.
+const MAGIC: &[u8; 4] = b"OTHP";
+
+/// Version of the pattern weights file format.
+const VERSION: u8 = 1;
+
+/// Weights are stored in hundredths of a disc.
+const WEIGHT_SCALE: f32 = 100.0;
+
+/// A pattern family: a name and its squares in one orientation.
+struct PatternFamily {
+    /// Name of the pattern, for reports
+    name: &'static str,
+    /// Squares of the pattern, in the order of the configuration index
+    squares: Vec<(usize, usize)>,
+}
+
+/// Returns the pattern families, in weights file order.
+///
+/// - **edge_2x**: An edge and its two X-squares
+/// - **corner_3x3**: The 3x3 block of a corner
+/// - **diag8** to **diag4**: The diagonals of 8 down to 4 squares
+/// - **hv2** to **hv4**: The second, third and fourth rows
+fn families() -> &'static [PatternFamily] {
+    static FAMILIES: OnceLock<Vec<PatternFamily>> = OnceLock::new();
+    FAMILIES.get_or_init(|| {
+        let row = |row: usize| (0..SIZE).map(move |col| (row, col));
+        let diagonal = |shift: usize| (0..SIZE - shift).map(move |i| (i, i + shift));
+        vec![
+            PatternFamily {
+                name: "edge_2x",
+                squares: row(0).chain([(1, 1), (1, SIZE - 2)]).collect(),
+            },
+            PatternFamily {
+                name: "corner_3x3",
+                squares: (0..3).flat_map(|r| (0..3).map(move |c| (r, c))).collect(),
+            },
+            PatternFamily {
+                name: "diag8",
+                squares: diagonal(0).collect(),
+            },
+            PatternFamily {
+                name: "diag7",
+                squares: diagonal(1).collect(),
+            },
+            PatternFamily {
+                name: "diag6",
+                squares: diagonal(2).collect(),
+            },
+            PatternFamily {
+                name: "diag5",
+                squares: diagonal(3).collect(),
+            },
+            PatternFamily {
+                name: "diag4",
+                squares: diagonal(4).collect(),
+            },
+            PatternFamily {
+                name: "hv2",
+                squares: row(1).collect(),
+            },
+            PatternFamily {
+                name: "hv3",
+                squares: row(2).collect(),
+            },
+            PatternFamily {
+                name: "hv4",
+                squares: row(3).collect(),
+            },
+        ]
+    })
+}
+
+/// A place where a pattern is read: its family and its squares.
+type Instance = (usize, Vec<(usize, usize)>);
+
+/// Returns every place where a pattern is read: its family and its squares.
+///
+/// The places are the symmetric images of the families. Images covering the
+/// same squares as an earlier one (like a row and its mirror) are dropped,
+/// so no square set is counted twice.
+fn instances() -> &'static [Instance] {
+    static INSTANCES: OnceLock<Vec<Instance>> = OnceLock::new();
+    INSTANCES.get_or_init(|| {
+        let mut instances = Vec::new();
+        for (family, pattern) in families().iter().enumerate() {
+            let mut seen = HashSet::new();
+            for symmetry in SYMMETRIES {
+                let squares: Vec<_> = pattern
+                    .squares
+                    .iter()
+                    .map(|&pos| symmetry.apply(pos))
+                    .collect();
+                let mut key = squares.clone();
+                key.sort();
+                if seen.insert(key) {
+                    instances.push((family, squares));
+                }
+            }
+        }
+        instances
+    })
+}
+
+/// Returns the number of configurations of a family (3 per square).
+fn nb_configurations(family: usize) -> usize {
+    3usize.pow(families()[family].squares.len() as u32)
+}
+
+/// Returns the stage of a position, from its number of discs.
+fn stage(board: &Board) -> usize {
+    let discs =
+        board.get_nb_discs(Cell::Black).unwrap_or(0) + board.get_nb_discs(Cell::White).unwrap_or(0);
+    (discs.saturating_sub(4) * PATTERN_STAGES / (SIZE * SIZE - 3)).min(PATTERN_STAGES - 1)
+}
+
+/// Returns the stage and the (family, configuration) pairs of a position.
+///
+/// Squares are read from the point of view of `player`: 0 for an empty
+/// square, 1 for a disc of `player`, 2 for an opponent disc.
+fn features(board: &Board, player: Cell) -> (usize, Vec<(usize, usize)>) {
+    let indices = instances()
+        .iter()
+        .map(|(family, squares)| {
+            let index = squares.iter().rev().fold(0, |index, &(row, col)| {
+                let code = match board.get_cell(row, col) {
+                    Ok(cell) if cell == player => 1,
+                    Ok(Cell::Empty) | Err(_) => 0,
+                    Ok(_) => 2,
+                };
+                index * 3 + code
+            });
+            (*family, index)
+        })
+        .collect();
+    (stage(board), indices)
+}
+
+/// Learned weights of every pattern configuration, for every stage.
+///
+/// # Examples
+///
+/// ```rust
+/// let weights = PatternWeights::load("pattern_weights.bin").unwrap_or_default();
+/// let score = weights.evaluate(&Board::new(), Cell::Black);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct PatternWeights {
+    /// Weights in hundredths of a disc, indexed by stage, family and configuration
+    weights: Vec<Vec<Vec<i16>>>,
+}
+
+impl fmt::Debug for PatternWeights {
+    /// Summarizes the weights instead of listing them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trained = self.weights.iter().flatten().flatten().filter(|&&w| w != 0);
+        write!(f, "PatternWeights({} non-zero weights)", trained.count())
+    }
+}
+
+impl Default for PatternWeights {
+    /// Untrained weights: every configuration weighs nothing.
+    fn default() -> Self {
+        let weights = (0..PATTERN_STAGES)
+            .map(|_| {
+                (0..families().len())
+                    .map(|family| vec![0; nb_configurations(family)])
+                    .collect()
+            })
+            .collect();
+        Self { weights }
+    }
+}
+
+impl PatternWeights {
+    /// Evaluates a position for a player.
+    ///
+    /// # Returns
+    ///
+    /// The predicted final disc difference for `player`, in hundredths of a disc.
+    pub fn evaluate(&self, board: &Board, player: Cell) -> isize {
+        let (stage, indices) = features(board, player);
+        indices
+            .iter()
+            .map(|&(family, index)| self.weights[stage][family][index] as isize)
+            .sum()
+    }
+
+    /// Loads weights from a binary file written by `save`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PatternWeights)` - If the file matches the current patterns
+    /// * `Err(String)` - If the file cannot be read or was made for other patterns
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        let mut bytes = Vec::new();
+        File::open(file_path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .map_err(|e| e.to_string())?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Saves the weights to a binary file.
+    ///
+    /// The file holds a header (magic, version, stage and family counts,
+    /// configurations per family) followed by every weight as a
+    /// little-endian `i16`.
+    pub fn save(&self, file_path: &str) -> Result<(), String> {
+        let mut file = File::create(file_path).map_err(|e| e.to_string())?;
+        file.write_all(&self.to_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Encodes the weights in the binary file format.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([VERSION, PATTERN_STAGES as u8, families().len() as u8]);
+        for family in 0..families().len() {
+            bytes.extend((nb_configurations(family) as u32).to_le_bytes());
+        }
+        for weight in self.weights.iter().flatten().flatten() {
+            bytes.extend(weight.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes weights from the binary file format.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let header_len = MAGIC.len() + 3 + 4 * families().len();
+        if bytes.len() < header_len || &bytes[..MAGIC.len()] != MAGIC {
+            return Err("Not a pattern weights file".to_string());
+        }
+        let header = &bytes[MAGIC.len()..];
+        if header[0] != VERSION {
+            return Err(format!("Unsupported pattern weights version {}", header[0]));
+        }
+        if header[1] as usize != PATTERN_STAGES || header[2] as usize != families().len() {
+            return Err("Pattern weights made for other stages or patterns".to_string());
+        }
+        for family in 0..families().len() {
+            let start = 3 + 4 * family;
+            let count = u32::from_le_bytes(header[start..start + 4].try_into().unwrap());
+            if count as usize != nb_configurations(family) {
+                return Err(format!(
+                    "Pattern '{}' has {} configurations in the file instead of {}",
+                    families()[family].name,
+                    count,
+                    nb_configurations(family)
+                ));
+            }
+        }
+
+        let mut weights = Self::default();
+        let mut values = bytes[header_len..]
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]));
+        for weight in weights.weights.iter_mut().flatten().flatten() {
+            *weight = values.next().ok_or("Pattern weights file is truncated")?;
+        }
+        if values.next().is_some() {
+            return Err("Pattern weights file is too long".to_string());
+        }
+        Ok(weights)
+    }
+}
+
+/// Returns the weights loaded from `PATTERN_WEIGHTS_FILE`.
+///
+/// The file is read once, the first time the pattern evaluation is selected.
+/// Without a file every weight is zero, until weights are trained.
+pub fn shared_pattern_weights() -> Arc<PatternWeights> {
+    static WEIGHTS: OnceLock<Arc<PatternWeights>> = OnceLock::new();
+    WEIGHTS
+        .get_or_init(|| Arc::new(PatternWeights::load(PATTERN_WEIGHTS_FILE).unwrap_or_default()))
+        .clone()
+}
+
+/// A training position: its features and the final result of its game.
+pub struct PatternSample {
+    /// Stage of the position
+    stage: usize,
+    /// (family, configuration) pairs of the position
+    indices: Vec<(usize, usize)>,
+    /// Final disc difference for the player the features were read for
+    target: f32,
+}
+
+/// Turns the positions of a finished game into training samples.
+///
+/// Every position gives two samples, one for each player, labelled with the
+/// final disc difference of that player.
+///
+/// # Arguments
+///
+/// * `positions` - The positions of the game, final position included
+///
+/// # Returns
+///
+/// The samples, or none if the last position is not the end of the game.
+pub fn game_samples(positions: &[Board]) -> Vec<PatternSample> {
+    let Some(last) = positions.last() else {
+        return Vec::new();
+    };
+    if !last.is_game_over() {
+        return Vec::new();
+    }
+    let black_result = last.get_nb_discs(Cell::Black).unwrap_or(0) as f32
+        - last.get_nb_discs(Cell::White).unwrap_or(0) as f32;
+    positions
+        .iter()
+        .flat_map(|board| {
+            [(Cell::Black, black_result), (Cell::White, -black_result)].map(|(player, target)| {
+                let (stage, indices) = features(board, player);
+                PatternSample {
+                    stage,
+                    indices,
+                    target,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Plays a game for training, with random moves in the opening.
+///
+/// # Arguments
+///
+/// * `heuristic` - Evaluation of the Alpha-Beta searches of both players
+/// * `random_plies` - Number of random moves at the start, for variety
+/// * `depth` - Depth of the Alpha-Beta searches playing the other moves
+/// * `seed` - Seed of the random opening moves
+///
+/// # Returns
+///
+/// * `Ok(Vec<Board>)` - The positions of the game, final position included
+/// * `Err(String)` - If a search failed
+pub fn self_play_game(
+    heuristic: &HeuristicType,
+    random_plies: usize,
+    depth: usize,
+    seed: u64,
+) -> Result<Vec<Board>, String> {
+    let players = [Cell::Black, Cell::White].map(|color| {
+        let mut ai = AIAlphaBeta::new(depth, heuristic.clone(), color, AIHeuristicMatrix::A);
+        ai.set_book_mode(BookMode::Off);
+        ai.set_threads(1);
+        ai
+    });
+    let mut board = Board::new();
+    let mut positions = vec![board.clone()];
+    let mut rng = StdRng::seed_from_u64(seed);
+    while !board.is_game_over() {
+        let moves = board
+            .has_legal_moves(board.get_player_turn())
+            .unwrap_or_default();
+        if moves.is_empty() {
+            break;
+        }
+        let (row, col) = if positions.len() <= random_plies {
+            moves[rng.random_range(0..moves.len())]
+        } else {
+            let player = match board.get_player_turn() {
+                Cell::Black => &players[0],
+                _ => &players[1],
+            };
+            player
+                .analyze(&board)?
+                .best()
+                .map(|best| best.action.pos)
+                .ok_or("The search found no move")?
+        };
+        board.play_and_advance(row, col)?;
+        positions.push(board.clone());
+    }
+    Ok(positions)
+}
+
+/// Fits pattern weights to samples by stochastic gradient descent.
+///
+/// The weights minimize the squared error between the predicted and the
+/// final disc difference. Weights of configurations that never appear stay
+/// at zero.
+///
+/// # Arguments
+///
+/// * `samples` - The training positions
+/// * `epochs` - Number of passes over the samples
+/// * `learning_rate` - Step of each update, shared by the weights of a sample
+/// * `seed` - Seed of the order in which the samples are visited
+/// * `progress` - Called after every epoch with its number and its error
+///
+/// # Returns
+///
+/// The fitted weights.
+pub fn train(
+    samples: &[PatternSample],
+    epochs: usize,
+    learning_rate: f32,
+    seed: u64,
+    mut progress: impl FnMut(usize, f64),
+) -> PatternWeights {
+    let mut weights: Vec<Vec<Vec<f32>>> = (0..PATTERN_STAGES)
+        .map(|_| {
+            (0..families().len())
+                .map(|family| vec![0.0; nb_configurations(family)])
+                .collect()
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..samples.len()).collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for epoch in 0..epochs {
+        // Visit the samples in a new random order every epoch
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.random_range(0..=i));
+        }
+        let mut squared_error = 0.0;
+        for &i in &order {
+            let sample = &samples[i];
+            let stage = &mut weights[sample.stage];
+            let prediction: f32 = sample
+                .indices
+                .iter()
+                .map(|&(family, index)| stage[family][index])
+                .sum();
+            let error = sample.target - prediction;
+            squared_error += (error * error) as f64;
+            for &(family, index) in &sample.indices {
+                stage[family][index] += learning_rate * error;
+            }
+        }
+        progress(
+            epoch + 1,
+            (squared_error / samples.len().max(1) as f64).sqrt(),
+        );
+    }
+
+    let weights = weights
+        .into_iter()
+        .map(|stage| {
+            stage
+                .into_iter()
+                .map(|family| {
+                    family
+                        .into_iter()
+                        .map(|weight| {
+                            (weight * WEIGHT_SCALE)
+                                .round()
+                                .clamp(i16::MIN as f32, i16::MAX as f32)
+                                as i16
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+    PatternWeights { weights }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instances_cover_symmetric_places() {
+        let count = |name: &str| {
+            let family = families().iter().position(|f| f.name == name).unwrap();
+            instances().iter().filter(|(f, _)| *f == family).count()
+        };
+        assert_eq!(count("edge_2x"), 4);
+        assert_eq!(count("corner_3x3"), 4);
+        assert_eq!(count("diag8"), 2);
+        assert_eq!(count("diag4"), 4);
+        assert_eq!(count("hv2"), 4);
+    }
+
+    #[test]
+    fn test_features_read_from_player_side() {
+        let board = Board::new();
+        let (stage, black) = features(&board, Cell::Black);
+        let (_, white) = features(&board, Cell::White);
+        assert_eq!(stage, 0);
+        assert_eq!(black.len(), instances().len());
+        // The main diagonals hold D4 and E5 (white) in the starting position
+        let diag8 = families().iter().position(|f| f.name == "diag8").unwrap();
+        let index = |features: &[(usize, usize)]| {
+            features
+                .iter()
+                .find(|(family, _)| *family == diag8)
+                .unwrap()
+                .1
+        };
+        assert_eq!(index(&black), 2 * 27 + 2 * 81);
+        assert_eq!(index(&white), 27 + 81);
+    }
+
+    #[test]
+    fn test_untrained_weights_evaluate_to_zero() {
+        let weights = PatternWeights::default();
+        assert_eq!(weights.evaluate(&Board::new(), Cell::Black), 0);
+    }
+
+    #[test]
+    fn test_weights_file_round_trip() {
+        let mut weights = PatternWeights::default();
+        weights.weights[1][0][42] = -1234;
+        weights.weights[3][9][7] = 99;
+        let bytes = weights.to_bytes();
+        assert_eq!(PatternWeights::from_bytes(&bytes).unwrap(), weights);
+
+        assert!(PatternWeights::from_bytes(&bytes[..bytes.len() - 2]).is_err());
+        assert!(PatternWeights::from_bytes(b"JSON").is_err());
+        let mut other_version = bytes.clone();
+        other_version[4] = VERSION + 1;
+        assert!(PatternWeights::from_bytes(&other_version).is_err());
+    }
+
+    #[test]
+    fn test_training_learns_game_results() {
+        let positions = self_play_game(&HeuristicType::Mixte, 4, 1, 0).unwrap();
+        assert!(positions.last().unwrap().is_game_over());
+        let samples = game_samples(&positions);
+        assert_eq!(samples.len(), 2 * positions.len());
+        assert!(game_samples(&positions[..positions.len() - 1]).is_empty());
+
+        let mut errors = Vec::new();
+        let weights = train(&samples, 20, 0.01, 0, |_, error| errors.push(error));
+        assert_eq!(errors.len(), 20);
+        assert!(errors[19] < errors[0]);
+
+        // The final position is predicted with the sign of its result
+        let last = positions.last().unwrap();
+        let result = last.get_nb_discs(Cell::Black).unwrap() as isize
+            - last.get_nb_discs(Cell::White).unwrap() as isize;
+        if result != 0 {
+            let predicted = weights.evaluate(last, Cell::Black);
+            assert_eq!(predicted.signum(), result.signum());
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Pondering: searching on the opponent's time.
+//!
+//! While a human looks for a move, the AI would otherwise sit idle. When
+//! pondering is enabled, the AI analyzes the positions that the likely human
+//! replies lead to, and keeps the analyses by position. If the human then
+//! plays one of these replies (a ponder hit), the AI answers at once with the
+//! analysis it already has instead of searching again.
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    ai::{
+        analysis::Analysis,
+        cancel::{CancelToken, SEARCH_CANCELLED},
+        opening_book::{is_in_book, BookMode},
+    },
+    game::{board::Board, player::Player},
+};
+
+/// Analyses computed while pondering, keyed by `Board::to_hash`.
+pub type PonderCache = HashMap<String, Analysis>;
+
+/// How often the human played one of the pondered replies.
+///
+/// # Examples
+///
+/// ```rust
+/// let stats = PonderStats { hits: 3, misses: 1 };
+/// assert_eq!(stats.hit_rate(), Some(75.0));
+/// println!("{}", stats); // "3/4 (75%)"
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PonderStats {
+    /// AI turns answered with a pondered analysis
+    pub hits: usize,
+    /// AI turns that needed a new search after pondering
+    pub misses: usize,
+}
+
+impl PonderStats {
+    /// Records whether the human played a pondered reply.
+    pub fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    /// Returns the share of hits in percent, None before the first pondered turn.
+    pub fn hit_rate(&self) -> Option<f64> {
+        match self.hits + self.misses {
+            0 => None,
+            total => Some(self.hits as f64 * 100.0 / total as f64),
+        }
+    }
+}
+
+impl Display for PonderStats {
+    /// Formats the hits over the pondered turns, with the hit rate.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.hit_rate() {
+            Some(rate) => write!(
+                f,
+                "{}/{} ({:.0}%)",
+                self.hits,
+                self.hits + self.misses,
+                rate
+            ),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+/// Analyzes the positions after the opponent's replies, likely replies first.
+///
+/// The replies are searched in order, starting with `expected` (usually the
+/// reply predicted by the principal variation of the AI's last search), then
+/// in the order of `Board::has_legal_moves`. Replies after which the AI does
+/// not move (pass or end of game) or would play from the opening book are
+/// skipped. Pondering stops as soon as `cancel` is cancelled, keeping the
+/// analyses finished so far.
+///
+/// # Arguments
+///
+/// * `player` - The AI waiting for its turn; its `CancelToken` must be `cancel`
+/// * `board` - The position with the opponent to move
+/// * `expected` - The reply to analyze first, if one is predicted
+/// * `cancel` - Token stopping the pondering when the opponent moves
+///
+/// # Returns
+///
+/// * `Ok(PonderCache)` - The analyses of the positions searched so far
+/// * `Err(String)` - If the player cannot analyze positions
+pub fn ponder(
+    player: &dyn Player,
+    board: &Board,
+    expected: Option<(usize, usize)>,
+    cancel: &CancelToken,
+) -> Result<PonderCache, String> {
+    let mut replies = board
+        .has_legal_moves(board.get_player_turn())
+        .unwrap_or_default();
+    if let Some(index) = replies.iter().position(|reply| Some(*reply) == expected) {
+        let reply = replies.remove(index);
+        replies.insert(0, reply);
+    }
+
+    let ai_color = board.get_player_turn().get_opponent();
+    let book_mode = player.get_book_mode().unwrap_or(BookMode::Off);
+    let mut cache = PonderCache::new();
+    for reply in replies {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let mut child = board.clone();
+        if child.play_and_advance(reply.0, reply.1).is_err()
+            || child.is_game_over()
+            || child.get_player_turn() != ai_color
+            || is_in_book(&child, book_mode)
+        {
+            continue;
+        }
+        match player.analyze(&child) {
+            Ok(analysis) => {
+                cache.insert(child.to_hash(), analysis);
+            }
+            Err(e) if e == SEARCH_CANCELLED => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::{
+            algo::{alphabeta::AIAlphaBeta, qlearning::QLearning},
+            heuristic::HeuristicType,
+            heuristic_matrix::AIHeuristicMatrix,
+        },
+        game::cell::Cell,
+    };
+
+    fn create_test_ai() -> AIAlphaBeta {
+        let mut ai = AIAlphaBeta::new(
+            2,
+            HeuristicType::Absolute,
+            Cell::White,
+            AIHeuristicMatrix::A,
+        );
+        ai.set_book_mode(BookMode::Off);
+        ai
+    }
+
+    #[test]
+    fn test_ponder_analyzes_every_reply() {
+        let ai = create_test_ai();
+        let board = Board::new();
+        let cache = ponder(&ai, &board, Some((5, 4)), &CancelToken::new()).unwrap();
+        assert_eq!(cache.len(), 4);
+
+        // A pondered position gets the analysis the AI would compute on its turn
+        let mut after = board.clone();
+        after.play_and_advance(2, 3).unwrap();
+        assert_eq!(
+            cache[&after.to_hash()].moves,
+            ai.analyze(&after).unwrap().moves
+        );
+    }
+
+    #[test]
+    fn test_cancelled_ponder_keeps_nothing() {
+        let mut ai = create_test_ai();
+        let token = CancelToken::new();
+        ai.set_cancel_token(token.clone());
+        token.cancel();
+        let cache = ponder(&ai, &Board::new(), None, &token).unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_ponder_needs_analysis() {
+        let ai = QLearning::new(
+            10,
+            HeuristicType::Absolute,
+            AIHeuristicMatrix::A,
+            1,
+            Cell::White,
+        );
+        assert!(ponder(&ai, &Board::new(), None, &CancelToken::new()).is_err());
+    }
+
+    #[test]
+    fn test_stats_hit_rate() {
+        let mut stats = PonderStats::default();
+        assert_eq!(stats.hit_rate(), None);
+        assert_eq!(stats.to_string(), "-");
+        stats.record(true);
+        stats.record(false);
+        assert_eq!(stats.hit_rate(), Some(50.0));
+        assert_eq!(stats.to_string(), "1/2 (50%)");
+    }
+}
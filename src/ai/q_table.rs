@@ -0,0 +1,386 @@
+//! Storage of the Q-learning tables.
+//!
+//! A Q-table maps a canonical board hash (see [`Board::to_hash`]) to the
+//! values of the actions played from it. Tables are saved either as
+//! pretty-printed JSON, readable but hundreds of megabytes once trained, or
+//! in a compact binary format where every state is a pair of bitboards and
+//! every action a square index with a value quantised to the scale of its
+//! state. The format is picked
+//! from the file extension when saving (`.json` for JSON, binary otherwise)
+//! and from the first bytes when loading, so both kinds of file keep working.
+//!
+//! Binary files are read and written as a stream, one state at a time.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+};
+
+use crate::{consts::SIZE, game::board::Board};
+
+/// Values of the actions of every state of a Q-table.
+pub type QTable = HashMap<String, HashMap<String, isize>>;
+
+/// Magic bytes at the start of a binary Q-table file.
+const MAGIC: &[u8; 4] = b"OTHQ";
+
+/// Version of the binary Q-table file format.
+const VERSION: u8 = 2;
+
+/// First version of the binary format, with one value scale for the whole
+/// table, still loaded.
+const GLOBAL_SCALE_VERSION: u8 = 1;
+
+/// Loads a Q-table saved by [`save_q_table`], in either format.
+///
+/// # Returns
+///
+/// * `Ok(QTable)` - The states and action values of the table
+/// * `Err(String)` - If the file cannot be read or is not a Q-table
+pub fn load_q_table(file_path: &str) -> Result<QTable, String> {
+    let mut reader = BufReader::new(File::open(file_path).map_err(|e| e.to_string())?);
+    let mut magic = [0; 4];
+    let is_binary = reader.read_exact(&mut magic).is_ok() && &magic == MAGIC;
+    reader.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    if is_binary {
+        read_binary(&mut reader)
+    } else {
+        serde_json::from_reader(reader).map_err(|e| format!("Could not deserialize Q-table: {}", e))
+    }
+}
+
+/// Saves a Q-table, as JSON if the path ends with `.json` and in the binary
+/// format otherwise.
+///
+/// Binary values are quantised to `i16` state by state: when the largest
+/// value of a state does not fit, the values of that state are divided by the
+/// smallest scale that makes it fit, and multiplied back on load. A state
+/// with huge values thus does not cost the others their precision.
+pub fn save_q_table(q_table: &QTable, file_path: &str) -> Result<(), String> {
+    let mut writer = BufWriter::new(File::create(file_path).map_err(|e| e.to_string())?);
+    if is_json(file_path) {
+        serde_json::to_writer_pretty(&mut writer, q_table)
+            .map_err(|e| format!("Could not serialize Q-table: {}", e))?;
+    } else {
+        write_binary(q_table, &mut writer)?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Converts a Q-table file to the format given by the output extension.
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of states converted
+/// * `Err(String)` - If the input cannot be loaded or the output written
+pub fn convert_q_table(input: &str, output: &str) -> Result<usize, String> {
+    let q_table = load_q_table(input)?;
+    save_q_table(&q_table, output)?;
+    Ok(q_table.len())
+}
+
+/// Returns whether a Q-table path is saved as JSON.
+fn is_json(file_path: &str) -> bool {
+    file_path.ends_with(".json")
+}
+
+/// Writes the binary format: a header (magic, version, state count as a
+/// little-endian `u64`) followed by every state as its black and white
+/// bitboards (`u64`), the side to move (`0` black, `1` white), the number of
+/// actions, the value scale of the state (little-endian `u32`), and for each
+/// action its square index (`row * 8 + col`) and quantised value (`i16`).
+fn write_binary(q_table: &QTable, writer: &mut impl Write) -> Result<(), String> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+    bytes.extend((q_table.len() as u64).to_le_bytes());
+    writer.write_all(&bytes).map_err(|e| e.to_string())?;
+
+    for (state, actions) in q_table {
+        bytes.clear();
+        let (black, white, turn) = encode_state(state)?;
+        let max_value = actions
+            .values()
+            .map(|value| value.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let scale = max_value.div_ceil(i16::MAX as usize).max(1);
+        let scale = u32::try_from(scale)
+            .map_err(|_| format!("Q-values of state '{}' are too large to store", state))?;
+        bytes.extend(black.to_le_bytes());
+        bytes.extend(white.to_le_bytes());
+        bytes.extend([turn, actions.len() as u8]);
+        bytes.extend(scale.to_le_bytes());
+        for (action, value) in actions {
+            let (row, col) = Board::input_to_coordinates(action)
+                .ok_or_else(|| format!("Invalid action '{}' in state '{}'", action, state))?;
+            let quantised = (*value as f64 / scale as f64).round() as i16;
+            bytes.push((row * SIZE + col) as u8);
+            bytes.extend(quantised.to_le_bytes());
+        }
+        writer.write_all(&bytes).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Reads the binary format written by [`write_binary`], one state at a time.
+///
+/// Files of the first version, with the value scale in the header instead of
+/// in every state, are read too.
+fn read_binary(reader: &mut impl Read) -> Result<QTable, String> {
+    let mut header = [0; 5];
+    reader
+        .read_exact(&mut header)
+        .map_err(|_| "Not a Q-table file")?;
+    if &header[..4] != MAGIC {
+        return Err("Not a Q-table file".to_string());
+    }
+    let version = header[4];
+    if version != VERSION && version != GLOBAL_SCALE_VERSION {
+        return Err(format!("Unsupported Q-table version {}", version));
+    }
+    let truncated = |_| "Q-table file is truncated".to_string();
+    let mut word = [0; 4];
+    let global_scale = if version == GLOBAL_SCALE_VERSION {
+        reader.read_exact(&mut word).map_err(truncated)?;
+        Some(u32::from_le_bytes(word) as isize)
+    } else {
+        None
+    };
+    let mut count = [0; 8];
+    reader.read_exact(&mut count).map_err(truncated)?;
+    let count = u64::from_le_bytes(count);
+
+    let mut q_table = QTable::with_capacity(count.min(1 << 20) as usize);
+    let mut state = [0; 18];
+    let mut action = [0; 3];
+    for _ in 0..count {
+        reader.read_exact(&mut state).map_err(truncated)?;
+        let scale = match global_scale {
+            Some(scale) => scale,
+            None => {
+                reader.read_exact(&mut word).map_err(truncated)?;
+                u32::from_le_bytes(word) as isize
+            }
+        };
+        let black = u64::from_le_bytes(state[0..8].try_into().unwrap());
+        let white = u64::from_le_bytes(state[8..16].try_into().unwrap());
+        let mut actions = HashMap::with_capacity(state[17] as usize);
+        for _ in 0..state[17] {
+            reader.read_exact(&mut action).map_err(truncated)?;
+            let index = action[0] as usize;
+            if index >= SIZE * SIZE {
+                return Err(format!("Invalid square {} in Q-table file", index));
+            }
+            let value = i16::from_le_bytes([action[1], action[2]]) as isize;
+            actions.insert(
+                Board::coordinates_to_input(index / SIZE, index % SIZE),
+                value * scale,
+            );
+        }
+        q_table.insert(decode_state(black, white, state[16])?, actions);
+    }
+    if reader.read(&mut action).map_err(|e| e.to_string())? != 0 {
+        return Err("Q-table file is too long".to_string());
+    }
+    Ok(q_table)
+}
+
+/// Splits a board hash into black and white bitboards and the side to move.
+fn encode_state(state: &str) -> Result<(u64, u64, u8), String> {
+    let invalid = || format!("Invalid Q-table state '{}'", state);
+    let mut chars = state.chars();
+    let turn = match chars.next() {
+        Some('B') => 0,
+        Some('W') => 1,
+        _ => return Err(invalid()),
+    };
+    let (mut black, mut white) = (0u64, 0u64);
+    let mut squares = 0;
+    for (index, c) in chars.enumerate() {
+        if index >= SIZE * SIZE {
+            return Err(invalid());
+        }
+        match c {
+            '0' => {}
+            '1' => black |= 1 << index,
+            '2' => white |= 1 << index,
+            _ => return Err(invalid()),
+        }
+        squares += 1;
+    }
+    if squares != SIZE * SIZE {
+        return Err(invalid());
+    }
+    Ok((black, white, turn))
+}
+
+/// Rebuilds the board hash encoded by [`encode_state`].
+fn decode_state(black: u64, white: u64, turn: u8) -> Result<String, String> {
+    if black & white != 0 {
+        return Err("Q-table file has overlapping discs".to_string());
+    }
+    let mut state = String::with_capacity(SIZE * SIZE + 1);
+    state.push(match turn {
+        0 => 'B',
+        1 => 'W',
+        _ => return Err(format!("Invalid side to move {} in Q-table file", turn)),
+    });
+    for index in 0..SIZE * SIZE {
+        state.push(if black >> index & 1 == 1 {
+            '1'
+        } else if white >> index & 1 == 1 {
+            '2'
+        } else {
+            '0'
+        });
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_table() -> QTable {
+        let board = Board::new();
+        let mut moved = board.clone();
+        moved.play_and_advance(2, 3).unwrap();
+        QTable::from([
+            (
+                board.to_hash(),
+                HashMap::from([("2D".to_string(), 12), ("3C".to_string(), -7)]),
+            ),
+            (moved.to_hash(), HashMap::from([("2C".to_string(), 1000)])),
+        ])
+    }
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let q_table = sample_table();
+        let mut bytes = Vec::new();
+        write_binary(&q_table, &mut bytes).unwrap();
+        assert_eq!(&bytes[..4], MAGIC);
+        assert_eq!(read_binary(&mut bytes.as_slice()).unwrap(), q_table);
+    }
+
+    #[test]
+    fn test_binary_is_smaller_than_json() {
+        let q_table = sample_table();
+        let mut bytes = Vec::new();
+        write_binary(&q_table, &mut bytes).unwrap();
+        let json = serde_json::to_string_pretty(&q_table).unwrap();
+        assert!(bytes.len() * 2 < json.len());
+    }
+
+    #[test]
+    fn test_large_values_are_quantised() {
+        let board = Board::new();
+        let q_table = QTable::from([(
+            board.to_hash(),
+            HashMap::from([("2D".to_string(), 100_000), ("3C".to_string(), -3)]),
+        )]);
+        let mut bytes = Vec::new();
+        write_binary(&q_table, &mut bytes).unwrap();
+        let loaded = read_binary(&mut bytes.as_slice()).unwrap();
+        let actions = &loaded[&board.to_hash()];
+        assert!((actions["2D"] - 100_000).abs() <= 4);
+        assert!(actions["3C"].abs() <= 4);
+    }
+
+    #[test]
+    fn test_outliers_keep_the_precision_of_other_states() {
+        let board = Board::new();
+        let mut moved = board.clone();
+        moved.play_and_advance(2, 3).unwrap();
+        let q_table = QTable::from([
+            (
+                board.to_hash(),
+                HashMap::from([("2D".to_string(), 5_000_000), ("3C".to_string(), 1)]),
+            ),
+            (
+                moved.to_hash(),
+                HashMap::from([("2C".to_string(), 7), ("4C".to_string(), -3)]),
+            ),
+        ]);
+        let mut bytes = Vec::new();
+        write_binary(&q_table, &mut bytes).unwrap();
+        let loaded = read_binary(&mut bytes.as_slice()).unwrap();
+
+        // Only the state of the outlier is rounded to its scale (153)
+        let outlier = &loaded[&board.to_hash()];
+        assert!((outlier["2D"] - 5_000_000).abs() <= 153 / 2);
+        assert_eq!(outlier["3C"], 0);
+        assert_eq!(loaded[&moved.to_hash()], q_table[&moved.to_hash()]);
+    }
+
+    #[test]
+    fn test_reads_tables_with_a_global_scale() {
+        let board = Board::new();
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(GLOBAL_SCALE_VERSION);
+        bytes.extend(4u32.to_le_bytes());
+        bytes.extend(1u64.to_le_bytes());
+        let (black, white, turn) = encode_state(&board.to_hash()).unwrap();
+        bytes.extend(black.to_le_bytes());
+        bytes.extend(white.to_le_bytes());
+        bytes.extend([turn, 1, (2 * SIZE + 3) as u8]);
+        bytes.extend(25i16.to_le_bytes());
+
+        let loaded = read_binary(&mut bytes.as_slice()).unwrap();
+        assert_eq!(
+            loaded,
+            QTable::from([(board.to_hash(), HashMap::from([("2D".to_string(), 100)]))])
+        );
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        let mut bytes = Vec::new();
+        write_binary(&sample_table(), &mut bytes).unwrap();
+
+        let mut other_magic = bytes.clone();
+        other_magic[0] = b'X';
+        assert_eq!(
+            read_binary(&mut other_magic.as_slice()),
+            Err("Not a Q-table file".to_string())
+        );
+        let mut other_version = bytes.clone();
+        other_version[4] = VERSION + 1;
+        assert_eq!(
+            read_binary(&mut other_version.as_slice()),
+            Err(format!("Unsupported Q-table version {}", VERSION + 1))
+        );
+        let truncated = &bytes[..bytes.len() - 1];
+        assert_eq!(
+            read_binary(&mut &truncated[..]),
+            Err("Q-table file is truncated".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convert_both_ways() {
+        let q_table = sample_table();
+        let json = temp_path("convert_q_table.json");
+        let binary = temp_path("convert_q_table.bin");
+        let back = temp_path("convert_q_table_back.json");
+        save_q_table(&q_table, &json).unwrap();
+
+        assert_eq!(convert_q_table(&json, &binary), Ok(2));
+        assert_eq!(convert_q_table(&binary, &back), Ok(2));
+        assert_eq!(load_q_table(&binary).unwrap(), q_table);
+        assert_eq!(load_q_table(&back).unwrap(), q_table);
+        assert!(std::fs::read_to_string(&back).unwrap().starts_with('{'));
+
+        for path in [json, binary, back] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Search statistics reported by the AI players.
+//!
+//! Every search AI attaches a `SearchInfo` to the `HistoryAction` of the move
+//! it plays. The numbers are shown on the game screen and saved with the game
+//! records, so heuristic or algorithm changes can be compared on node counts
+//! and speed instead of results only.
+
+use std::{fmt::Display, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Statistics of one search.
+///
+/// # Examples
+///
+/// ```rust
+/// let info = SearchInfo::new(20000, Duration::from_millis(500), 4, 12, vec!["2D".into(), "2C".into()]);
+/// assert_eq!(info.nps, 40000);
+/// println!("{}", info); // "4 plies, 20000 nodes in 500 ms (40000 nps), score 12, pv 2D 2C"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchInfo {
+    /// Number of positions visited (playouts for MCTS)
+    pub nodes: u64,
+    /// Time taken by the search
+    pub time: Duration,
+    /// Nodes visited per second
+    pub nps: u64,
+    /// Deepest ply reached by the search
+    pub depth: usize,
+    /// Score of the played move (win rate in percent for MCTS)
+    pub best_score: isize,
+    /// Principal variation: the expected line, starting with the played move
+    pub pv: Vec<String>,
+}
+
+impl SearchInfo {
+    /// Creates the statistics of a search and computes its speed.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - Number of positions visited
+    /// * `time` - Time taken by the search
+    /// * `depth` - Deepest ply reached
+    /// * `best_score` - Score of the played move
+    /// * `pv` - Coordinates of the principal variation
+    pub fn new(
+        nodes: u64,
+        time: Duration,
+        depth: usize,
+        best_score: isize,
+        pv: Vec<String>,
+    ) -> Self {
+        let nps = match time.as_micros() {
+            0 => nodes,
+            micros => (nodes as u128 * 1_000_000 / micros) as u64,
+        };
+        Self {
+            nodes,
+            time,
+            nps,
+            depth,
+            best_score,
+            pv,
+        }
+    }
+}
+
+impl Display for SearchInfo {
+    /// Formats the statistics on a single line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} plies, {} nodes in {} ms ({} nps), score {}, pv {}",
+            self.depth,
+            self.nodes,
+            self.time.as_millis(),
+            self.nps,
+            self.best_score,
+            self.pv.join(" ")
+        )
+    }
+}
+
+/// Counters updated by the recursive calls of a tree search.
+///
+/// Each search thread owns its counters; they are merged once the threads
+/// are joined.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SearchCounters {
+    /// Number of positions visited
+    pub nodes: u64,
+    /// Deepest ply visited
+    pub max_ply: usize,
+}
+
+impl SearchCounters {
+    /// Records a visit of a position at `ply`.
+    pub fn visit(&mut self, ply: usize) {
+        self.nodes += 1;
+        self.max_ply = self.max_ply.max(ply);
+    }
+
+    /// Adds the counters of another thread.
+    pub fn merge(&mut self, other: SearchCounters) {
+        self.nodes += other.nodes;
+        self.max_ply = self.max_ply.max(other.max_ply);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nps() {
+        let info = SearchInfo::new(3000, Duration::from_millis(1500), 3, 0, vec![]);
+        assert_eq!(info.nps, 2000);
+        let instant = SearchInfo::new(10, Duration::ZERO, 1, 0, vec![]);
+        assert_eq!(instant.nps, 10);
+    }
+
+    #[test]
+    fn test_counters_merge() {
+        let mut counters = SearchCounters::default();
+        counters.visit(1);
+        counters.visit(3);
+        let mut other = SearchCounters::default();
+        other.visit(5);
+        counters.merge(other);
+        assert_eq!(counters.nodes, 3);
+        assert_eq!(counters.max_ply, 5);
+    }
+}
@@ -0,0 +1,401 @@
+//! Texel tuning of the weighted evaluation from labelled positions.
+//!
+//! Instead of playing matches like the genetic tuner, Texel tuning fits the
+//! weights of an evaluation profile to positions whose outcome is known. The
+//! evaluation of a position is turned into an expected score between 0 and
+//! 1 with a sigmoid, and the weights are moved to minimize the mean squared
+//! difference between the expected score and the real one, over the whole
+//! set of positions:
+//!
+//! ```text
+//! error = mean((target - sigmoid(k * evaluation))²)
+//! ```
+//!
+//! The scale `k` is fitted once to the starting weights, then every phase
+//! weight is tried a step up and a step down, keeping the changes that lower
+//! the error, with smaller steps once no change helps. The run only depends
+//! on the positions, so it is reproducible.
+//!
+//! Positions come from saved games or from a position file, one position per
+//! line: the board hash of `Board::to_hash` and the final disc difference
+//! for Black, separated by a space. Empty lines and lines starting with `#`
+//! are skipped.
+//!
+//! ```text
+//! # turn + 64 cells, final Black discs - White discs
+//! B0000000000000000000000000002100000012000000000000000000000000000 -6
+//! ```
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+use crate::{
+    ai::{
+        eval_profile::{EvalProfile, Feature, PhaseWeights},
+        heuristic_matrix::AIHeuristicMatrix,
+    },
+    game::{board::Board, cell::Cell},
+};
+
+/// Number of tuned weights: three phase weights per feature.
+const NB_PARAMETERS: usize = 3 * Feature::ALL.len();
+
+/// Smallest weight step tried before the local search stops.
+const MIN_STEP: f64 = 0.01;
+
+/// What the expected score of a position is compared with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    /// 1 for a Black win, 0.5 for a draw, 0 for a loss
+    Result,
+    /// The final disc difference mapped from [-64, 64] to [0, 1]
+    Discs,
+}
+
+impl Target {
+    /// Returns the score to predict for a final disc difference.
+    fn score(&self, disc_difference: f64) -> f64 {
+        match self {
+            Target::Result if disc_difference > 0.0 => 1.0,
+            Target::Result if disc_difference < 0.0 => 0.0,
+            Target::Result => 0.5,
+            Target::Discs => (0.5 + disc_difference / 128.0).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// A position with the final disc difference of its game, for Black.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelledPosition {
+    /// The position
+    pub board: Board,
+    /// Final Black discs minus White discs
+    pub disc_difference: f64,
+}
+
+/// Labels the positions of a finished game with its result.
+///
+/// # Arguments
+///
+/// * `positions` - The positions of the game, final position included
+///
+/// # Returns
+///
+/// The labelled positions, or none if the last position is not the end of
+/// the game.
+pub fn game_positions(positions: &[Board]) -> Vec<LabelledPosition> {
+    let Some(last) = positions.last() else {
+        return Vec::new();
+    };
+    if !last.is_game_over() {
+        return Vec::new();
+    }
+    let disc_difference = last.get_nb_discs(Cell::Black).unwrap_or(0) as f64
+        - last.get_nb_discs(Cell::White).unwrap_or(0) as f64;
+    positions
+        .iter()
+        .map(|board| LabelledPosition {
+            board: board.clone(),
+            disc_difference,
+        })
+        .collect()
+}
+
+/// Reads a position file.
+///
+/// # Returns
+///
+/// * `Ok(Vec<LabelledPosition>)` - Every position of the file
+/// * `Err(String)` - If the file cannot be read or a line is invalid
+pub fn load_positions(file_path: &str) -> Result<Vec<LabelledPosition>, String> {
+    let file = File::open(file_path).map_err(|e| format!("{}: {}", file_path, e))?;
+    let mut positions = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| e.to_string())?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |e: String| format!("{} line {}: {}", file_path, number + 1, e);
+        let (hash, label) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| error("expected a board hash and a disc difference".to_string()))?;
+        let board = Board::from_hash(hash).map_err(error)?;
+        let disc_difference = label
+            .trim()
+            .parse()
+            .map_err(|_| error(format!("invalid disc difference '{}'", label.trim())))?;
+        positions.push(LabelledPosition {
+            board,
+            disc_difference,
+        });
+    }
+    Ok(positions)
+}
+
+/// A position reduced to what the error depends on.
+struct Sample {
+    /// Coefficient of every phase weight in the evaluation
+    inputs: [f64; NB_PARAMETERS],
+    /// Score to predict
+    target: f64,
+}
+
+/// Fits the weights of an evaluation profile to labelled positions.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut tuner = TexelTuner::new(EvalProfile::default(), &positions, Target::Result);
+/// let profile = tuner.tune(100, |pass, error| println!("Pass {}: {:.5}", pass, error));
+/// profile.save("texel_profile.json").unwrap();
+/// ```
+pub struct TexelTuner {
+    /// Profile being tuned, its weights updated by `tune`
+    profile: EvalProfile,
+    /// Current value of every phase weight
+    parameters: [f64; NB_PARAMETERS],
+    /// The positions
+    samples: Vec<Sample>,
+    /// Scale turning an evaluation into an expected score
+    k: f64,
+}
+
+impl TexelTuner {
+    /// Prepares the tuning of a profile.
+    ///
+    /// Every feature of every position is computed once, for Black. The
+    /// `Matrix` feature reads the matrix of the profile, or matrix A when
+    /// the profile has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The starting profile
+    /// * `positions` - The labelled positions
+    /// * `target` - What the expected scores are compared with
+    pub fn new(profile: EvalProfile, positions: &[LabelledPosition], target: Target) -> Self {
+        let samples = positions
+            .iter()
+            .map(|position| {
+                let coefficients = PhaseWeights::coefficients(EvalProfile::phase(&position.board));
+                let mut inputs = [0.0; NB_PARAMETERS];
+                for (i, feature) in Feature::ALL.into_iter().enumerate() {
+                    let value = profile.feature_value(
+                        feature,
+                        &position.board,
+                        Cell::Black,
+                        &AIHeuristicMatrix::A,
+                    ) as f64;
+                    for (phase, coefficient) in coefficients.iter().enumerate() {
+                        inputs[3 * i + phase] = coefficient * value;
+                    }
+                }
+                Sample {
+                    inputs,
+                    target: target.score(position.disc_difference),
+                }
+            })
+            .collect();
+
+        let mut parameters = [0.0; NB_PARAMETERS];
+        for (i, feature) in Feature::ALL.iter().enumerate() {
+            let weights = profile.weights.get(feature).copied().unwrap_or_default();
+            parameters[3 * i..3 * i + 3].copy_from_slice(&[
+                weights.opening,
+                weights.midgame,
+                weights.endgame,
+            ]);
+        }
+
+        let mut tuner = Self {
+            profile,
+            parameters,
+            samples,
+            k: 1.0,
+        };
+        tuner.k = tuner.fit_k();
+        tuner
+    }
+
+    /// Returns the scale of the sigmoid.
+    pub fn k(&self) -> f64 {
+        self.k
+    }
+
+    /// Returns the mean squared error of the current weights.
+    pub fn error(&self) -> f64 {
+        self.error_with(&self.parameters, self.k)
+    }
+
+    /// Runs the local search and returns the tuned profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_passes` - Maximum number of passes over the weights
+    /// * `progress` - Called after every pass with its number and the error
+    pub fn tune(&mut self, max_passes: usize, mut progress: impl FnMut(usize, f64)) -> EvalProfile {
+        let mut error = self.error();
+        let mut step = 1.0;
+        for pass in 1..=max_passes {
+            if step < MIN_STEP {
+                break;
+            }
+            let mut improved = false;
+            for i in 0..NB_PARAMETERS {
+                // Steps follow the size of the weight, so small and large
+                // weights both move
+                let delta = step * self.parameters[i].abs().max(1.0);
+                for candidate in [self.parameters[i] + delta, self.parameters[i] - delta] {
+                    let mut parameters = self.parameters;
+                    parameters[i] = candidate;
+                    let candidate_error = self.error_with(&parameters, self.k);
+                    if candidate_error < error {
+                        self.parameters = parameters;
+                        error = candidate_error;
+                        improved = true;
+                        break;
+                    }
+                }
+            }
+            if !improved {
+                step /= 2.0;
+            }
+            progress(pass, error);
+        }
+        self.profile()
+    }
+
+    /// Returns the profile with the current weights.
+    pub fn profile(&self) -> EvalProfile {
+        let mut profile = self.profile.clone();
+        profile.weights.clear();
+        for (i, feature) in Feature::ALL.into_iter().enumerate() {
+            let round = |weight: f64| (weight * 1000.0).round() / 1000.0;
+            let weights = PhaseWeights::new(
+                round(self.parameters[3 * i]),
+                round(self.parameters[3 * i + 1]),
+                round(self.parameters[3 * i + 2]),
+            );
+            if !weights.is_zero() {
+                profile.weights.insert(feature, weights);
+            }
+        }
+        profile
+    }
+
+    /// Returns the mean squared error of some weights and scale.
+    fn error_with(&self, parameters: &[f64; NB_PARAMETERS], k: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = self
+            .samples
+            .iter()
+            .map(|sample| {
+                let evaluation: f64 = sample
+                    .inputs
+                    .iter()
+                    .zip(parameters)
+                    .map(|(input, weight)| input * weight)
+                    .sum();
+                let expected = 1.0 / (1.0 + (-k * evaluation).exp());
+                (sample.target - expected).powi(2)
+            })
+            .sum();
+        total / self.samples.len() as f64
+    }
+
+    /// Finds the scale minimizing the error of the starting weights.
+    ///
+    /// The scale is searched on a logarithmic grid, then refined around the
+    /// best point.
+    fn fit_k(&self) -> f64 {
+        let error = |k: f64| self.error_with(&self.parameters, k);
+        let mut best = (1..=60)
+            .map(|i| 10f64.powf(-6.0 + i as f64 / 10.0))
+            .min_by(|&a, &b| error(a).total_cmp(&error(b)))
+            .unwrap_or(1.0);
+        let mut factor = 10f64.powf(0.05);
+        for _ in 0..20 {
+            best = [best / factor, best, best * factor]
+                .into_iter()
+                .min_by(|&a, &b| error(a).total_cmp(&error(b)))
+                .unwrap_or(best);
+            factor = factor.sqrt();
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{heuristic::HeuristicType, pattern::self_play_game};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_position_file() {
+        let board = {
+            let mut board = Board::new();
+            board.play_and_advance(2, 3).unwrap();
+            board
+        };
+        let path = std::env::temp_dir().join("othello_test_positions.txt");
+        let path = path.to_str().unwrap();
+        let content = format!(
+            "# comment\n\n{} -6\n{} 12.5\n",
+            board.to_hash(),
+            Board::new().to_hash()
+        );
+        std::fs::write(path, content).unwrap();
+        let positions = load_positions(path).unwrap();
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[0].board.to_hash(), board.to_hash());
+        assert_eq!(positions[0].disc_difference, -6.0);
+        assert_eq!(positions[1].disc_difference, 12.5);
+
+        std::fs::write(path, format!("{}\n", board.to_hash())).unwrap();
+        assert!(load_positions(path).unwrap_err().contains("line 1"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_targets() {
+        assert_eq!(Target::Result.score(10.0), 1.0);
+        assert_eq!(Target::Result.score(0.0), 0.5);
+        assert_eq!(Target::Result.score(-2.0), 0.0);
+        assert_eq!(Target::Discs.score(64.0), 1.0);
+        assert_eq!(Target::Discs.score(-32.0), 0.25);
+    }
+
+    #[test]
+    fn test_tuning_lowers_the_error() {
+        let mut positions = Vec::new();
+        let mut seed = 0;
+        while positions.len() < 200 {
+            positions.extend(game_positions(
+                &self_play_game(&HeuristicType::Mixte, 6, 1, seed).unwrap(),
+            ));
+            seed += 1;
+        }
+        // Start from a profile that only counts discs
+        let profile = EvalProfile {
+            name: "Discs".to_string(),
+            weights: BTreeMap::from([(Feature::Discs, PhaseWeights::new(1.0, 1.0, 1.0))]),
+            matrix: None,
+        };
+        let mut tuner = TexelTuner::new(profile.clone(), &positions, Target::Discs);
+        assert!(tuner.k() > 0.0);
+        let before = tuner.error();
+        assert_eq!(tuner.profile(), profile);
+
+        let mut passes = 0;
+        let tuned = tuner.tune(5, |_, _| passes += 1);
+        assert!(passes > 0);
+        assert!(tuner.error() < before);
+        assert_eq!(tuned.name, "Discs");
+        assert_ne!(tuned.weights, profile.weights);
+    }
+}
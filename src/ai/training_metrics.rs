@@ -0,0 +1,379 @@
+//! Metrics of a Q-learning training.
+//!
+//! After every epoch, the training sends a [`TrainingMetrics`] to the
+//! loading screen, which charts them, and appends it to a CSV log saved next
+//! to the Q-table (see [`metrics_log_path`]). Rewards, wins and speed are
+//! measured over the last games only, so the charts follow the learning
+//! instead of flattening out as the training goes on.
+//!
+//! Every `QLEARNING_EVALUATION_EPOCHS` epochs, the training also plays greedy
+//! evaluation games against each of the [`EVALUATION_OPPONENTS`], and their
+//! win, draw and loss rates are sent with the metrics of that epoch. Unlike
+//! the training rewards, these show whether the agent actually plays better.
+
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+
+/// Columns of the CSV metrics log, before the evaluation columns.
+const CSV_COLUMNS: &str = "epoch,epsilon,average_reward,win_rate,q_table_size,epochs_per_second";
+
+/// Opponent of the evaluation games played during training.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EvaluationOpponent {
+    /// Plays uniformly random legal moves
+    Random,
+    /// Alpha-Beta search at the given depth
+    AlphaBeta(usize),
+}
+
+/// Opponents every evaluation plays against, from the weakest.
+pub const EVALUATION_OPPONENTS: [EvaluationOpponent; 4] = [
+    EvaluationOpponent::Random,
+    EvaluationOpponent::AlphaBeta(1),
+    EvaluationOpponent::AlphaBeta(2),
+    EvaluationOpponent::AlphaBeta(3),
+];
+
+impl Display for EvaluationOpponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvaluationOpponent::Random => write!(f, "Random"),
+            EvaluationOpponent::AlphaBeta(depth) => write!(f, "Alpha-Beta {}", depth),
+        }
+    }
+}
+
+impl EvaluationOpponent {
+    /// Returns the prefix of the opponent's columns in the CSV log.
+    fn csv_name(&self) -> String {
+        match self {
+            EvaluationOpponent::Random => "random".to_string(),
+            EvaluationOpponent::AlphaBeta(depth) => format!("alphabeta{}", depth),
+        }
+    }
+}
+
+/// Results of the evaluation games against one opponent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvaluationResult {
+    /// Opponent of the games
+    pub opponent: EvaluationOpponent,
+    /// Games won by the AI
+    pub wins: usize,
+    /// Drawn games
+    pub draws: usize,
+    /// Games lost by the AI
+    pub losses: usize,
+    /// Error of the last game that could not be played, if any
+    pub error: Option<String>,
+}
+
+impl EvaluationResult {
+    /// Creates the empty result of the games against `opponent`.
+    pub fn new(opponent: EvaluationOpponent) -> Self {
+        EvaluationResult {
+            opponent,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            error: None,
+        }
+    }
+
+    /// Returns the share of games won, between 0 and 1.
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    /// Returns the share of games drawn, between 0 and 1.
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    /// Returns the share of games lost, between 0 and 1.
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+
+    /// Returns the share of the games given by `count`.
+    fn rate(&self, count: usize) -> f64 {
+        let games = self.wins + self.draws + self.losses;
+        if games == 0 {
+            0.0
+        } else {
+            count as f64 / games as f64
+        }
+    }
+}
+
+/// Metrics of a Q-learning training after an epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrainingMetrics {
+    /// Number of epochs completed
+    pub epoch: usize,
+    /// Total number of epochs of the training
+    pub epochs: usize,
+    /// Exploration rate of the last game
+    pub epsilon: f64,
+    /// Average total reward of the last games
+    pub average_reward: f64,
+    /// Share of the last games won by the AI's colour, between 0 and 1
+    pub win_rate: f64,
+    /// Number of states in the Q-table
+    pub q_table_size: usize,
+    /// Training speed over the last games
+    pub epochs_per_second: f64,
+    /// Results of the evaluation games played after this epoch, if any
+    pub evaluation: Option<Vec<EvaluationResult>>,
+    /// Problem met by the training so far, like a failed evaluation game or
+    /// an unwritable log, shown on the training screen
+    pub status: Option<String>,
+}
+
+impl TrainingMetrics {
+    /// Returns the share of the training completed, between 0 and 1.
+    pub fn progress(&self) -> f64 {
+        if self.epochs == 0 {
+            1.0
+        } else {
+            self.epoch as f64 / self.epochs as f64
+        }
+    }
+
+    /// Returns the metrics as a line of the CSV log.
+    ///
+    /// The evaluation columns are left empty for epochs without evaluation.
+    pub fn to_csv(&self) -> String {
+        let mut line = format!(
+            "{},{:.4},{:.2},{:.4},{},{:.2}",
+            self.epoch,
+            self.epsilon,
+            self.average_reward,
+            self.win_rate,
+            self.q_table_size,
+            self.epochs_per_second
+        );
+        for opponent in EVALUATION_OPPONENTS {
+            let result = self
+                .evaluation
+                .iter()
+                .flatten()
+                .find(|result| result.opponent == opponent);
+            match result {
+                Some(result) => line.push_str(&format!(
+                    ",{:.4},{:.4},{:.4}",
+                    result.win_rate(),
+                    result.draw_rate(),
+                    result.loss_rate()
+                )),
+                None => line.push_str(",,,"),
+            }
+        }
+        line
+    }
+}
+
+/// Rolling record of the last training games, turned into metrics.
+pub struct MetricsTracker {
+    /// Number of games the metrics are measured over
+    window: usize,
+    /// Total reward, win and start time of the last games
+    games: VecDeque<(isize, bool, Instant)>,
+    /// Start time of the current game
+    game_start: Instant,
+}
+
+impl MetricsTracker {
+    /// Creates a tracker measuring the metrics over the last `window` games.
+    pub fn new(window: usize) -> Self {
+        MetricsTracker {
+            window: window.max(1),
+            games: VecDeque::new(),
+            game_start: Instant::now(),
+        }
+    }
+
+    /// Records a finished game and starts timing the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `reward` - Total reward of the game
+    /// * `won` - Whether the AI's colour won the game
+    pub fn record(&mut self, reward: isize, won: bool) {
+        if self.games.len() == self.window {
+            self.games.pop_front();
+        }
+        self.games.push_back((reward, won, self.game_start));
+        self.game_start = Instant::now();
+    }
+
+    /// Returns the metrics of the recorded games.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch` - Number of epochs completed
+    /// * `epochs` - Total number of epochs of the training
+    /// * `epsilon` - Exploration rate of the last game
+    /// * `q_table_size` - Number of states in the Q-table
+    pub fn metrics(
+        &self,
+        epoch: usize,
+        epochs: usize,
+        epsilon: f64,
+        q_table_size: usize,
+    ) -> TrainingMetrics {
+        let games = self.games.len().max(1) as f64;
+        let elapsed = self
+            .games
+            .front()
+            .map_or(0.0, |(_, _, start)| start.elapsed().as_secs_f64());
+        TrainingMetrics {
+            epoch,
+            epochs,
+            epsilon,
+            average_reward: self
+                .games
+                .iter()
+                .map(|(reward, _, _)| reward)
+                .sum::<isize>() as f64
+                / games,
+            win_rate: self.games.iter().filter(|(_, won, _)| *won).count() as f64 / games,
+            q_table_size,
+            epochs_per_second: if elapsed > 0.0 {
+                self.games.len() as f64 / elapsed
+            } else {
+                0.0
+            },
+            evaluation: None,
+            status: None,
+        }
+    }
+}
+
+/// Returns the header of the CSV metrics log.
+fn csv_header() -> String {
+    let mut header = CSV_COLUMNS.to_string();
+    for opponent in EVALUATION_OPPONENTS {
+        let name = opponent.csv_name();
+        header.push_str(&format!(
+            ",{}_win_rate,{}_draw_rate,{}_loss_rate",
+            name, name, name
+        ));
+    }
+    header
+}
+
+/// Returns the path of the CSV metrics log saved next to a Q-table.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(
+///     metrics_log_path("q_tables/q_table_black.bin"),
+///     "q_tables/q_table_black.metrics.csv"
+/// );
+/// ```
+pub fn metrics_log_path(q_table_path: &str) -> String {
+    format!(
+        "{}.metrics.csv",
+        Path::new(q_table_path).with_extension("").to_string_lossy()
+    )
+}
+
+/// Opens the CSV metrics log of a training.
+///
+/// A new training starts a new log with its header. A resumed training
+/// appends to the existing log, which keeps the rows of the first run.
+///
+/// # Arguments
+///
+/// * `path` - Path of the log, see [`metrics_log_path`]
+/// * `resume` - Whether the rows are appended to an existing log
+pub fn open_metrics_log(path: &str, resume: bool) -> Result<BufWriter<File>, String> {
+    let append = resume && Path::new(path).exists();
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|e| e.to_string())?;
+    let mut log = BufWriter::new(file);
+    if !append {
+        writeln!(log, "{}", csv_header()).map_err(|e| e.to_string())?;
+    }
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_cover_the_last_games() {
+        let mut tracker = MetricsTracker::new(2);
+        tracker.record(100, false);
+        tracker.record(10, true);
+        tracker.record(30, true);
+
+        let metrics = tracker.metrics(3, 4, 0.5, 42);
+        assert_eq!(metrics.average_reward, 20.0);
+        assert_eq!(metrics.win_rate, 1.0);
+        assert_eq!(metrics.q_table_size, 42);
+        assert_eq!(metrics.progress(), 0.75);
+        assert!(metrics.epochs_per_second > 0.0);
+    }
+
+    #[test]
+    fn test_no_game_gives_empty_metrics() {
+        let metrics = MetricsTracker::new(10).metrics(0, 0, 1.0, 0);
+        assert_eq!(metrics.average_reward, 0.0);
+        assert_eq!(metrics.win_rate, 0.0);
+        assert_eq!(metrics.epochs_per_second, 0.0);
+        assert_eq!(metrics.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_resumed_log_keeps_its_rows() {
+        let path = std::env::temp_dir()
+            .join(format!("{}_metrics.csv", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let metrics = MetricsTracker::new(1).metrics(1, 2, 0.25, 7);
+        for resume in [false, true] {
+            let mut log = open_metrics_log(&path, resume).unwrap();
+            writeln!(log, "{}", metrics.to_csv()).unwrap();
+        }
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let row = "1,0.2500,0.00,0.0000,7,0.00,,,,,,,,,,,,";
+        assert_eq!(content, format!("{}\n{}\n{}\n", csv_header(), row, row));
+    }
+
+    #[test]
+    fn test_evaluation_is_logged_in_its_columns() {
+        let mut metrics = MetricsTracker::new(1).metrics(500, 1000, 0.5, 7);
+        let mut result = EvaluationResult::new(EvaluationOpponent::AlphaBeta(2));
+        result.wins = 2;
+        result.draws = 1;
+        result.losses = 1;
+        metrics.evaluation = Some(vec![result]);
+
+        let row = metrics.to_csv();
+        let columns: Vec<&str> = row.split(',').skip(6).collect();
+        let header = csv_header();
+        let names: Vec<&str> = header.split(',').skip(6).collect();
+        assert_eq!(columns.len(), names.len());
+        assert_eq!(names[3], "alphabeta1_win_rate");
+        assert_eq!(&columns[..6], ["", "", "", "", "", ""]);
+        assert_eq!(&columns[6..9], ["0.5000", "0.2500", "0.2500"]);
+        assert_eq!(&columns[9..], ["", "", ""]);
+    }
+}
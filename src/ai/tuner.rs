@@ -0,0 +1,500 @@
+//! Genetic tuning of the weighted evaluation by self-play.
+//!
+//! The heuristic matrices and the phase switches of `Mixte` were picked by
+//! hand. The tuner evolves a population of evaluation profiles instead, each
+//! carrying its own symmetric matrix and its own phase weights, and ranks
+//! them by playing Alpha-Beta matches between them at a fixed depth:
+//!
+//! 1. Every pair of profiles plays every opening of the generation twice,
+//!    once with each color. The openings are a few random moves.
+//! 2. The fitness of a profile is its share of the points (1 for a win,
+//!    0.5 for a draw) over all its games.
+//! 3. The best profiles are kept, the others are replaced by children of
+//!    profiles picked by tournament, mixing their genes and mutating them.
+//!
+//! All randomness comes from the seed, so a run can be replayed, and the
+//! whole state is written to a checkpoint after every generation so a long
+//! run can be stopped and resumed (see the `tune` command).
+
+use std::{fs::File, io::Write, sync::Arc, thread};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai::{
+        algo::alphabeta::AIAlphaBeta,
+        eval_profile::{EvalProfile, Feature},
+        heuristic::HeuristicType,
+        heuristic_matrix::{set_symmetric, AIHeuristicMatrix, CustomMatrix},
+        opening_book::BookMode,
+    },
+    game::{arena::play_game, board::Board, cell::Cell, player::Player},
+};
+
+/// Number of profiles drawn for every tournament selection.
+const TOURNAMENT_SIZE: usize = 3;
+
+/// Black and White profiles of a game, and its winner.
+type GameResult = (usize, usize, Option<Cell>);
+
+/// Settings of a tuning run, saved in its checkpoints.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TunerConfig {
+    /// Number of profiles in every generation
+    pub population: usize,
+    /// Generation to stop after
+    pub generations: usize,
+    /// Alpha-Beta depth of the matches
+    pub depth: usize,
+    /// Openings of every generation, each played twice by every pair
+    pub openings: usize,
+    /// Random moves of every opening
+    pub random_plies: usize,
+    /// Best profiles copied unchanged into the next generation
+    pub elite: usize,
+    /// Probability of mutating every gene of a child
+    pub mutation_rate: f64,
+    /// Seed of all the random choices
+    pub seed: u64,
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        Self {
+            population: 10,
+            generations: 20,
+            depth: 2,
+            openings: 2,
+            random_plies: 6,
+            elite: 2,
+            mutation_rate: 0.2,
+            seed: 0,
+        }
+    }
+}
+
+/// Fitness of a generation, for the report.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// Generation number, from 1
+    pub generation: usize,
+    /// Fitness of the best profile
+    pub best: f64,
+    /// Mean fitness of the population
+    pub mean: f64,
+    /// Fitness of the worst profile
+    pub worst: f64,
+}
+
+/// State of a tuning run, which is also its checkpoint.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut tuner = Tuner::new(TunerConfig::default());
+/// while !tuner.is_done() {
+///     let report = tuner.run_generation()?;
+///     println!("Generation {}: best {:.2}", report.generation, report.best);
+///     tuner.save("tuner_checkpoint.json")?;
+/// }
+/// tuner.best().unwrap().save("tuned_profile.json")?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tuner {
+    /// Settings of the run
+    pub config: TunerConfig,
+    /// Number of generations already evaluated
+    generation: usize,
+    /// Profiles of the next generation to evaluate
+    population: Vec<EvalProfile>,
+    /// Best profile of the last evaluated generation
+    best: Option<EvalProfile>,
+    /// Fitness of every evaluated generation
+    history: Vec<GenerationReport>,
+}
+
+impl Tuner {
+    /// Starts a tuning run.
+    ///
+    /// The first generation holds the built-in profile with matrix A and
+    /// matrix B, and mutated copies of them.
+    pub fn new(config: TunerConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let seeds: Vec<EvalProfile> = [AIHeuristicMatrix::A, AIHeuristicMatrix::B]
+            .iter()
+            .map(|matrix| {
+                let values = matrix.value().map(|row| row.to_vec()).to_vec();
+                EvalProfile {
+                    name: format!("Default with {}", matrix),
+                    matrix: CustomMatrix::new(&matrix.to_string(), values).ok(),
+                    ..EvalProfile::default()
+                }
+            })
+            .collect();
+        let population = (0..config.population)
+            .map(|i| {
+                let mut profile = seeds[i % seeds.len()].clone();
+                if i >= seeds.len() {
+                    mutate(&mut profile, config.mutation_rate, &mut rng);
+                }
+                profile
+            })
+            .collect();
+        Self {
+            config,
+            generation: 0,
+            population,
+            best: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Loads a run from a checkpoint file.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Tuner)` - The run, ready to evaluate its next generation
+    /// * `Err(String)` - If the file cannot be read or is not a checkpoint
+    pub fn load(file_path: &str) -> Result<Self, String> {
+        match File::open(file_path) {
+            Ok(file) => serde_json::from_reader(file)
+                .map_err(|e| format!("Could not deserialize tuner checkpoint: {}", e)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Saves the run to a checkpoint file.
+    pub fn save(&self, file_path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Could not serialize tuner checkpoint: {}", e))?;
+        let mut file = File::create(file_path).map_err(|e| e.to_string())?;
+        file.write_all(json.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Tells whether the configured number of generations was reached.
+    pub fn is_done(&self) -> bool {
+        self.generation >= self.config.generations
+    }
+
+    /// Returns the best profile of the last evaluated generation.
+    pub fn best(&self) -> Option<&EvalProfile> {
+        self.best.as_ref()
+    }
+
+    /// Returns the fitness history as CSV, one line per generation.
+    pub fn report_csv(&self) -> String {
+        let mut csv = "generation,best,mean,worst\n".to_string();
+        for report in &self.history {
+            csv += &format!(
+                "{},{:.4},{:.4},{:.4}\n",
+                report.generation, report.best, report.mean, report.worst
+            );
+        }
+        csv
+    }
+
+    /// Evaluates the current generation and breeds the next one.
+    ///
+    /// The random choices of a generation only depend on the seed and the
+    /// generation number, so a resumed run goes on exactly like an
+    /// uninterrupted one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(&GenerationReport)` - The fitness of the evaluated generation
+    /// * `Err(String)` - If a match could not be played
+    pub fn run_generation(&mut self) -> Result<&GenerationReport, String> {
+        let generation = self.generation + 1;
+        let mut rng = StdRng::seed_from_u64(self.config.seed.wrapping_add(generation as u64));
+        let openings: Vec<Board> = (0..self.config.openings.max(1))
+            .map(|_| random_opening(self.config.random_plies, &mut rng))
+            .collect::<Result<_, _>>()?;
+        let fitness = self.fitness(&openings)?;
+
+        let mut ranking: Vec<usize> = (0..self.population.len()).collect();
+        ranking.sort_by(|&a, &b| fitness[b].total_cmp(&fitness[a]));
+        let mut best = self.population[ranking[0]].clone();
+        best.name = format!("Tuned generation {}", generation);
+        if let Some(matrix) = best.matrix.as_mut() {
+            matrix.name = best.name.clone();
+        }
+
+        let mut next: Vec<EvalProfile> = ranking
+            .iter()
+            .take(self.config.elite.min(self.population.len()))
+            .map(|&i| self.population[i].clone())
+            .collect();
+        while next.len() < self.population.len() {
+            let first = tournament(&fitness, &mut rng);
+            let second = tournament(&fitness, &mut rng);
+            let mut child = crossover(&self.population[first], &self.population[second], &mut rng);
+            mutate(&mut child, self.config.mutation_rate, &mut rng);
+            next.push(child);
+        }
+
+        self.history.push(GenerationReport {
+            generation,
+            best: fitness[ranking[0]],
+            mean: fitness.iter().sum::<f64>() / fitness.len() as f64,
+            worst: fitness[ranking[ranking.len() - 1]],
+        });
+        self.generation = generation;
+        self.population = next;
+        self.best = Some(best);
+        Ok(self.history.last().unwrap())
+    }
+
+    /// Plays the round robin of the population and returns every fitness.
+    ///
+    /// The games are spread over the available cores. A search at a fixed
+    /// depth always finds the same move, so the results do not depend on
+    /// the order the games are played in.
+    fn fitness(&self, openings: &[Board]) -> Result<Vec<f64>, String> {
+        let heuristics: Vec<HeuristicType> = self
+            .population
+            .iter()
+            .map(|profile| HeuristicType::Weighted(Arc::new(profile.clone())))
+            .collect();
+        let mut games = Vec::new();
+        for first in 0..heuristics.len() {
+            for second in first + 1..heuristics.len() {
+                for opening in openings {
+                    games.push((first, second, opening));
+                    games.push((second, first, opening));
+                }
+            }
+        }
+
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = games.len().div_ceil(threads).max(1);
+        let depth = self.config.depth;
+        let results: Vec<Result<Vec<GameResult>, String>> = thread::scope(|scope| {
+            let handles: Vec<_> = games
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let heuristics = &heuristics;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|&(black, white, opening)| {
+                                let board = play_match(
+                                    &heuristics[black],
+                                    &heuristics[white],
+                                    depth,
+                                    opening.clone(),
+                                )?;
+                                Ok((black, white, board.get_winner()))
+                            })
+                            .collect()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or(Err("A match panicked".to_string())))
+                .collect()
+        });
+
+        let mut points = vec![0.0; heuristics.len()];
+        let mut played = vec![0; heuristics.len()];
+        for (black, white, winner) in results.into_iter().collect::<Result<Vec<_>, _>>()?.concat() {
+            match winner {
+                Some(Cell::Black) => points[black] += 1.0,
+                Some(Cell::White) => points[white] += 1.0,
+                _ => {
+                    points[black] += 0.5;
+                    points[white] += 0.5;
+                }
+            }
+            played[black] += 1;
+            played[white] += 1;
+        }
+        Ok(points
+            .iter()
+            .zip(played)
+            .map(|(points, played)| {
+                if played == 0 {
+                    0.0
+                } else {
+                    points / played as f64
+                }
+            })
+            .collect())
+    }
+}
+
+/// Plays one game between two evaluations from an opening position.
+fn play_match(
+    black: &HeuristicType,
+    white: &HeuristicType,
+    depth: usize,
+    opening: Board,
+) -> Result<Board, String> {
+    let [black, white] = [(black, Cell::Black), (white, Cell::White)].map(|(heuristic, color)| {
+        let mut ai = AIAlphaBeta::new(depth, heuristic.clone(), color, AIHeuristicMatrix::A);
+        ai.set_book_mode(BookMode::Off);
+        ai.set_threads(1);
+        ai
+    });
+    play_game(&black, &white, opening)
+}
+
+/// Plays random legal moves from the starting position.
+fn random_opening(plies: usize, rng: &mut StdRng) -> Result<Board, String> {
+    let mut board = Board::new();
+    for _ in 0..plies {
+        let moves = board
+            .has_legal_moves(board.get_player_turn())
+            .unwrap_or_default();
+        if moves.is_empty() {
+            break;
+        }
+        let (row, col) = moves[rng.random_range(0..moves.len())];
+        board.play_and_advance(row, col)?;
+    }
+    Ok(board)
+}
+
+/// Returns the best of a few profiles drawn at random.
+fn tournament(fitness: &[f64], rng: &mut StdRng) -> usize {
+    (0..TOURNAMENT_SIZE)
+        .map(|_| rng.random_range(0..fitness.len()))
+        .max_by(|&a, &b| fitness[a].total_cmp(&fitness[b]))
+        .unwrap_or(0)
+}
+
+/// Returns the squares standing for their symmetric classes.
+///
+/// Every square of the board is the image of exactly one of them by a board
+/// symmetry, so they hold all the genes of a symmetric matrix.
+fn matrix_genes() -> impl Iterator<Item = (usize, usize)> {
+    (0..4).flat_map(|row| (row..4).map(move |col| (row, col)))
+}
+
+/// Mixes two profiles, taking every gene from either parent.
+fn crossover(first: &EvalProfile, second: &EvalProfile, rng: &mut StdRng) -> EvalProfile {
+    let mut child = first.clone();
+    if let (Some(matrix), Some(other)) = (child.matrix.as_mut(), second.matrix.as_ref()) {
+        for pos in matrix_genes() {
+            if rng.random_bool(0.5) {
+                set_symmetric(&mut matrix.values, pos, other.values[pos.0][pos.1]);
+            }
+        }
+    }
+    for (feature, weights) in &second.weights {
+        if rng.random_bool(0.5) {
+            child.weights.insert(*feature, *weights);
+        }
+    }
+    child
+}
+
+/// Changes some genes of a profile by a random amount.
+///
+/// Matrix values move by up to a quarter of their size (at least 5) and
+/// keep the matrix symmetric. Feature weights are scaled by 0.5 to 1.5 or
+/// shifted by up to 1, and never become negative.
+fn mutate(profile: &mut EvalProfile, rate: f64, rng: &mut StdRng) {
+    let rate = rate.clamp(0.0, 1.0);
+    if let Some(matrix) = profile.matrix.as_mut() {
+        for pos in matrix_genes() {
+            if rng.random_bool(rate) {
+                let value = matrix.values[pos.0][pos.1];
+                let step = (value.abs() / 4).max(5) as i64;
+                let delta = rng.random_range(-step..=step) as isize;
+                set_symmetric(&mut matrix.values, pos, value + delta);
+            }
+        }
+    }
+    for feature in Feature::ALL {
+        let weights = profile.weights.entry(feature).or_default();
+        for weight in [
+            &mut weights.opening,
+            &mut weights.midgame,
+            &mut weights.endgame,
+        ] {
+            if rng.random_bool(rate) {
+                let scaled = *weight * rng.random_range(0.5..1.5) + rng.random_range(-1.0..1.0);
+                *weight = (scaled.max(0.0) * 100.0).round() / 100.0;
+            }
+        }
+    }
+    profile.weights.retain(|_, weights| !weights.is_zero());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{eval_profile::PhaseWeights, heuristic_matrix::is_symmetric};
+    use std::collections::BTreeMap;
+
+    fn small_config() -> TunerConfig {
+        TunerConfig {
+            population: 4,
+            generations: 2,
+            depth: 1,
+            openings: 1,
+            random_plies: 4,
+            elite: 1,
+            mutation_rate: 0.5,
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn test_mutation_and_crossover_keep_matrices_symmetric() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let tuner = Tuner::new(small_config());
+        let mut child = crossover(&tuner.population[0], &tuner.population[1], &mut rng);
+        for _ in 0..20 {
+            mutate(&mut child, 1.0, &mut rng);
+            assert!(is_symmetric(&child.matrix.as_ref().unwrap().values));
+            assert!(child
+                .weights
+                .values()
+                .all(|w| w.opening >= 0.0 && w.midgame >= 0.0 && w.endgame >= 0.0));
+        }
+        assert_eq!(matrix_genes().count(), 10);
+    }
+
+    #[test]
+    fn test_fitness_ranks_the_stronger_profile() {
+        let mut tuner = Tuner::new(TunerConfig {
+            population: 2,
+            ..small_config()
+        });
+        // A profile trying to lose its discs against the built-in one
+        tuner.population[1] = EvalProfile {
+            name: "Giveaway".to_string(),
+            weights: BTreeMap::from([(Feature::Discs, PhaseWeights::new(-1.0, -1.0, -1.0))]),
+            matrix: None,
+        };
+        let openings = [Board::new()];
+        let fitness = tuner.fitness(&openings).unwrap();
+        assert_eq!(fitness.len(), 2);
+        assert!((fitness[0] + fitness[1] - 1.0).abs() < 1e-9);
+        assert!(fitness[0] > fitness[1]);
+    }
+
+    #[test]
+    fn test_resumed_run_matches_uninterrupted_run() {
+        let mut uninterrupted = Tuner::new(small_config());
+        while !uninterrupted.is_done() {
+            uninterrupted.run_generation().unwrap();
+        }
+        assert_eq!(uninterrupted.history.len(), 2);
+        assert!(uninterrupted.best().is_some());
+        assert_eq!(uninterrupted.report_csv().lines().count(), 3);
+
+        let path = std::env::temp_dir().join("othello_test_tuner_checkpoint.json");
+        let path = path.to_str().unwrap();
+        let mut resumed = Tuner::new(small_config());
+        resumed.run_generation().unwrap();
+        resumed.save(path).unwrap();
+        let mut resumed = Tuner::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        resumed.run_generation().unwrap();
+        assert_eq!(resumed, uninterrupted);
+    }
+}
@@ -0,0 +1,155 @@
+//! Bounded worker pool for the parallel searches.
+//!
+//! The search AIs split their work into independent jobs (one per root move,
+//! or one per root move and reply for MinMax double threading). Instead of
+//! one OS thread per job, a `WorkerPool` runs a fixed number of workers that
+//! take the jobs from a shared queue until it is empty, so a position with
+//! many legal moves does not oversubscribe the machine.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Returns the default number of search workers: one per available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1)
+}
+
+/// A fixed number of workers sharing a queue of jobs.
+///
+/// The workers are scoped threads started by `map` and joined before it
+/// returns, so the jobs may borrow data from the caller.
+///
+/// # Examples
+///
+/// ```rust
+/// let pool = WorkerPool::new(4);
+/// let squares = pool.map((1..=20).collect(), |n: u64| n * n).unwrap();
+/// assert_eq!(squares[2], 9);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorkerPool {
+    /// Maximum number of jobs running at the same time
+    threads: usize,
+}
+
+impl WorkerPool {
+    /// Creates a pool of `threads` workers (at least one).
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+        }
+    }
+
+    /// Runs `work` on every job and returns the results in the order of the jobs.
+    ///
+    /// No more than `threads` workers are started, and fewer when there are
+    /// fewer jobs. With a single job or a single worker, the jobs run on the
+    /// calling thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `jobs` - The inputs of the jobs
+    /// * `work` - The function applied to each job, shared by all workers
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<R>)` - One result per job, in the same order
+    /// * `Err(String)` - If a worker panicked
+    pub fn map<T, R, F>(&self, jobs: Vec<T>, work: F) -> Result<Vec<R>, String>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(T) -> R + Sync,
+    {
+        let workers = self.threads.min(jobs.len());
+        if workers <= 1 {
+            return Ok(jobs.into_iter().map(work).collect());
+        }
+
+        let count = jobs.len();
+        let queue: Vec<Mutex<Option<T>>> =
+            jobs.into_iter().map(|job| Mutex::new(Some(job))).collect();
+        let next = AtomicUsize::new(0);
+
+        let results: Vec<Vec<(usize, R)>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = vec![];
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            if index >= count {
+                                break;
+                            }
+                            let job = queue[index].lock().ok().and_then(|mut job| job.take());
+                            if let Some(job) = job {
+                                done.push((index, work(job)));
+                            }
+                        }
+                        done
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().map_err(|_| "Thread join error".to_string()))
+                .collect::<Result<_, _>>()
+        })?;
+
+        let mut ordered: Vec<Option<R>> = (0..count).map(|_| None).collect();
+        for (index, result) in results.into_iter().flatten() {
+            ordered[index] = Some(result);
+        }
+        ordered
+            .into_iter()
+            .map(|result| result.ok_or("A search job was lost".to_string()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_job_order() {
+        let pool = WorkerPool::new(3);
+        let results = pool.map((0..50).collect(), |n: usize| n * 2).unwrap();
+        assert_eq!(results, (0..50).map(|n| n * 2).collect::<Vec<_>>());
+        assert_eq!(WorkerPool::new(0), WorkerPool::new(1));
+    }
+
+    #[test]
+    fn test_map_never_exceeds_worker_count() {
+        let pool = WorkerPool::new(2);
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        pool.map((0..16).collect(), |_: usize| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(2));
+            running.fetch_sub(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn test_worker_panic_is_an_error() {
+        let pool = WorkerPool::new(2);
+        let result = pool.map(vec![1, 2, 3], |n: usize| {
+            if n == 2 {
+                panic!("search failed");
+            }
+            n
+        });
+        assert_eq!(result, Err("Thread join error".to_string()));
+    }
+}
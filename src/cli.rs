@@ -0,0 +1,725 @@
+//! Command line tools for the Othello game.
+//!
+//! Running the binary without arguments starts the terminal interface.
+//! When a command is given, the matching tool runs instead and prints its
+//! results on the standard output:
+//!
+//! ```bash
+//! cargo run --release -- arena --first mcts --second alphabeta --games 10
+//! cargo run --release -- build-book --from search --plies 8 --depth 4
+//! cargo run --release -- write-profile --output my_profile.json
+//! cargo run --release -- train-patterns --from self-play --games 500
+//! cargo run --release -- tune --population 12 --generations 30 --seed 42
+//! cargo run --release -- texel --from positions --positions-file positions.txt
+//! cargo run --release -- train-network --iterations 20 --games 50
+//! cargo run --release -- convert-q-table --input q_table.json --output q_table.bin
+//! cargo run --release -- count-states --plies 9
+//! ```
+//!
+//! Options are always given as `--name value` pairs after the command name.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    ai::{
+        algo::{
+            alphabeta::AIAlphaBeta,
+            mcts::{AIMcts, PlayoutPolicy, SearchBudget},
+            minmax::AIMinMax,
+            qlearning::QTableMetadata,
+        },
+        eval_profile::EvalProfile,
+        heuristic::HeuristicType,
+        heuristic_matrix::AIHeuristicMatrix,
+        network::{network_samples, ValueNetwork},
+        opening_book::{BookMode, OpeningBook},
+        pattern::{game_samples, self_play_game, train},
+        q_table,
+        texel::{game_positions, load_positions, Target, TexelTuner},
+        tuner::{Tuner, TunerConfig},
+    },
+    consts::{
+        BOOK_MAX_PLIES, EVAL_PROFILE_FILE, MATRICES_DIR, MCTS_EXPLORATION, MCTS_ITERATIONS,
+        NETWORK_HIDDEN, NETWORK_WEIGHTS_FILE, OPENING_BOOK_FILE, PATTERN_WEIGHTS_FILE,
+        SAVED_GAMES_FILE, TUNER_CHECKPOINT_FILE,
+    },
+    game::{
+        arena::play_game, board::Board, cell::Cell, player::Player, record,
+        symmetry::count_positions,
+    },
+};
+
+/// Usage text printed by the `help` command and on invalid input.
+const USAGE: &str = "Usage: othello_rust [COMMAND] [--option value]...
+
+Without a command, the terminal interface is started.
+
+Commands:
+  arena   Play a match between two AIs, alternating colors every game
+          --first <alphabeta|minmax|mcts>   (default: mcts)
+          --second <alphabeta|minmax|mcts>  (default: alphabeta)
+          --games <n>                       (default: 2)
+          --depth <n>                       search depth of Alpha-Beta/Min-Max (default: 3)
+          --iterations <n>                  MCTS playouts per move (default: MCTS_ITERATIONS)
+          --time-ms <ms>                    MCTS time budget per move, instead of iterations
+          --exploration <c>                 MCTS UCT constant (default: MCTS_EXPLORATION)
+          --playout <random|heuristic>      MCTS playout policy (default: random)
+          --threads <n>                     search threads of both AIs (default: all cores)
+          --seed <n>                        MCTS seed, incremented for every game
+          --book <off|best|random>          opening book usage (default: best)
+          --first-profile <path>            evaluation profile of the first AI (Weighted heuristic)
+          --second-profile <path>           evaluation profile of the second AI (Weighted heuristic)
+  build-book  Build the opening book used by the search AIs
+          --from <games|search>             saved games or Alpha-Beta searches (default: games)
+          --games-file <path>               saved games to read (default: SAVED_GAMES_FILE)
+          --plies <n>                       plies covered by the book (default: BOOK_MAX_PLIES)
+          --depth <n>                       depth of the Alpha-Beta searches (default: 4)
+          --output <path>                   book file to write (default: OPENING_BOOK_FILE)
+  write-profile  Write the built-in evaluation profile, to edit and load as a Weighted heuristic
+          --output <path>                   profile file to write (default: EVAL_PROFILE_FILE)
+  train-patterns  Fit the weights of the Pattern heuristic to finished games
+          --from <games|self-play>          saved games or new Alpha-Beta games (default: games)
+          --games-file <path>               saved games to read (default: SAVED_GAMES_FILE)
+          --games <n>                       self-play games (default: 200)
+          --random-plies <n>                random opening moves of self-play games (default: 10)
+          --depth <n>                       Alpha-Beta depth of self-play games (default: 2)
+          --epochs <n>                      passes over the positions (default: 10)
+          --learning-rate <x>               gradient descent step (default: 0.005)
+          --seed <n>                        seed of the opening moves and sample order (default: 0)
+          --output <path>                   weights file to write (default: PATTERN_WEIGHTS_FILE)
+  tune    Evolve evaluation profiles (matrix and phase weights) by self-play matches
+          --population <n>                  profiles per generation (default: 10)
+          --generations <n>                 generation to stop after (default: 20)
+          --depth <n>                       Alpha-Beta depth of the matches (default: 2)
+          --openings <n>                    random openings per generation (default: 2)
+          --random-plies <n>                random moves of every opening (default: 6)
+          --elite <n>                       best profiles kept unchanged (default: 2)
+          --mutation-rate <x>               probability of mutating every gene (default: 0.2)
+          --seed <n>                        seed of the run (default: 0)
+          --checkpoint <path>               checkpoint written every generation (default: TUNER_CHECKPOINT_FILE)
+          --resume <true|false>             go on from the checkpoint (default: false)
+          --output <path>                   best profile to write (default: tuned_profile.json)
+          --report <path>                   fitness of every generation as CSV (default: tuning_report.csv)
+  texel   Fit the weights of an evaluation profile to labelled positions (Texel tuning)
+          --from <games|positions>          saved games or a position file (default: games)
+          --games-file <path>               saved games to read (default: SAVED_GAMES_FILE)
+          --positions-file <path>           board hashes with their final Black disc difference
+          --profile <path>                  starting profile (default: EVAL_PROFILE_FILE or built-in)
+          --target <result|discs>           predict the game result or the disc difference (default: result)
+          --passes <n>                      maximum passes over the weights (default: 100)
+          --output <path>                   tuned profile to write (default: texel_profile.json)
+  train-network  Train the value network of the Network heuristic by self-play
+          --iterations <n>                  rounds of self-play then training (default: 10)
+          --games <n>                       self-play games per iteration (default: 20)
+          --buffer <n>                      latest games trained on (default: 100)
+          --random-plies <n>                random opening moves of self-play games (default: 8)
+          --depth <n>                       Alpha-Beta depth of self-play games (default: 1)
+          --hidden <n>                      hidden units of a new network (default: NETWORK_HIDDEN)
+          --epochs <n>                      passes over the buffer per iteration (default: 2)
+          --learning-rate <x>               gradient descent step (default: 0.01)
+          --seed <n>                        seed of the initial weights, opening moves and sample order (default: 0)
+          --resume <true|false>             go on training the network of the output file (default: false)
+          --output <path>                   weights file, written every iteration (default: NETWORK_WEIGHTS_FILE)
+  convert-q-table  Convert a Q-table between JSON and the compact binary format
+          --input <path>                    Q-table to read, in either format
+          --output <path>                   Q-table to write, as JSON if it ends with .json, binary otherwise
+  count-states  Count the positions reachable from the start, with and without symmetry
+          --plies <n>                       plies to explore (default: 7)
+  help    Print this message";
+
+/// Runs the command line tool named by the first argument.
+///
+/// # Arguments
+///
+/// * `args` - The command line arguments, without the program name
+///
+/// # Returns
+///
+/// * `Ok(())` - The command completed successfully
+/// * `Err(String)` - Unknown command, invalid option or failed command
+///
+/// # Examples
+///
+/// ```rust
+/// let args: Vec<String> = std::env::args().skip(1).collect();
+/// if let Err(e) = cli::run(&args) {
+///     eprintln!("{}", e);
+/// }
+/// ```
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("arena") => arena(&parse_options(&args[1..])?),
+        Some("build-book") => build_book(&parse_options(&args[1..])?),
+        Some("write-profile") => write_profile(&parse_options(&args[1..])?),
+        Some("train-patterns") => train_patterns(&parse_options(&args[1..])?),
+        Some("tune") => tune(&parse_options(&args[1..])?),
+        Some("texel") => texel(&parse_options(&args[1..])?),
+        Some("train-network") => train_network(&parse_options(&args[1..])?),
+        Some("convert-q-table") => convert_q_table(&parse_options(&args[1..])?),
+        Some("count-states") => count_states(&parse_options(&args[1..])?),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(format!("Unknown command '{}'\n\n{}", command, USAGE)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+/// Parses `--name value` pairs into a map.
+fn parse_options(args: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut options = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(name) = iter.next() {
+        let name = name
+            .strip_prefix("--")
+            .ok_or_else(|| format!("Expected an option, found '{}'", name))?;
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value for option '--{}'", name))?;
+        options.insert(name.to_string(), value.clone());
+    }
+    Ok(options)
+}
+
+/// Reads an option and parses it, falling back to `default` when absent.
+fn option<T: std::str::FromStr>(
+    options: &HashMap<String, String>,
+    name: &str,
+    default: T,
+) -> Result<T, String> {
+    match options.get(name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("Invalid value '{}' for option '--{}'", value, name)),
+        None => Ok(default),
+    }
+}
+
+/// Builds an AI player from its command line name and the shared options.
+///
+/// # Arguments
+///
+/// * `name` - The AI name (`alphabeta`, `minmax` or `mcts`)
+/// * `color` - The color the AI plays in this game
+/// * `options` - The parsed command line options
+/// * `game` - Index of the game, added to the MCTS seed
+fn build_player(
+    name: &str,
+    color: Cell,
+    options: &HashMap<String, String>,
+    game: u64,
+) -> Result<Box<dyn Player>, String> {
+    let depth = option(options, "depth", 3)?;
+    let book_mode = match options.get("book").map(String::as_str) {
+        None | Some("best") => BookMode::Best,
+        Some("off") => BookMode::Off,
+        Some("random") => BookMode::Random,
+        Some(other) => return Err(format!("Unknown book mode '{}'", other)),
+    };
+    let mut player: Box<dyn Player> = match name {
+        "alphabeta" => Box::new(AIAlphaBeta::new(
+            depth,
+            HeuristicType::Mixte,
+            color,
+            AIHeuristicMatrix::A,
+        )),
+        "minmax" => Box::new(AIMinMax::new(
+            depth,
+            HeuristicType::Mixte,
+            color,
+            AIHeuristicMatrix::A,
+            false,
+        )),
+        "mcts" => Box::new(build_mcts(color, options, game)?),
+        other => return Err(format!("Unknown AI '{}'", other)),
+    };
+    player.set_book_mode(book_mode);
+    if options.contains_key("threads") {
+        player.set_threads(option(options, "threads", 1)?);
+    }
+    Ok(player)
+}
+
+/// Builds the MCTS player described by the command line options.
+///
+/// # Arguments
+///
+/// * `color` - The color the AI plays in this game
+/// * `options` - The parsed command line options
+/// * `game` - Index of the game, added to the seed
+fn build_mcts(color: Cell, options: &HashMap<String, String>, game: u64) -> Result<AIMcts, String> {
+    let budget = match options.get("time-ms") {
+        Some(_) => SearchBudget::Time(Duration::from_millis(option(options, "time-ms", 0)?)),
+        None => SearchBudget::Iterations(option(options, "iterations", MCTS_ITERATIONS)?),
+    };
+    let playout = match options.get("playout").map(String::as_str) {
+        None | Some("random") => PlayoutPolicy::Random,
+        Some("heuristic") => PlayoutPolicy::Heuristic,
+        Some(other) => return Err(format!("Unknown playout policy '{}'", other)),
+    };
+    let seed = match options.get("seed") {
+        Some(_) => Some(option::<u64>(options, "seed", 0)?.wrapping_add(game)),
+        None => None,
+    };
+    let ai = AIMcts::new(budget, HeuristicType::Matrix, color, AIHeuristicMatrix::A)
+        .with_exploration(option(options, "exploration", MCTS_EXPLORATION)?)
+        .with_playout(playout)
+        .with_seed(seed);
+    Ok(ai)
+}
+
+/// Plays a match between two AIs and prints the score of every game.
+///
+/// The first AI plays Black in even games and White in odd games, so both
+/// AIs get the first move equally often.
+fn arena(options: &HashMap<String, String>) -> Result<(), String> {
+    let first = options.get("first").map(String::as_str).unwrap_or("mcts");
+    let second = options
+        .get("second")
+        .map(String::as_str)
+        .unwrap_or("alphabeta");
+    let games: u64 = option(options, "games", 2)?;
+    if first == "mcts" || second == "mcts" {
+        let mcts = build_mcts(Cell::Black, options, 0)?;
+        println!(
+            "MCTS: budget {:?}, exploration {:.3}, playouts {}",
+            mcts.get_search_budget().unwrap(),
+            mcts.get_exploration().unwrap(),
+            mcts.get_playout_policy().unwrap()
+        );
+    }
+
+    let [first_profile, second_profile] = ["first-profile", "second-profile"].map(|name| {
+        options
+            .get(name)
+            .map(|path| {
+                EvalProfile::load(path)
+                    .map(Arc::new)
+                    .map_err(|e| format!("Could not load '{}': {}", path, e))
+            })
+            .transpose()
+    });
+    let (first_profile, second_profile) = (first_profile?, second_profile?);
+    for (name, profile) in [(first, &first_profile), (second, &second_profile)] {
+        if let Some(profile) = profile {
+            println!("{}: evaluation profile '{}'", name, profile.name);
+        }
+    }
+
+    // Wins for the first AI, wins for the second AI, draws
+    let mut results = [0; 3];
+    for game in 0..games {
+        let ((black_name, black_profile), (white_name, white_profile)) = if game % 2 == 0 {
+            ((first, &first_profile), (second, &second_profile))
+        } else {
+            ((second, &second_profile), (first, &first_profile))
+        };
+        let mut black = build_player(black_name, Cell::Black, options, game)?;
+        let mut white = build_player(white_name, Cell::White, options, game)?;
+        for (player, profile) in [(&mut black, black_profile), (&mut white, white_profile)] {
+            if let Some(profile) = profile {
+                player.set_heuristic(HeuristicType::Weighted(profile.clone()));
+            }
+        }
+        let board = play_game(black.as_ref(), white.as_ref(), Board::new())?;
+
+        let black_discs = board.get_nb_discs(Cell::Black)?;
+        let white_discs = board.get_nb_discs(Cell::White)?;
+        println!(
+            "Game {}: {} (BLACK) {} - {} {} (WHITE)",
+            game + 1,
+            black_name,
+            black_discs,
+            white_discs,
+            white_name
+        );
+        match board.get_winner() {
+            Some(Cell::Black) if game % 2 == 0 => results[0] += 1,
+            Some(Cell::White) if game % 2 == 1 => results[0] += 1,
+            Some(_) => results[1] += 1,
+            None => results[2] += 1,
+        }
+    }
+
+    println!(
+        "{}: {} wins / {}: {} wins / {} draws",
+        first, results[0], second, results[1], results[2]
+    );
+    Ok(())
+}
+
+/// Builds an opening book and writes it to a JSON file.
+///
+/// With `--from games`, the first plies of every saved game are added to the
+/// book, winning moves weighing more. With `--from search`, the best two
+/// moves of every position are found with Alpha-Beta searches, following
+/// both lines until the requested number of plies.
+fn build_book(options: &HashMap<String, String>) -> Result<(), String> {
+    let plies = option(options, "plies", BOOK_MAX_PLIES)?;
+    let output = options
+        .get("output")
+        .map(String::as_str)
+        .unwrap_or(OPENING_BOOK_FILE);
+
+    let mut book = OpeningBook::new();
+    match options.get("from").map(String::as_str) {
+        None | Some("games") => {
+            let games_file = options
+                .get("games-file")
+                .map(String::as_str)
+                .unwrap_or(SAVED_GAMES_FILE);
+            let games = record::load_games(games_file)?;
+            for (i, moves) in games.iter().enumerate() {
+                book.add_game(moves, plies)
+                    .map_err(|e| format!("Game {} of {}: {}", i + 1, games_file, e))?;
+            }
+            println!("Read {} games from {}", games.len(), games_file);
+        }
+        Some("search") => {
+            let depth = option(options, "depth", 4)?;
+            book.add_search_lines(plies, depth, HeuristicType::Mixte);
+        }
+        Some(other) => return Err(format!("Unknown book source '{}'", other)),
+    }
+
+    if book.is_empty() {
+        return Err("The opening book is empty, nothing was written".to_string());
+    }
+    book.save(output)?;
+    println!("Wrote {} positions to {}", book.len(), output);
+    Ok(())
+}
+
+/// Writes the built-in evaluation profile to a JSON file.
+///
+/// The file is a starting point for tuning: the `Weighted` heuristic reads
+/// `EVAL_PROFILE_FILE`, and the arena loads the files given as profiles.
+fn write_profile(options: &HashMap<String, String>) -> Result<(), String> {
+    let output = options
+        .get("output")
+        .map(String::as_str)
+        .unwrap_or(EVAL_PROFILE_FILE);
+    EvalProfile::default().save(output)?;
+    println!("Wrote the default evaluation profile to {}", output);
+    Ok(())
+}
+
+/// Fits the pattern weights to finished games and writes them to a file.
+///
+/// With `--from games`, the positions of the saved games are used; games
+/// that did not reach the end are skipped. With `--from self-play`, new
+/// games are played by Alpha-Beta after a few random opening moves. Every
+/// position is labelled with the final disc difference of its game.
+fn train_patterns(options: &HashMap<String, String>) -> Result<(), String> {
+    let output = options
+        .get("output")
+        .map(String::as_str)
+        .unwrap_or(PATTERN_WEIGHTS_FILE);
+    let seed: u64 = option(options, "seed", 0)?;
+
+    let mut samples = Vec::new();
+    match options.get("from").map(String::as_str) {
+        None | Some("games") => {
+            let games_file = options
+                .get("games-file")
+                .map(String::as_str)
+                .unwrap_or(SAVED_GAMES_FILE);
+            let games = record::load_games(games_file)?;
+            let mut finished = 0;
+            for (i, moves) in games.iter().enumerate() {
+                let positions = record::replay(moves)
+                    .map_err(|e| format!("Game {} of {}: {}", i + 1, games_file, e))?;
+                let game = game_samples(&positions);
+                if !game.is_empty() {
+                    finished += 1;
+                }
+                samples.extend(game);
+            }
+            println!(
+                "Read {} finished games out of {} from {}",
+                finished,
+                games.len(),
+                games_file
+            );
+        }
+        Some("self-play") => {
+            let games: u64 = option(options, "games", 200)?;
+            let random_plies = option(options, "random-plies", 10)?;
+            let depth = option(options, "depth", 2)?;
+            for game in 0..games {
+                samples.extend(game_samples(&self_play_game(
+                    &HeuristicType::Mixte,
+                    random_plies,
+                    depth,
+                    seed.wrapping_add(game),
+                )?));
+                if (game + 1) % 10 == 0 || game + 1 == games {
+                    println!("Played {}/{} games", game + 1, games);
+                }
+            }
+        }
+        Some(other) => return Err(format!("Unknown training source '{}'", other)),
+    }
+    if samples.is_empty() {
+        return Err("No finished game to learn from, nothing was written".to_string());
+    }
+
+    println!("Training on {} positions", samples.len());
+    let weights = train(
+        &samples,
+        option(options, "epochs", 10)?,
+        option(options, "learning-rate", 0.005)?,
+        seed,
+        |epoch, error| println!("Epoch {}: RMS error {:.2} discs", epoch, error),
+    );
+    weights.save(output)?;
+    println!("Wrote pattern weights to {}", output);
+    Ok(())
+}
+
+/// Runs the genetic tuner and writes the best profile and the fitness report.
+///
+/// The best profile carries its tuned matrix, so it can be loaded with
+/// `--first-profile` in the arena or as `EVAL_PROFILE_FILE`. The matrix is
+/// also written to `MATRICES_DIR`, to be picked in the setup screens.
+fn tune(options: &HashMap<String, String>) -> Result<(), String> {
+    let checkpoint = options
+        .get("checkpoint")
+        .map(String::as_str)
+        .unwrap_or(TUNER_CHECKPOINT_FILE);
+    let output = options
+        .get("output")
+        .map(String::as_str)
+        .unwrap_or("tuned_profile.json");
+    let report = options
+        .get("report")
+        .map(String::as_str)
+        .unwrap_or("tuning_report.csv");
+
+    let mut tuner = if option(options, "resume", false)? {
+        let mut tuner = Tuner::load(checkpoint)
+            .map_err(|e| format!("Could not resume from '{}': {}", checkpoint, e))?;
+        tuner.config.generations = option(options, "generations", tuner.config.generations)?;
+        println!("Resumed from {}", checkpoint);
+        tuner
+    } else {
+        let defaults = TunerConfig::default();
+        let config = TunerConfig {
+            population: option(options, "population", defaults.population)?,
+            generations: option(options, "generations", defaults.generations)?,
+            depth: option(options, "depth", defaults.depth)?,
+            openings: option(options, "openings", defaults.openings)?,
+            random_plies: option(options, "random-plies", defaults.random_plies)?,
+            elite: option(options, "elite", defaults.elite)?,
+            mutation_rate: option(options, "mutation-rate", defaults.mutation_rate)?,
+            seed: option(options, "seed", defaults.seed)?,
+        };
+        if config.population < 2 {
+            return Err("The population needs at least 2 profiles".to_string());
+        }
+        Tuner::new(config)
+    };
+
+    while !tuner.is_done() {
+        let generation = tuner.run_generation()?;
+        println!(
+            "Generation {}: best {:.3} / mean {:.3} / worst {:.3}",
+            generation.generation, generation.best, generation.mean, generation.worst
+        );
+        tuner.save(checkpoint)?;
+    }
+
+    let best = tuner
+        .best()
+        .ok_or("No generation was evaluated, nothing was written")?;
+    best.save(output)?;
+    println!("Wrote the best profile to {}", output);
+    if let Some(matrix) = &best.matrix {
+        println!("Wrote its matrix to {}", matrix.save(MATRICES_DIR)?);
+    }
+    std::fs::write(report, tuner.report_csv()).map_err(|e| e.to_string())?;
+    println!("Wrote the fitness report to {}", report);
+    Ok(())
+}
+
+/// Tunes the weights of an evaluation profile on labelled positions.
+///
+/// With `--from games`, the positions of the finished saved games are
+/// labelled with their final disc difference. With `--from positions`, they
+/// are read from a position file (see `ai::texel`).
+fn texel(options: &HashMap<String, String>) -> Result<(), String> {
+    let output = options
+        .get("output")
+        .map(String::as_str)
+        .unwrap_or("texel_profile.json");
+    let target = match options.get("target").map(String::as_str) {
+        None | Some("result") => Target::Result,
+        Some("discs") => Target::Discs,
+        Some(other) => return Err(format!("Unknown target '{}'", other)),
+    };
+    let profile = match options.get("profile") {
+        Some(path) => {
+            EvalProfile::load(path).map_err(|e| format!("Could not load '{}': {}", path, e))?
+        }
+        None => EvalProfile::load(EVAL_PROFILE_FILE).unwrap_or_default(),
+    };
+
+    let positions = match options.get("from").map(String::as_str) {
+        None | Some("games") => {
+            let games_file = options
+                .get("games-file")
+                .map(String::as_str)
+                .unwrap_or(SAVED_GAMES_FILE);
+            let mut positions = Vec::new();
+            for (i, moves) in record::load_games(games_file)?.iter().enumerate() {
+                let game = record::replay(moves)
+                    .map_err(|e| format!("Game {} of {}: {}", i + 1, games_file, e))?;
+                positions.extend(game_positions(&game));
+            }
+            positions
+        }
+        Some("positions") => {
+            let file = options
+                .get("positions-file")
+                .ok_or("The option '--positions-file' is required with '--from positions'")?;
+            load_positions(file)?
+        }
+        Some(other) => return Err(format!("Unknown position source '{}'", other)),
+    };
+    if positions.is_empty() {
+        return Err("No labelled position to learn from, nothing was written".to_string());
+    }
+
+    let mut tuner = TexelTuner::new(profile, &positions, target);
+    println!(
+        "Tuning '{}' on {} positions (k = {:.6}, error {:.6})",
+        tuner.profile().name,
+        positions.len(),
+        tuner.k(),
+        tuner.error()
+    );
+    let mut profile = tuner.tune(option(options, "passes", 100)?, |pass, error| {
+        println!("Pass {}: error {:.6}", pass, error)
+    });
+    profile.name = format!("{} (Texel)", profile.name);
+    profile.save(output)?;
+    println!("Wrote the tuned profile to {}", output);
+    Ok(())
+}
+
+/// Trains the value network by self-play and writes its weights.
+///
+/// Every iteration plays new games with Alpha-Beta searches using the
+/// current network, then trains the network on the latest games. The weights
+/// are written after every iteration, so training can be stopped at any time
+/// and resumed later.
+fn train_network(options: &HashMap<String, String>) -> Result<(), String> {
+    let output = options
+        .get("output")
+        .map(String::as_str)
+        .unwrap_or(NETWORK_WEIGHTS_FILE);
+    let iterations: u64 = option(options, "iterations", 10)?;
+    let games: u64 = option(options, "games", 20)?;
+    let buffer_size = option(options, "buffer", 100)?;
+    let random_plies = option(options, "random-plies", 8)?;
+    let depth = option(options, "depth", 1)?;
+    let epochs = option(options, "epochs", 2)?;
+    let learning_rate = option(options, "learning-rate", 0.01)?;
+    let seed: u64 = option(options, "seed", 0)?;
+
+    let mut network = if option(options, "resume", false)? {
+        ValueNetwork::load(output)
+            .map_err(|e| format!("Could not resume from '{}': {}", output, e))?
+    } else {
+        ValueNetwork::new(option(options, "hidden", NETWORK_HIDDEN)?, seed)
+    };
+    println!("Training a network of {} hidden units", network.hidden());
+
+    let mut buffer = VecDeque::new();
+    for iteration in 1..=iterations {
+        let heuristic = HeuristicType::Network(Arc::new(network.clone()));
+        for game in 0..games {
+            buffer.push_back(network_samples(&self_play_game(
+                &heuristic,
+                random_plies,
+                depth,
+                seed.wrapping_add((iteration - 1) * games + game),
+            )?));
+            if buffer.len() > buffer_size {
+                buffer.pop_front();
+            }
+        }
+        let samples: Vec<_> = buffer.iter().flatten().cloned().collect();
+        println!(
+            "Iteration {}: training on {} positions of {} games",
+            iteration,
+            samples.len(),
+            buffer.len()
+        );
+        network.train(
+            &samples,
+            epochs,
+            learning_rate,
+            seed.wrapping_add(iteration),
+            |epoch, error| println!("  Epoch {}: RMS error {:.4}", epoch, error),
+        );
+        network.save(output)?;
+    }
+    println!("Wrote the network weights to {}", output);
+    Ok(())
+}
+
+/// Converts a Q-table between the JSON and the binary formats.
+///
+/// The output format is given by its extension (`.json` for JSON, binary
+/// otherwise). The training metadata of the input is copied next to the
+/// output, so the converted table keeps its colour and hyperparameters.
+fn convert_q_table(options: &HashMap<String, String>) -> Result<(), String> {
+    let input = options
+        .get("input")
+        .ok_or("The option '--input' is required")?;
+    let output = options
+        .get("output")
+        .ok_or("The option '--output' is required")?;
+    let states = q_table::convert_q_table(input, output)
+        .map_err(|e| format!("Could not convert '{}': {}", input, e))?;
+
+    let metadata = QTableMetadata::path_for(input);
+    let output_metadata = QTableMetadata::path_for(output);
+    if metadata != output_metadata && Path::new(&metadata).exists() {
+        std::fs::copy(&metadata, &output_metadata).map_err(|e| e.to_string())?;
+    }
+    println!("Wrote {} states to {}", states, output);
+    Ok(())
+}
+
+/// Prints the number of positions and canonical positions after every ply.
+///
+/// These are the states a Q-table could hold at most in the opening, keyed
+/// on the position or on its symmetry class.
+fn count_states(options: &HashMap<String, String>) -> Result<(), String> {
+    let plies = option(options, "plies", 7)?;
+    let (mut raw_total, mut canonical_total) = (0, 0);
+    println!(
+        "{:>4} {:>12} {:>16}",
+        "Ply", "Raw states", "Canonical states"
+    );
+    for (ply, (raw, canonical)) in count_positions(plies).into_iter().enumerate() {
+        raw_total += raw;
+        canonical_total += canonical;
+        println!("{:>4} {:>12} {:>16}", ply + 1, raw, canonical);
+    }
+    println!(
+        "Total {:>11} {:>16} ({:.2}x fewer)",
+        raw_total,
+        canonical_total,
+        raw_total as f64 / canonical_total.max(1) as f64
+    );
+    Ok(())
+}
@@ -11,7 +11,7 @@
 /// This constant defines the dimensions of the standard Othello board.
 /// The game is played on an 8x8 grid, which is the official board size
 /// for Othello/Reversi. This value is used throughout the codebase for:
-/// 
+///
 /// - Array dimensioning for the game board
 /// - Boundary checking for move validation
 /// - Loop iterations for board traversal
@@ -53,6 +53,13 @@ pub const SIZE: usize = 8;
 /// - Any tree-based search algorithms
 pub const MAX_DEPTH: usize = 5; // Maximum depth for the AI search algorithms
 
+/// Upper bound for the number of search workers on the setup screens.
+///
+/// The search AIs default to one worker per available core. The setup
+/// screens let the user choose between one worker and this bound, to keep
+/// a search from taking over a shared machine.
+pub const MAX_SEARCH_THREADS: usize = 32;
+
 // Qlearning
 
 /// Learning rate parameter for Q-Learning algorithm.
@@ -99,6 +106,16 @@ pub const LAMBDA_LEARN: f64 = 0.8; // Learning rate for Q-learning
 /// - Suitable for complex games like Othello where position matters
 pub const GAMMA: f64 = 0.99; // Discount factor for future rewards
 
+/// Decay of the eligibility traces of the TD(λ) update rule.
+///
+/// After each move, the traces of the earlier moves are multiplied by
+/// `GAMMA * TRACE_DECAY`: 0 gives back one-step Q-learning, values close to
+/// 1 credit the whole game for each reward.
+pub const TRACE_DECAY: f64 = 0.8;
+
+/// Eligibility traces below this value are dropped by the TD(λ) update rule.
+pub const TRACE_THRESHOLD: f64 = 0.01;
+
 /// Initial exploration rate for epsilon-greedy strategy in Q-Learning.
 ///
 /// This constant sets the starting probability for random exploration
@@ -121,6 +138,25 @@ pub const GAMMA: f64 = 0.99; // Discount factor for future rewards
 /// - Ensures comprehensive exploration of the strategy space
 pub const EPSILON: f64 = 1.0; // INITIAL Exploration rate for epsilon-greedy strategy
 
+/// Default lowest exploration rate reached by the epsilon schedules.
+///
+/// Keeping a little exploration until the end of training lets the AI keep
+/// discovering states it has never visited instead of only replaying its
+/// current best lines.
+pub const EPSILON_FLOOR: f64 = 0.01;
+
+/// Default factor applied to epsilon after each epoch by the exponential
+/// schedule.
+///
+/// With EPSILON_DECAY = 0.999, epsilon is halved roughly every 700 epochs.
+pub const EPSILON_DECAY: f64 = 0.999;
+
+/// Number of plateaus of the step epsilon schedule.
+///
+/// Training is split into this many equal parts, and epsilon drops by the
+/// same amount at the start of each of them until it reaches its floor.
+pub const EPSILON_STEPS: usize = 10;
+
 /// Maximum number of training epochs for Q-Learning sessions.
 ///
 /// This constant defines the upper limit for Q-Learning training iterations.
@@ -143,6 +179,190 @@ pub const EPSILON: f64 = 1.0; // INITIAL Exploration rate for epsilon-greedy str
 /// - **Convergence**: Most learning typically occurs in first few thousand epochs
 pub const QLEARNING_MAX_EPOCHS: usize = 10000;
 
+/// Default search depth of the Alpha-Beta and Min-Max training opponents.
+///
+/// A shallow search keeps training games fast while still punishing the
+/// obvious blunders a random mover lets through.
+pub const QLEARNING_OPPONENT_DEPTH: usize = 2;
+
+/// Number of epochs between two refreshes of the snapshot training opponent.
+///
+/// The snapshot opponent plays greedily with a frozen copy of the Q-table,
+/// so the AI always trains against a slightly older version of itself.
+pub const QLEARNING_SNAPSHOT_EPOCHS: usize = 1000;
+
+/// Number of epochs between two checkpoints of a Q-learning training.
+///
+/// The table is saved with its epoch counter and epsilon at each checkpoint,
+/// so a crashed or killed training can be continued from the last one.
+pub const QLEARNING_CHECKPOINT_EPOCHS: usize = 500;
+
+/// Number of last training games the Q-learning metrics are measured over.
+///
+/// The average reward, win rate and speed shown while training, and written
+/// to the `.metrics.csv` log, follow these games only.
+pub const QLEARNING_METRICS_WINDOW: usize = 100;
+
+/// Number of epochs between two evaluations of a Q-learning training.
+///
+/// An evaluation plays greedy games against a random mover and Alpha-Beta at
+/// depths 1 to 3, showing whether the agent improves rather than just grows
+/// its table.
+pub const QLEARNING_EVALUATION_EPOCHS: usize = 500;
+
+/// Number of evaluation games against each opponent, half with each colour.
+pub const QLEARNING_EVALUATION_GAMES: usize = 10;
+
+/// Directory of the trained Q-tables.
+///
+/// Training writes the chosen table there with its `.params.json` metadata,
+/// and the setup screens list its `.json` files in their Q-table pickers.
+pub const Q_TABLES_DIR: &str = "q_tables";
+
+// Monte Carlo Tree Search
+
+/// Default number of playouts for the Monte Carlo Tree Search AI.
+///
+/// This constant sets how many select/expand/simulate/backpropagate
+/// iterations the MCTS player runs per move when it uses an iteration
+/// budget. The budget is shared between all search threads.
+///
+/// # Strength vs Speed
+///
+/// - **Fewer iterations**: Quick moves, mostly driven by random playouts
+/// - **More iterations**: Sharper statistics at the root, slower moves
+pub const MCTS_ITERATIONS: usize = 5000;
+
+/// Step used when adjusting the MCTS iteration budget from the setup screens.
+pub const MCTS_ITERATIONS_STEP: usize = 1000;
+
+/// Upper bound for the MCTS iteration budget on the setup screens.
+pub const MCTS_MAX_ITERATIONS: usize = 100000;
+
+/// Time budget per move, in milliseconds, when the setup screens switch the
+/// MCTS player to a time budget.
+pub const MCTS_TIME_MS: u64 = 1000;
+
+/// Step used when adjusting the MCTS time budget from the setup screens.
+pub const MCTS_TIME_STEP_MS: u64 = 250;
+
+/// Upper bound for the MCTS time budget on the setup screens, in milliseconds.
+pub const MCTS_MAX_TIME_MS: u64 = 30000;
+
+/// Default exploration constant for the UCT selection formula.
+///
+/// The UCT score of a child is `wins / visits + C * sqrt(ln(N) / visits)`.
+/// √2 is the textbook value for rewards in the `[0, 1]` range: lower values
+/// exploit the best known moves, higher values spread the playouts more.
+pub const MCTS_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// Step used when adjusting the MCTS exploration constant from the setup screens.
+pub const MCTS_EXPLORATION_STEP: f64 = 0.1;
+
+/// Upper bound for the MCTS exploration constant on the setup screens.
+pub const MCTS_MAX_EXPLORATION: f64 = 5.0;
+
+/// Probability of following the heuristic during guided playouts.
+///
+/// Heuristic-guided playouts pick the move with the best one-ply heuristic
+/// score with this probability, and a random legal move otherwise, so the
+/// simulations keep some diversity.
+pub const MCTS_PLAYOUT_GREEDINESS: f64 = 0.8;
+
+// Opening book
+
+/// Default file of the opening book used by the search AIs.
+///
+/// The book is loaded once, the first time an AI looks up a position. When
+/// the file is missing the AIs simply search every move.
+pub const OPENING_BOOK_FILE: &str = "opening_book.json";
+
+/// File where finished games are saved from the game screen.
+///
+/// Each line holds one game as a JSON record with the search statistics of
+/// the AI moves. The `build-book` command reads this file by default.
+pub const SAVED_GAMES_FILE: &str = "saved_games.txt";
+
+/// Number of plies recorded in the opening book when it is built.
+///
+/// Positions deeper than this are left to the search algorithms.
+pub const BOOK_MAX_PLIES: usize = 12;
+
+/// Share of the best weight a book move needs to be picked at random.
+///
+/// In random book mode, moves weighing less than this fraction of the best
+/// move of the position are ignored, so only good book moves are played.
+pub const BOOK_RANDOM_THRESHOLD: f64 = 0.5;
+
+// Analysis
+
+/// Search depth of the analysis overlay when the player to move cannot analyze.
+///
+/// The game screen asks the player to move for the scores of its candidate
+/// moves. Humans and Q-Learning players cannot, so an Alpha-Beta search with
+/// this depth scores the moves instead.
+pub const ANALYSIS_DEPTH: usize = 4;
+
+// Interface
+
+/// Frames of the spinner shown on the game screen while an AI is thinking.
+///
+/// The screen is redrawn about every 100 ms during a background search and
+/// shows the next frame each time.
+pub const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+
+// Evaluation
+
+/// Weight of one stable disc in the `Global` heuristic and the opening of `Mixte`.
+///
+/// Matrix scores run in the hundreds, so a stable disc is worth about as
+/// much as a good edge square.
+pub const STABILITY_WEIGHT: isize = 10;
+
+/// File of the evaluation profile used by the `Weighted` heuristic.
+///
+/// The profile is loaded once, the first time the heuristic is selected.
+/// When the file is missing the built-in profile is used.
+pub const EVAL_PROFILE_FILE: &str = "eval_profile.json";
+
+/// Directory of the evaluation profiles picked on the setup screens.
+///
+/// Every `.json` file in it holds one profile, listed after the profile of
+/// `EVAL_PROFILE_FILE` in the profile pickers of the `Weighted` heuristic.
+pub const PROFILES_DIR: &str = "profiles";
+
+/// Directory of the user-defined heuristic matrices.
+///
+/// Every `.json` file in it holds one named 8x8 symmetric matrix. The
+/// matrices are loaded once and listed after A and B in the matrix cyclers.
+pub const MATRICES_DIR: &str = "matrices";
+
+/// File of the learned weights used by the `Pattern` heuristic.
+///
+/// Written by the `train-patterns` command and loaded once, the first time
+/// the heuristic is selected. Without the file every weight is zero.
+pub const PATTERN_WEIGHTS_FILE: &str = "pattern_weights.bin";
+
+/// Number of game stages with their own pattern weights.
+///
+/// The stages split the game by disc count, since the value of a pattern
+/// configuration changes as the board fills up.
+pub const PATTERN_STAGES: usize = 4;
+
+/// File of the weights used by the `Network` heuristic.
+///
+/// Written by the `train-network` command and loaded once, the first time
+/// the heuristic is selected. Without the file an untrained network is used.
+pub const NETWORK_WEIGHTS_FILE: &str = "network_weights.bin";
+
+/// Hidden units of the value network, when none is loaded.
+pub const NETWORK_HIDDEN: usize = 32;
+
+/// Checkpoint file of the genetic tuner, rewritten after every generation.
+///
+/// The `tune` command resumes the run it holds when given `--resume`.
+pub const TUNER_CHECKPOINT_FILE: &str = "tuner_checkpoint.json";
+
 #[cfg(test)]
 mod tests {
 
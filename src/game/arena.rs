@@ -0,0 +1,52 @@
+//! Headless game runner for matches between players.
+//!
+//! This module plays complete Othello games between two `Player`
+//! implementations without any user interface. It follows the same turn
+//! sequence as the GUI game loop and is used by the command line tools to
+//! compare AI players against each other.
+
+use crate::game::{board::Board, cell::Cell, player::Player};
+
+/// Plays a complete game between two players from the given position.
+///
+/// The turn sequence mirrors `App::gui_play_turn`: the player to move plays,
+/// the action is added to the history, then the game is either over or the
+/// turn is handed over with `Board::next_turn` (which records passes).
+///
+/// # Arguments
+///
+/// * `black` - The player controlling the black discs
+/// * `white` - The player controlling the white discs
+/// * `board` - The starting position (usually `Board::new()`)
+///
+/// # Returns
+///
+/// * `Ok(Board)` - The final board, with the full move history
+/// * `Err(String)` - The error returned by a player that failed to move
+///
+/// # Examples
+///
+/// ```rust
+/// let black = AIAlphaBeta::new(2, HeuristicType::Absolute, Cell::Black, AIHeuristicMatrix::A);
+/// let white = AIAlphaBeta::new(2, HeuristicType::Matrix, Cell::White, AIHeuristicMatrix::A);
+/// let board = play_game(&black, &white, Board::new()).unwrap();
+/// println!("Winner: {:?}", board.get_winner());
+/// ```
+pub fn play_game(
+    black: &dyn Player,
+    white: &dyn Player,
+    mut board: Board,
+) -> Result<Board, String> {
+    while !board.check_game_over() {
+        let player = match board.get_player_turn() {
+            Cell::Black => black,
+            _ => white,
+        };
+        let action = player.play_turn(&mut board, None)?;
+        board.add_to_history(action);
+        if !board.check_game_over() {
+            board.next_turn();
+        }
+    }
+    Ok(board)
+}
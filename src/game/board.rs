@@ -127,6 +127,8 @@ impl Board {
     ///     color: Cell::Black,
     ///     move_number: 1,
     ///     player_turn: Cell::Black,
+    ///     from_book: false,
+    ///     search_info: None,
     /// };
     /// board.add_to_history(action);
     /// assert_eq!(board.get_history().len(), 1);
@@ -219,6 +221,8 @@ impl Board {
                 color: self.player_turn.get_opponent(),
                 move_number: self.turn_number,
                 player_turn: self.player_turn,
+                from_book: false,
+                search_info: None,
             });
             self.turn_number += 1;
         } else {
@@ -351,6 +355,216 @@ impl Board {
         }
     }
 
+    /// Computes which discs are stable, i.e. can never be flipped again.
+    ///
+    /// A disc can only be flipped along one of the four lines through it
+    /// (row, column and both diagonals). On each line, it is safe when the
+    /// line is full, or when one of its two neighbours on that line is off
+    /// the board or a stable disc of the same color. A disc safe on all four
+    /// lines is stable. Corners are therefore always stable, and stability
+    /// then spreads from them along filled edges and lines until nothing
+    /// changes.
+    ///
+    /// The result is a lower bound: a few stable discs in unusual shapes are
+    /// not detected, but every disc reported stable really is.
+    ///
+    /// # Returns
+    ///
+    /// A grid with `true` on every stable disc.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut board = Board::new();
+    /// board.set_cell(0, 0, Cell::Black);
+    /// assert!(board.get_stable_discs()[0][0]); // A corner is always stable
+    /// assert!(!board.get_stable_discs()[3][3]);
+    /// ```
+    pub fn get_stable_discs(&self) -> [[bool; SIZE]; SIZE] {
+        const AXES: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+        let neighbour = |row: usize, col: usize, (dr, dc): (isize, isize)| {
+            let (row, col) = (row as isize + dr, col as isize + dc);
+            if (0..SIZE as isize).contains(&row) && (0..SIZE as isize).contains(&col) {
+                Some((row as usize, col as usize))
+            } else {
+                None
+            }
+        };
+        let line_is_full = |row: usize, col: usize, (dr, dc): (isize, isize)| {
+            [(dr, dc), (-dr, -dc)].into_iter().all(|direction| {
+                let mut current = (row, col);
+                while let Some(next) = neighbour(current.0, current.1, direction) {
+                    if self.cells[next.0][next.1] == Cell::Empty {
+                        return false;
+                    }
+                    current = next;
+                }
+                true
+            })
+        };
+
+        let mut stable = [[false; SIZE]; SIZE];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for row in 0..SIZE {
+                for col in 0..SIZE {
+                    let color = self.cells[row][col];
+                    if color == Cell::Empty || stable[row][col] {
+                        continue;
+                    }
+                    let anchored = |direction: (isize, isize)| match neighbour(row, col, direction)
+                    {
+                        None => true,
+                        Some((r, c)) => stable[r][c] && self.cells[r][c] == color,
+                    };
+                    if AXES.iter().all(|&(dr, dc)| {
+                        anchored((dr, dc))
+                            || anchored((-dr, -dc))
+                            || line_is_full(row, col, (dr, dc))
+                    }) {
+                        stable[row][col] = true;
+                        changed = true;
+                    }
+                }
+            }
+        }
+        stable
+    }
+
+    /// Returns the number of stable discs of a player.
+    ///
+    /// See `get_stable_discs` for the discs counted as stable.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player color (Black or White)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let board = Board::new();
+    /// assert_eq!(board.get_nb_stable_discs(Cell::Black), 0); // Nothing is stable at the start
+    /// ```
+    pub fn get_nb_stable_discs(&self, color: Cell) -> usize {
+        let stable = self.get_stable_discs();
+        (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+            .filter(|&(row, col)| stable[row][col] && self.cells[row][col] == color)
+            .count()
+    }
+
+    /// Returns the cells around a position, in the eight directions.
+    fn neighbours(row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> {
+        (-1..=1isize)
+            .flat_map(|dr| (-1..=1isize).map(move |dc| (dr, dc)))
+            .filter(|&direction| direction != (0, 0))
+            .filter_map(move |(dr, dc)| {
+                let (row, col) = (row as isize + dr, col as isize + dc);
+                if (0..SIZE as isize).contains(&row) && (0..SIZE as isize).contains(&col) {
+                    Some((row as usize, col as usize))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Returns the number of frontier discs of a player.
+    ///
+    /// A frontier disc touches at least one empty cell. Frontier discs give
+    /// the opponent moves, so a player usually wants as few as possible.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player color (Black or White)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let board = Board::new();
+    /// assert_eq!(board.get_nb_frontier_discs(Cell::Black), 2); // Every disc touches an empty cell
+    /// ```
+    pub fn get_nb_frontier_discs(&self, color: Cell) -> usize {
+        (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                self.cells[row][col] == color
+                    && Self::neighbours(row, col).any(|(r, c)| self.cells[r][c] == Cell::Empty)
+            })
+            .count()
+    }
+
+    /// Returns the potential mobility of a player.
+    ///
+    /// The potential mobility counts the empty cells next to at least one
+    /// opponent disc: the cells where the player may get moves later, even if
+    /// they are not legal yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The player color (Black or White)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let board = Board::new();
+    /// assert_eq!(board.get_potential_mobility(Cell::Black), 10);
+    /// ```
+    pub fn get_potential_mobility(&self, color: Cell) -> usize {
+        let opponent = color.get_opponent();
+        (0..SIZE)
+            .flat_map(|row| (0..SIZE).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                self.cells[row][col] == Cell::Empty
+                    && Self::neighbours(row, col).any(|(r, c)| self.cells[r][c] == opponent)
+            })
+            .count()
+    }
+
+    /// Splits the empty cells into regions.
+    ///
+    /// Two empty cells belong to the same region when they touch, in any of
+    /// the eight directions. In the endgame, the parity of each region tells
+    /// which player should get the last move inside it.
+    ///
+    /// # Returns
+    ///
+    /// The cells of each region, regions in row-major order of their first cell.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let board = Board::new();
+    /// let regions = board.get_empty_regions();
+    /// assert_eq!(regions.len(), 1);
+    /// assert_eq!(regions[0].len(), 60);
+    /// ```
+    pub fn get_empty_regions(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut visited = [[false; SIZE]; SIZE];
+        let mut regions = Vec::new();
+        for row in 0..SIZE {
+            for col in 0..SIZE {
+                if visited[row][col] || self.cells[row][col] != Cell::Empty {
+                    continue;
+                }
+                visited[row][col] = true;
+                let mut region = Vec::new();
+                let mut stack = vec![(row, col)];
+                while let Some((r, c)) = stack.pop() {
+                    region.push((r, c));
+                    for (nr, nc) in Self::neighbours(r, c) {
+                        if !visited[nr][nc] && self.cells[nr][nc] == Cell::Empty {
+                            visited[nr][nc] = true;
+                            stack.push((nr, nc));
+                        }
+                    }
+                }
+                regions.push(region);
+            }
+        }
+        regions
+    }
+
     /// Returns a reference to the game move history.
     ///
     /// This method provides access to the complete history of moves
@@ -827,6 +1041,69 @@ impl Board {
         hash
     }
 
+    /// Builds a board from its hash, as returned by `to_hash`.
+    ///
+    /// The move history is empty and the turn number is derived from the
+    /// number of discs, since passes cannot be recovered from a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - The player to move ('B' or 'W') followed by the 64 cells
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Board)` - The position, with its legal move counts computed
+    /// * `Err(String)` - If the hash has the wrong length or characters
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let board = Board::new();
+    /// assert_eq!(Board::from_hash(&board.to_hash()), Ok(board));
+    /// ```
+    pub fn from_hash(hash: &str) -> Result<Self, String> {
+        let mut chars = hash.chars();
+        let player_turn = match chars.next() {
+            Some('B') => Cell::Black,
+            Some('W') => Cell::White,
+            _ => return Err(format!("Invalid player to move in '{}'", hash)),
+        };
+        let cells: Vec<Cell> = chars
+            .map(|c| match c {
+                '0' => Ok(Cell::Empty),
+                '1' => Ok(Cell::Black),
+                '2' => Ok(Cell::White),
+                _ => Err(format!("Invalid cell '{}' in '{}'", c, hash)),
+            })
+            .collect::<Result<_, _>>()?;
+        if cells.len() != SIZE * SIZE {
+            return Err(format!(
+                "Expected {} cells in '{}', found {}",
+                SIZE * SIZE,
+                hash,
+                cells.len()
+            ));
+        }
+
+        let mut board = Board::new();
+        for (i, cell) in cells.into_iter().enumerate() {
+            board.set_cell(i / SIZE, i % SIZE, cell);
+        }
+        for (index, color) in [Cell::Black, Cell::White].into_iter().enumerate() {
+            board.nb_discs[index] = board
+                .cells
+                .iter()
+                .flatten()
+                .filter(|&&c| c == color)
+                .count();
+            board.nb_legal_moves[index] = board.has_legal_moves(color).map(|moves| moves.len());
+        }
+        board.turn_number = (board.nb_discs[0] + board.nb_discs[1]).saturating_sub(3);
+        board.player_turn = player_turn;
+        board.check_game_over();
+        Ok(board)
+    }
+
     /// Checks if the game has ended.
     ///
     /// This method returns the current game state without performing any
@@ -888,6 +1165,38 @@ impl Board {
             && self.get_nb_legal_moves(Cell::White).unwrap().is_none();
         self.game_over
     }
+
+    /// Plays a move for the player to move, then hands the turn over.
+    ///
+    /// This follows the same sequence as the game loop: the move is played,
+    /// the end of the game is checked and, if the game goes on, the turn is
+    /// passed with `next_turn` (which records a pass when needed). Search
+    /// algorithms use it to walk the game tree with the correct player to move.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row index (0-7)
+    /// * `col` - The column index (0-7)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of opponent discs flipped by the move
+    /// * `Err(String)` - If the move is illegal for the player to move
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut board = Board::new();
+    /// board.play_and_advance(2, 3).unwrap();
+    /// assert_eq!(board.get_player_turn(), Cell::White);
+    /// ```
+    pub fn play_and_advance(&mut self, row: usize, col: usize) -> Result<usize, String> {
+        let gained_discs = self.try_play_move(row, col, self.player_turn)?;
+        if !self.check_game_over() {
+            self.next_turn();
+        }
+        Ok(gained_discs)
+    }
 }
 
 /// Implementation of Display trait for Board.
@@ -1346,6 +1655,8 @@ mod tests {
             color: Cell::Black,
             player_turn: Cell::Black,
             move_number: 1,
+            from_book: false,
+            search_info: None,
         };
         board_with_history.add_to_history(action);
 
@@ -1355,4 +1666,126 @@ mod tests {
             Some("2D".to_string())
         );
     }
+
+    /// Helper building a board from 8 rows of '.', 'B' and 'W'
+    fn board_from_rows(rows: [&str; SIZE]) -> Board {
+        let mut board = Board::new();
+        for (row, line) in rows.iter().enumerate() {
+            for (col, symbol) in line.chars().enumerate() {
+                let cell = match symbol {
+                    'B' => Cell::Black,
+                    'W' => Cell::White,
+                    _ => Cell::Empty,
+                };
+                board.set_cell(row, col, cell);
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn test_stable_discs_start_position() {
+        let board = Board::new();
+        assert_eq!(board.get_nb_stable_discs(Cell::Black), 0);
+        assert_eq!(board.get_nb_stable_discs(Cell::White), 0);
+    }
+
+    #[test]
+    fn test_stable_discs_grow_from_corner() {
+        let board = board_from_rows([
+            "BBB.....", "BB......", "B.......", "...WB...", "...BW...", "........", "........",
+            "W......W",
+        ]);
+        // The black triangle leans on the corner, the lone white corners are stable
+        assert_eq!(board.get_nb_stable_discs(Cell::Black), 6);
+        assert_eq!(board.get_nb_stable_discs(Cell::White), 2);
+        assert!(!board.get_stable_discs()[3][4]);
+    }
+
+    #[test]
+    fn test_stable_discs_full_edge() {
+        // A full top edge without corners of its own color is stable
+        let board = board_from_rows([
+            "WBBBBBBW", "........", "........", "...WB...", "...BW...", "........", "........",
+            "........",
+        ]);
+        assert_eq!(board.get_nb_stable_discs(Cell::Black), 6);
+        assert_eq!(board.get_nb_stable_discs(Cell::White), 2);
+
+        // The same edge with a hole: only the corners stay stable
+        let board = board_from_rows([
+            "WBBB.BBW", "........", "........", "...WB...", "...BW...", "........", "........",
+            "........",
+        ]);
+        assert_eq!(board.get_nb_stable_discs(Cell::Black), 0);
+        assert_eq!(board.get_nb_stable_discs(Cell::White), 2);
+    }
+
+    #[test]
+    fn test_stable_discs_full_board() {
+        let mut board = Board::new();
+        for row in 0..SIZE {
+            for col in 0..SIZE {
+                let color = if (row + col) % 2 == 0 {
+                    Cell::Black
+                } else {
+                    Cell::White
+                };
+                board.set_cell(row, col, color);
+            }
+        }
+        // Every line is full, so nothing can be flipped
+        assert_eq!(board.get_nb_stable_discs(Cell::Black), 32);
+        assert_eq!(board.get_nb_stable_discs(Cell::White), 32);
+    }
+
+    #[test]
+    fn test_frontier_and_potential_mobility_start_position() {
+        let board = Board::new();
+        assert_eq!(board.get_nb_frontier_discs(Cell::Black), 2);
+        assert_eq!(board.get_nb_frontier_discs(Cell::White), 2);
+        assert_eq!(board.get_potential_mobility(Cell::Black), 10);
+        assert_eq!(board.get_potential_mobility(Cell::White), 10);
+    }
+
+    #[test]
+    fn test_frontier_and_regions_in_endgame() {
+        let board = board_from_rows([
+            ".WBBBBBB", "BBBBBBBB", "BBBBBBBB", "BBBBBBBB", "BBBBBBBB", "BBBBBBBB", "BBBBBBBB",
+            "BBBBBB..",
+        ]);
+        // Only the discs around the three empty cells are on the frontier
+        assert_eq!(board.get_nb_frontier_discs(Cell::Black), 6);
+        assert_eq!(board.get_nb_frontier_discs(Cell::White), 1);
+        assert_eq!(board.get_potential_mobility(Cell::Black), 1);
+        assert_eq!(board.get_potential_mobility(Cell::White), 3);
+
+        let regions = board.get_empty_regions();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0], vec![(0, 0)]);
+        assert_eq!(regions[1].len(), 2);
+        assert!(regions[1].contains(&(7, 6)) && regions[1].contains(&(7, 7)));
+    }
+
+    #[test]
+    fn test_hash_round_trip() {
+        let mut board = Board::new();
+        board.play_and_advance(2, 3).unwrap();
+        board.play_and_advance(2, 2).unwrap();
+        let copy = Board::from_hash(&board.to_hash()).unwrap();
+        assert_eq!(copy.to_hash(), board.to_hash());
+        assert_eq!(copy.get_turn_number(), board.get_turn_number());
+        assert_eq!(
+            copy.get_nb_discs(Cell::White),
+            board.get_nb_discs(Cell::White)
+        );
+        assert_eq!(
+            copy.get_nb_legal_moves(Cell::Black),
+            board.get_nb_legal_moves(Cell::Black)
+        );
+
+        assert!(Board::from_hash("X").is_err());
+        assert!(Board::from_hash(&board.to_hash()[..60]).is_err());
+        assert!(Board::from_hash(&board.to_hash().replace('0', "3")).is_err());
+    }
 }
@@ -7,6 +7,8 @@
 
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 /// Represents the state of a cell on the Othello game board.
 ///
 /// Each cell on the 8x8 Othello board can be in one of three states:
@@ -31,7 +33,7 @@ use std::fmt;
 /// // Display cell state
 /// println!("Current cell: {}", black_disc); // Prints "BLACK"
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Cell {
     /// Empty cell with no disc placed
     Empty = 0,
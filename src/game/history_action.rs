@@ -4,7 +4,9 @@
 //! about moves played during an Othello game. This enables features like
 //! move replay, game analysis, and undo functionality.
 
-use crate::game::cell::Cell;
+use serde::{Deserialize, Serialize};
+
+use crate::{ai::search_info::SearchInfo, game::cell::Cell};
 
 /// Represents a recorded move in the game history.
 ///
@@ -23,6 +25,8 @@ use crate::game::cell::Cell;
 ///     color: Cell::Black,
 ///     move_number: 5,
 ///     player_turn: Cell::Black,
+///     from_book: false,
+///     search_info: None,
 /// };
 ///
 /// // Create a pass action (no move available)
@@ -32,9 +36,11 @@ use crate::game::cell::Cell;
 ///     color: Cell::White,
 ///     move_number: 6,
 ///     player_turn: Cell::White,
+///     from_book: false,
+///     search_info: None,
 /// };
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HistoryAction {
     /// The board coordinates where the move was played (e.g., "3D"), or None for a pass
     pub coordinates: Option<String>,
@@ -46,4 +52,10 @@ pub struct HistoryAction {
     pub move_number: usize,
     /// The player whose turn it was when this move was made
     pub player_turn: Cell,
+    /// Whether the move was taken from the opening book instead of a search
+    #[serde(default)]
+    pub from_book: bool,
+    /// Statistics of the search that chose the move (None for humans, book moves and passes)
+    #[serde(default)]
+    pub search_info: Option<SearchInfo>,
 }
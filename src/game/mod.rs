@@ -1,5 +1,8 @@
+pub mod arena;
 pub mod board;
 pub mod cell;
 pub mod history_action;
 pub mod player;
+pub mod record;
+pub mod symmetry;
 pub mod timer;
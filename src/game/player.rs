@@ -6,7 +6,15 @@
 //! configuration options that all players must support.
 
 use crate::{
-    ai::{ai_type::AIType, heuristic::HeuristicType, heuristic_matrix::AIHeuristicMatrix},
+    ai::{
+        ai_type::AIType,
+        algo::mcts::{PlayoutPolicy, SearchBudget},
+        analysis::Analysis,
+        cancel::CancelToken,
+        heuristic::HeuristicType,
+        heuristic_matrix::AIHeuristicMatrix,
+        opening_book::BookMode,
+    },
     game::{board::Board, history_action::HistoryAction},
 };
 
@@ -27,6 +35,9 @@ use crate::{
 /// - Execute a turn and return the resulting action
 /// - Indicate whether they are human or AI controlled
 ///
+/// Players are `Send` so that the interface can move an AI to a background
+/// thread while it searches.
+///
 /// # AI Configuration
 ///
 /// AI players can optionally support:
@@ -46,7 +57,7 @@ use crate::{
 ///     Err(e) => println!("Invalid move: {}", e),
 /// }
 /// ```
-pub trait Player {
+pub trait Player: Send {
     /// Executes a player's turn on the game board.
     ///
     /// This is the core method that all players must implement. It processes
@@ -85,6 +96,38 @@ pub trait Player {
         cell: Option<(usize, usize)>,
     ) -> Result<HistoryAction, String>;
 
+    /// Scores every legal move of the position for the player to move.
+    ///
+    /// Unlike `play_turn`, this method does not play any move and ignores the
+    /// opening book. The search runs from the point of view of the player to
+    /// move on `board`, whatever the color of this player.
+    ///
+    /// # Arguments
+    ///
+    /// * `_board` - The position to analyze
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Analysis)` - Every legal move ranked from best to worst
+    /// * `Err(String)` - Analysis failed or not supported for this player type
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns an error indicating analysis is not supported.
+    /// Only the search AIs should override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let analysis = alphabeta_player.analyze(&board)?;
+    /// if let Some(best) = analysis.best() {
+    ///     println!("Best move {:?} scored {}", best.action.pos, best.action.score);
+    /// }
+    /// ```
+    fn analyze(&self, _board: &Board) -> Result<Analysis, String> {
+        Err("Analysis is not supported for this player type".to_string())
+    }
+
     /// Imports a Q-table from file for Q-Learning AI players.
     ///
     /// This method allows Q-Learning AI players to load previously trained
@@ -109,7 +152,7 @@ pub trait Player {
     ///
     /// ```rust
     /// // Only works for Q-Learning players
-    /// match qlearning_player.import_q_table_file("qtable.json") {
+    /// match qlearning_player.import_q_table_file("qtable.bin") {
     ///     Ok(()) => println!("Q-table loaded successfully"),
     ///     Err(e) => println!("Failed to load Q-table: {}", e),
     /// }
@@ -415,4 +458,269 @@ pub trait Player {
     /// ai_player.set_depth(5);
     /// ```
     fn set_depth(&mut self, _depth: usize) {}
+
+    /// Gets the iteration budget for simulation-based AI algorithms.
+    ///
+    /// This method returns how many playouts a Monte Carlo Tree Search
+    /// player runs for each move. More iterations give more reliable move
+    /// statistics at the cost of a longer thinking time.
+    ///
+    /// # Returns
+    ///
+    /// The number of playouts per move.
+    /// Default implementation returns 0 (no simulation budget).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let iterations = mcts_player.get_iterations();
+    /// println!("MCTS runs {} playouts per move", iterations);
+    /// ```
+    fn get_iterations(&self) -> usize {
+        0
+    }
+
+    /// Sets the iteration budget for simulation-based AI algorithms.
+    ///
+    /// Players searching with a time budget keep it: use
+    /// [`Player::set_search_budget`] to change the kind of budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `_iterations` - The number of playouts to run for each move
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. AI implementations should override this method
+    /// if they are driven by a number of simulations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// mcts_player.set_iterations(10000);
+    /// ```
+    fn set_iterations(&mut self, _iterations: usize) {}
+
+    /// Gets the search budget of simulation-based AI algorithms.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(SearchBudget)` - The iteration or time budget of each search
+    /// * `None` - For players that do not run simulations (default)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// if let Some(SearchBudget::Time(time)) = player.get_search_budget() {
+    ///     println!("Searching for {} ms", time.as_millis());
+    /// }
+    /// ```
+    fn get_search_budget(&self) -> Option<SearchBudget> {
+        None
+    }
+
+    /// Sets the search budget of simulation-based AI algorithms.
+    ///
+    /// # Arguments
+    ///
+    /// * `_budget` - The iteration or time budget of each search
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. AI implementations should override this method
+    /// if they run simulated games.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// mcts_player.set_search_budget(SearchBudget::Time(Duration::from_millis(500)));
+    /// ```
+    fn set_search_budget(&mut self, _budget: SearchBudget) {}
+
+    /// Gets the exploration constant of tree search AI algorithms.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(f64)` - The constant C of the UCT selection formula
+    /// * `None` - For players without a UCT search (default)
+    fn get_exploration(&self) -> Option<f64> {
+        None
+    }
+
+    /// Sets the exploration constant of tree search AI algorithms.
+    ///
+    /// # Arguments
+    ///
+    /// * `_exploration` - The constant C of the UCT selection formula
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. AI implementations should override this method
+    /// if they select moves with the UCT formula.
+    fn set_exploration(&mut self, _exploration: f64) {}
+
+    /// Gets the playout policy used by simulation-based AI algorithms.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PlayoutPolicy)` - The move selection strategy of the simulations
+    /// * `None` - For players that do not run simulations (default)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// if let Some(policy) = player.get_playout_policy() {
+    ///     println!("Playouts are {}", policy);
+    /// }
+    /// ```
+    fn get_playout_policy(&self) -> Option<PlayoutPolicy> {
+        None
+    }
+
+    /// Sets the playout policy used by simulation-based AI algorithms.
+    ///
+    /// # Arguments
+    ///
+    /// * `_playout` - The move selection strategy for the simulations
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. AI implementations should override this method
+    /// if they run simulated games.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// mcts_player.set_playout_policy(PlayoutPolicy::Heuristic);
+    /// ```
+    fn set_playout_policy(&mut self, _playout: PlayoutPolicy) {}
+
+    /// Gets how the player uses the opening book.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(BookMode)` - For search AIs that can play book moves
+    /// * `None` - For players that never use the book (default)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// if let Some(mode) = player.get_book_mode() {
+    ///     println!("Opening book: {}", mode);
+    /// }
+    /// ```
+    fn get_book_mode(&self) -> Option<BookMode> {
+        None
+    }
+
+    /// Sets how the player uses the opening book.
+    ///
+    /// # Arguments
+    ///
+    /// * `_mode` - Off, best book move, or random good book move
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. Search AI implementations should override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// ai_player.set_book_mode(BookMode::Random);
+    /// ```
+    fn set_book_mode(&mut self, _mode: BookMode) {}
+
+    /// Gets the number of workers used by the search.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - For search AIs running on a worker pool
+    /// * `None` - For players that do not search in parallel (default)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// if let Some(threads) = player.get_threads() {
+    ///     println!("Searching with {} threads", threads);
+    /// }
+    /// ```
+    fn get_threads(&self) -> Option<usize> {
+        None
+    }
+
+    /// Sets the number of workers used by the search.
+    ///
+    /// # Arguments
+    ///
+    /// * `_threads` - Number of workers (at least one is used)
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. Search AI implementations should override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// ai_player.set_threads(4);
+    /// ```
+    fn set_threads(&mut self, _threads: usize) {}
+
+    /// Gives the player the token that stops its searches.
+    ///
+    /// Once the token is cancelled, a running `play_turn` or `analyze`
+    /// returns the `SEARCH_CANCELLED` error as soon as possible, and so do
+    /// the later searches until another token is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `_cancel` - Token shared with the caller that may stop the search
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. Search AI implementations should override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let token = CancelToken::new();
+    /// ai_player.set_cancel_token(token.clone());
+    /// // From another thread: stop the search
+    /// token.cancel();
+    /// ```
+    fn set_cancel_token(&mut self, _cancel: CancelToken) {}
+
+    /// Gets the Q-table file the player loads before a game.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` - For Q-Learning players, the path of their Q-table
+    /// * `None` - For players without a Q-table (default)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// if let Some(file) = player.get_q_table_file() {
+    ///     player.import_q_table_file(&file)?;
+    /// }
+    /// ```
+    fn get_q_table_file(&self) -> Option<String> {
+        None
+    }
+
+    /// Sets the Q-table file the player loads before a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `_file` - Path of the Q-table file
+    ///
+    /// # Default Implementation
+    ///
+    /// Does nothing. Only Q-Learning implementations should override this method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// qlearning_player.set_q_table_file("q_tables/q_table_white.bin".to_string());
+    /// ```
+    fn set_q_table_file(&mut self, _file: String) {}
 }
@@ -0,0 +1,219 @@
+//! Saved game records.
+//!
+//! Finished or interrupted games can be saved from the game screen. Each game
+//! is stored on one line of a text file as a JSON `GameRecord`, which keeps the
+//! search statistics of every AI move. Lines holding only the list of moves,
+//! for example `3C 2C 2D -- 4B` where `--` marks a pass, are also accepted.
+//! The records are read back by the command line tools, e.g. to build the
+//! opening book.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{board::Board, history_action::HistoryAction};
+
+/// Moves of a recorded game, `None` being a pass.
+pub type GameMoves = Vec<Option<(usize, usize)>>;
+
+/// Text used for a pass in a game record.
+const PASS: &str = "--";
+
+/// A saved game: its whole history, search statistics included.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// Every move of the game, in order, passes included
+    pub moves: Vec<HistoryAction>,
+}
+
+/// Formats a game history as a single record line.
+///
+/// # Arguments
+///
+/// * `history` - The history of the game, as returned by `Board::get_history`
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON `GameRecord` of the game, without line break
+/// * `Err(String)` - If the history could not be serialized
+///
+/// # Examples
+///
+/// ```rust
+/// let mut board = Board::new();
+/// board.add_to_history(ai.play_turn(&mut board, None).unwrap());
+/// assert!(game_to_line(board.get_history()).unwrap().starts_with("{\"moves\""));
+/// ```
+pub fn game_to_line(history: &[HistoryAction]) -> Result<String, String> {
+    serde_json::to_string(&GameRecord {
+        moves: history.to_vec(),
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Parses a record line into its list of moves.
+///
+/// # Arguments
+///
+/// * `line` - A line written by `game_to_line`, or a plain list of moves
+///
+/// # Returns
+///
+/// * `Ok(GameMoves)` - The moves, `None` being a pass
+/// * `Err(String)` - If the record is malformed or a move is not a valid coordinate
+pub fn line_to_moves(line: &str) -> Result<GameMoves, String> {
+    if line.trim_start().starts_with('{') {
+        let record: GameRecord = serde_json::from_str(line).map_err(|e| e.to_string())?;
+        return record
+            .moves
+            .iter()
+            .map(|action| match &action.coordinates {
+                None => Ok(None),
+                Some(coordinates) => Board::input_to_coordinates(coordinates)
+                    .map(Some)
+                    .ok_or(format!("Invalid move '{}' in game record", coordinates)),
+            })
+            .collect();
+    }
+    line.split_whitespace()
+        .map(|token| {
+            if token == PASS {
+                Ok(None)
+            } else {
+                Board::input_to_coordinates(token)
+                    .map(Some)
+                    .ok_or(format!("Invalid move '{}' in game record", token))
+            }
+        })
+        .collect()
+}
+
+/// Replays a list of moves from the starting position.
+///
+/// Passes are skipped since `Board::next_turn` already hands the turn over
+/// when a player has no legal move.
+///
+/// # Arguments
+///
+/// * `moves` - The moves of the game, as returned by `line_to_moves`
+///
+/// # Returns
+///
+/// * `Ok(Vec<Board>)` - The position before every played move, followed by the final position
+/// * `Err(String)` - If a move is illegal in the replayed position
+pub fn replay(moves: &[Option<(usize, usize)>]) -> Result<Vec<Board>, String> {
+    let mut board = Board::new();
+    let mut positions = vec![];
+    for (row, col) in moves.iter().flatten() {
+        if board.check_game_over() {
+            return Err("Game record continues after the end of the game".to_string());
+        }
+        positions.push(board.clone());
+        let color = board.get_player_turn();
+        board.try_play_move(*row, *col, color)?;
+        if !board.check_game_over() {
+            board.next_turn();
+        }
+    }
+    positions.push(board);
+    Ok(positions)
+}
+
+/// Appends a game to a record file, creating the file if needed.
+///
+/// # Arguments
+///
+/// * `board` - The board of the game to save
+/// * `file_path` - Path of the record file
+///
+/// # Returns
+///
+/// * `Ok(())` - If the game was written
+/// * `Err(String)` - If the file could not be opened or written
+pub fn save_game(board: &Board, file_path: &str) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)
+        .map_err(|e| e.to_string())?;
+    let line = game_to_line(board.get_history())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())
+}
+
+/// Loads every game of a record file.
+///
+/// Empty lines are ignored.
+///
+/// # Arguments
+///
+/// * `file_path` - Path of the record file
+///
+/// # Returns
+///
+/// * `Ok(Vec<GameMoves>)` - The moves of every game
+/// * `Err(String)` - If the file could not be read or a line is invalid
+pub fn load_games(file_path: &str) -> Result<Vec<GameMoves>, String> {
+    let file = File::open(file_path).map_err(|e| e.to_string())?;
+    let mut games = vec![];
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if !line.trim().is_empty() {
+            games.push(line_to_moves(&line)?);
+        }
+    }
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ai::search_info::SearchInfo, game::cell::Cell};
+    use std::time::Duration;
+
+    #[test]
+    fn test_line_round_trip() {
+        let moves = line_to_moves("2D 2C -- 3C").unwrap();
+        assert_eq!(moves, vec![Some((2, 3)), Some((2, 2)), None, Some((3, 2))]);
+        assert!(line_to_moves("2D Z9").is_err());
+    }
+
+    #[test]
+    fn test_replay_matches_history() {
+        let mut board = Board::new();
+        for _ in 0..6 {
+            let color = board.get_player_turn();
+            let (row, col) = board.has_legal_moves(color).unwrap()[0];
+            let gained_discs = board.try_play_move(row, col, color).unwrap();
+            board.add_to_history(HistoryAction {
+                coordinates: Some(Board::coordinates_to_input(row, col)),
+                gained_discs: Some(gained_discs),
+                color,
+                move_number: board.get_turn_number(),
+                player_turn: color,
+                from_book: false,
+                search_info: Some(SearchInfo::new(
+                    100,
+                    Duration::from_millis(4),
+                    2,
+                    -3,
+                    vec![Board::coordinates_to_input(row, col)],
+                )),
+            });
+            board.next_turn();
+        }
+
+        let line = game_to_line(board.get_history()).unwrap();
+        let record: GameRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(record.moves, board.get_history().to_vec());
+
+        let moves = line_to_moves(&line).unwrap();
+        let positions = replay(&moves).unwrap();
+        assert_eq!(positions.len(), 7);
+        assert_eq!(positions[6].to_hash(), board.to_hash());
+        assert_eq!(positions[0], Board::new());
+        assert_eq!(positions[1].get_player_turn(), Cell::White);
+    }
+}
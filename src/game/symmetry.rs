@@ -0,0 +1,227 @@
+//! Board symmetries for position lookups.
+//!
+//! The Othello board has eight symmetries (four rotations and four
+//! reflections). Positions that only differ by one of them are strategically
+//! identical, so tables indexed by position (like the opening book) store
+//! them once under a canonical hash and map the moves back to the real board.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{consts::SIZE, game::board::Board};
+
+/// One of the eight symmetries of the square board.
+///
+/// # Examples
+///
+/// ```rust
+/// // A corner stays a corner under every symmetry
+/// assert_eq!(Symmetry::Rotate90.apply((0, 0)), (0, 7));
+/// assert_eq!(Symmetry::Rotate90.inverse().apply((0, 7)), (0, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    /// No transformation
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    /// Half turn
+    Rotate180,
+    /// Quarter turn counter-clockwise
+    Rotate270,
+    /// Mirror left and right columns
+    FlipHorizontal,
+    /// Mirror top and bottom rows
+    FlipVertical,
+    /// Mirror along the 0A-7H diagonal
+    Transpose,
+    /// Mirror along the 0H-7A diagonal
+    AntiTranspose,
+}
+
+/// All the symmetries of the board, starting with the identity.
+pub const SYMMETRIES: [Symmetry; 8] = [
+    Symmetry::Identity,
+    Symmetry::Rotate90,
+    Symmetry::Rotate180,
+    Symmetry::Rotate270,
+    Symmetry::FlipHorizontal,
+    Symmetry::FlipVertical,
+    Symmetry::Transpose,
+    Symmetry::AntiTranspose,
+];
+
+impl Symmetry {
+    /// Maps board coordinates through the symmetry.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The (row, col) coordinates to transform
+    ///
+    /// # Returns
+    ///
+    /// The (row, col) coordinates of the same cell on the transformed board.
+    pub fn apply(self, pos: (usize, usize)) -> (usize, usize) {
+        let (row, col) = pos;
+        let last = SIZE - 1;
+        match self {
+            Symmetry::Identity => (row, col),
+            Symmetry::Rotate90 => (col, last - row),
+            Symmetry::Rotate180 => (last - row, last - col),
+            Symmetry::Rotate270 => (last - col, row),
+            Symmetry::FlipHorizontal => (row, last - col),
+            Symmetry::FlipVertical => (last - row, col),
+            Symmetry::Transpose => (col, row),
+            Symmetry::AntiTranspose => (last - col, last - row),
+        }
+    }
+
+    /// Returns the symmetry that undoes this one.
+    ///
+    /// Only the quarter turns differ from their inverse; every other
+    /// symmetry undoes itself.
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            other => other,
+        }
+    }
+
+    /// Transforms a hash produced by `Board::to_hash`.
+    ///
+    /// The player turn character is kept and every cell character is moved
+    /// to its transformed position.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - A 65 characters board hash
+    pub fn apply_to_hash(self, hash: &str) -> String {
+        let bytes = hash.as_bytes();
+        let mut transformed = bytes.to_vec();
+        for row in 0..SIZE {
+            for col in 0..SIZE {
+                let (new_row, new_col) = self.apply((row, col));
+                transformed[1 + new_row * SIZE + new_col] = bytes[1 + row * SIZE + col];
+            }
+        }
+        String::from_utf8(transformed).expect("Board hashes are ASCII")
+    }
+}
+
+/// Computes the canonical hash of a position.
+///
+/// The canonical hash is the smallest hash among the eight transformed
+/// boards, so every symmetric variant of a position gets the same key.
+///
+/// # Arguments
+///
+/// * `board` - The position to hash
+///
+/// # Returns
+///
+/// The canonical hash and the symmetry that maps `board` onto it. Moves on
+/// `board` are converted to canonical coordinates with `symmetry.apply` and
+/// back with `symmetry.inverse().apply`.
+///
+/// # Examples
+///
+/// ```rust
+/// let board = Board::new();
+/// let (hash, symmetry) = canonical_hash(&board);
+/// assert_eq!(symmetry.apply_to_hash(&board.to_hash()), hash);
+/// ```
+pub fn canonical_hash(board: &Board) -> (String, Symmetry) {
+    let hash = board.to_hash();
+    SYMMETRIES
+        .iter()
+        .map(|symmetry| (symmetry.apply_to_hash(&hash), *symmetry))
+        .min_by(|a, b| a.0.cmp(&b.0))
+        .expect("There is always at least one symmetry")
+}
+
+/// Counts the positions reachable from the start, with and without symmetry.
+///
+/// Every sequence of legal moves is played from the initial position, and
+/// the distinct positions after each number of plies are counted twice: by
+/// their hash and by their canonical hash. Passes do not count as plies.
+///
+/// # Arguments
+///
+/// * `plies` - Number of plies to explore
+///
+/// # Returns
+///
+/// For every ply from 1 to `plies`, the number of distinct positions and the
+/// number of distinct canonical positions.
+///
+/// # Examples
+///
+/// ```rust
+/// // The four first moves of Black are symmetric to each other
+/// assert_eq!(count_positions(1), vec![(4, 1)]);
+/// ```
+pub fn count_positions(plies: usize) -> Vec<(usize, usize)> {
+    let mut frontier = vec![Board::new()];
+    let mut counts = Vec::with_capacity(plies);
+    for _ in 0..plies {
+        let mut next = HashMap::new();
+        for board in &frontier {
+            for (row, col) in board
+                .has_legal_moves(board.get_player_turn())
+                .unwrap_or_default()
+            {
+                let mut child = board.clone();
+                if child.play_and_advance(row, col).is_ok() {
+                    next.entry(child.to_hash()).or_insert(child);
+                }
+            }
+        }
+        let canonical: HashSet<String> =
+            next.values().map(|board| canonical_hash(board).0).collect();
+        counts.push((next.len(), canonical.len()));
+        frontier = next.into_values().collect();
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::cell::Cell;
+
+    #[test]
+    fn test_inverse_undoes_symmetry() {
+        for symmetry in SYMMETRIES {
+            for row in 0..SIZE {
+                for col in 0..SIZE {
+                    assert_eq!(
+                        symmetry.inverse().apply(symmetry.apply((row, col))),
+                        (row, col)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_symmetric_openings_share_canonical_hash() {
+        // The four first moves of Black are symmetric to each other
+        let mut hashes = vec![];
+        for (row, col) in Board::new().has_legal_moves(Cell::Black).unwrap() {
+            let mut board = Board::new();
+            board.try_play_move(row, col, Cell::Black).unwrap();
+            board.next_turn();
+            hashes.push(canonical_hash(&board).0);
+        }
+        assert_eq!(hashes.len(), 4);
+        assert!(hashes.iter().all(|hash| *hash == hashes[0]));
+    }
+
+    #[test]
+    fn test_count_positions() {
+        assert_eq!(
+            count_positions(5),
+            vec![(4, 1), (12, 3), (54, 14), (236, 60), (1288, 322)]
+        );
+    }
+}
@@ -5,14 +5,52 @@
 //! navigation, game state coordination, player management, and user
 //! interaction processing for the terminal-based interface.
 
+use std::{
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
 use ratatui::{crossterm::event::KeyCode, widgets::ListState};
 
 use crate::{
-    ai::algo::qlearning::QLearning,
-    consts::SIZE,
-    game::{board::Board, cell::Cell, player::Player, timer::Timer},
+    ai::{
+        algo::{alphabeta::AIAlphaBeta, qlearning::QLearning},
+        analysis::Analysis,
+        cancel::{CancelToken, SEARCH_CANCELLED},
+        heuristic::HeuristicType,
+        heuristic_matrix::{
+            custom_matrices, save_custom_matrix, set_symmetric, AIHeuristicMatrix, CustomMatrix,
+        },
+        ponder::{ponder, PonderCache, PonderStats},
+        training_metrics::TrainingMetrics,
+    },
+    consts::{ANALYSIS_DEPTH, SAVED_GAMES_FILE, SIZE},
+    game::{
+        board::Board, cell::Cell, history_action::HistoryAction, player::Player, record,
+        timer::Timer,
+    },
 };
 
+/// What the background search thread hands back: the player, the board it
+/// played on and the result of its turn.
+type SearchOutcome = (Box<dyn Player>, Board, Result<HistoryAction, String>);
+
+/// An AI turn running on a background thread.
+///
+/// The searching player is moved out of its slot in `App` for the duration
+/// of the search and comes back with the outcome, so the interface keeps
+/// redrawing and handling keys while the AI thinks.
+pub struct AISearch {
+    /// Color of the searching player, whose slot is empty meanwhile
+    pub color: Cell,
+    /// When the search started
+    pub started: Instant,
+    /// Token that stops the search
+    cancel: CancelToken,
+    /// Thread playing the turn on a copy of the board
+    handle: JoinHandle<SearchOutcome>,
+}
+
 /// Enumeration of all possible application screens.
 ///
 /// This enum represents the different screens/states that the application
@@ -27,6 +65,7 @@ use crate::{
 /// - **HumanVsAI**: Configuration screen for Human vs AI games
 /// - **AIVsAI**: Configuration screen for AI vs AI games
 /// - **QLearningParameters**: Training configuration for Q-Learning AI
+/// - **MatrixEditor**: Editor of the heuristic matrices, drawn as a heatmap
 /// - **Exit**: Confirmation dialog for application termination
 ///
 /// # Navigation Flow
@@ -58,9 +97,107 @@ pub enum CurrentScreen {
     AIVsAI,
     QLearningParameters,
     QLearningLoading,
+    MatrixEditor,
     Exit,
 }
 
+/// State of the heuristic matrix editor.
+///
+/// The editor works on a copy of the weights. Saving writes a user-defined
+/// matrix: the edited one, or a new one when a built-in matrix was edited.
+pub struct MatrixEditor {
+    /// Matrix the weights were loaded from
+    pub matrix: AIHeuristicMatrix,
+    /// Weights being edited
+    pub values: [[isize; SIZE]; SIZE],
+    /// Whether the weights differ from the saved matrix
+    pub modified: bool,
+    /// Result of the last save, or why a key was refused
+    pub message: Option<String>,
+}
+
+impl MatrixEditor {
+    /// Opens a matrix in the editor.
+    pub fn new(matrix: AIHeuristicMatrix) -> Self {
+        Self {
+            values: matrix.value(),
+            matrix,
+            modified: false,
+            message: None,
+        }
+    }
+
+    /// Adds `delta` to a square and to every square symmetric to it.
+    pub fn adjust(&mut self, pos: (usize, usize), delta: isize) {
+        let value = self.values[pos.0][pos.1] + delta;
+        set_symmetric(&mut self.values, pos, value);
+        self.modified = true;
+        self.message = None;
+    }
+
+    /// Saves the weights and opens the saved matrix.
+    ///
+    /// Matrices A and B are built in, so their edits are saved as a new
+    /// matrix named "Custom N".
+    pub fn save(&mut self) {
+        let name = match &self.matrix {
+            AIHeuristicMatrix::Custom(matrix) => matrix.name.clone(),
+            _ => {
+                let names: Vec<String> = custom_matrices()
+                    .iter()
+                    .map(|matrix| matrix.name.clone())
+                    .collect();
+                (1..)
+                    .map(|n| format!("Custom {}", n))
+                    .find(|name| !names.contains(name))
+                    .expect("There is always a free name")
+            }
+        };
+        let values = self.values.iter().map(|row| row.to_vec()).collect();
+        match CustomMatrix::new(&name, values).and_then(save_custom_matrix) {
+            Ok(matrix) => {
+                self.matrix = AIHeuristicMatrix::Custom(matrix);
+                self.modified = false;
+                self.message = Some(format!("Saved as '{}'", name));
+            }
+            Err(e) => self.message = Some(format!("Could not save: {}", e)),
+        }
+    }
+}
+
+/// What the pondering thread hands back: the player and its analyses.
+type PonderOutcome = (Box<dyn Player>, Result<PonderCache, String>);
+
+/// What the analysis thread hands back: the player taken from its slot to
+/// analyze, if any, and the scores of the candidate moves.
+type AnalysisOutcome = (Option<Box<dyn Player>>, Result<Analysis, String>);
+
+/// The overlay analysis running on a background thread.
+///
+/// An AI to move analyzes the position itself, out of its slot like an
+/// `AISearch`, and goes back to it when the analysis is joined.
+struct AnalysisSearch {
+    /// Color of the player to move on the analyzed position
+    color: Cell,
+    /// Token that stops the analysis
+    cancel: CancelToken,
+    /// Thread scoring the legal moves
+    handle: JoinHandle<AnalysisOutcome>,
+}
+
+/// An AI pondering on the human's time.
+///
+/// Like an `AISearch`, the pondering AI is out of its slot until the human
+/// has moved and `stop_pondering` joins the thread.
+struct PonderSearch {
+    /// Color of the pondering AI
+    color: Cell,
+    /// Token that stops the pondering
+    cancel: CancelToken,
+    /// Thread analyzing the positions after the human replies
+    handle: JoinHandle<PonderOutcome>,
+}
+
 /// Main application state container for the Othello game GUI.
 ///
 /// The `App` struct serves as the central state manager for the entire
@@ -176,17 +313,67 @@ pub struct App {
     /// None when not configuring or running Q-Learning training.
     pub qlearning_parameters: Option<QLearning>,
 
-    /// Optional loading state for Q-Learning training progress.
-    ///     
-    /// Represents the current progress of Q-Learning training
-    /// session, typically a percentage or epoch count.
-    pub qlearning_loading: Option<f64>,
+    /// Metrics received from the running Q-Learning training, one per epoch.
+    ///
+    /// The last one gives the training progress, and the whole history is
+    /// charted on the loading screen.
+    pub qlearning_metrics: Vec<TrainingMetrics>,
 
-    /// Channel for receiving Q-Learning training progress updates.
+    /// Channel for receiving Q-Learning training metrics.
     ///     
     /// Used to communicate between the Q-Learning training thread
     /// and the main application for real-time updates.
-    pub qlearning_channel: Option<std::sync::mpsc::Receiver<f64>>,
+    pub qlearning_channel: Option<std::sync::mpsc::Receiver<TrainingMetrics>>,
+
+    /// Q-table file written by the running Q-Learning training.
+    ///
+    /// Shown on the loading screen once the training is complete.
+    pub qlearning_output: Option<String>,
+
+    /// Token stopping the running Q-Learning training after its current epoch.
+    pub qlearning_cancel: Option<CancelToken>,
+
+    /// Thread of the running Q-Learning training, joined to wait for its save.
+    pub qlearning_handle: Option<JoinHandle<()>>,
+
+    /// Whether the scores of the candidate moves are shown on the board.
+    ///
+    /// Toggled with 'a' on the game screen.
+    pub show_analysis: bool,
+
+    /// Scores of the legal moves of the current position.
+    ///
+    /// Computed in the background after every move while `show_analysis`
+    /// is enabled, None otherwise and until the analysis is done.
+    pub analysis: Option<Analysis>,
+
+    /// Analysis of the current position running in the background, if any.
+    analysis_search: Option<AnalysisSearch>,
+
+    /// AI turn being searched in the background, if any.
+    ///
+    /// Started by `play_ai_turn` and stopped by `cancel_ai_search` when
+    /// the user quits or leaves the game screen.
+    pub ai_search: Option<AISearch>,
+
+    /// Whether the AI thinks on the human's time in Human vs AI.
+    ///
+    /// Toggled on the Human vs AI setup screen.
+    pub pondering: bool,
+
+    /// AI pondering during the human's turn, if any.
+    ponder_search: Option<PonderSearch>,
+
+    /// Analyses left by the last pondering, until the AI turn uses them.
+    ///
+    /// None when the AI did not ponder (or cannot analyze positions).
+    ponder_cache: Option<PonderCache>,
+
+    /// Ponder hits and misses of the current game.
+    pub ponder_stats: PonderStats,
+
+    /// Heuristic matrix editor, while its screen is open.
+    pub matrix_editor: Option<MatrixEditor>,
 }
 
 impl App {
@@ -228,9 +415,21 @@ impl App {
             selected_cell: None,        // Aucune cellule sélectionnée par défaut
             timer: None,                // Pas de timer initialement
             qlearning_parameters: None, // Pas de paramètres QLearning initialement
-            qlearning_loading: None,
+            qlearning_metrics: Vec::new(),
             qlearning_channel: None, // Pas de canal QLearning initialement
+            qlearning_output: None,
+            qlearning_cancel: None,
+            qlearning_handle: None,
             previous_screen: None,
+            show_analysis: false,
+            analysis: None,
+            analysis_search: None,
+            ai_search: None,
+            pondering: false,
+            ponder_search: None,
+            ponder_cache: None,
+            ponder_stats: PonderStats::default(),
+            matrix_editor: None,
         }
     }
 
@@ -279,6 +478,8 @@ impl App {
             self.board.as_ref().unwrap().get_player_turn()
         ));
         self.timer = Some(Timer::new());
+        self.ponder_stats = PonderStats::default();
+        self.refresh_analysis();
     }
 
     /// Processes a player's turn in the GUI context.
@@ -312,6 +513,11 @@ impl App {
     /// - **Missing players**: Show appropriate error message
     /// - **Game over**: Prevent further moves, show final status
     ///
+    /// # Opening Book
+    ///
+    /// When an AI plays a move from the opening book, the turn message says
+    /// so (e.g. "Book move 2D ! It's WHITE turn !").
+    ///
     /// # Game Completion
     ///
     /// When game ends:
@@ -337,54 +543,290 @@ impl App {
     /// app.gui_play_turn(); // Processes the move
     /// ```
     pub fn gui_play_turn(&mut self) {
-        let mut play_turn_result = Err("Error in Enter".to_string());
-        let mut new_message = None;
-        if let Some(board) = &mut self.board {
-            if !board.is_game_over() {
-                match board.get_player_turn() {
-                    Cell::Black => {
-                        if let Some(player) = &self.player_1 {
-                            play_turn_result = player.play_turn(board, self.selected_cell);
-                        }
-                    }
-                    Cell::White => {
-                        if let Some(player) = &self.player_2 {
-                            play_turn_result = player.play_turn(board, self.selected_cell);
-                        }
-                    }
-                    _ => {
-                        play_turn_result = Err("Invalid player turn".to_string());
-                    }
+        let play_turn_result = match &mut self.board {
+            Some(board) if !board.is_game_over() => match board.get_player_turn() {
+                Cell::Black => match &self.player_1 {
+                    Some(player) => player.play_turn(board, self.selected_cell),
+                    None => return,
+                },
+                Cell::White => match &self.player_2 {
+                    Some(player) => player.play_turn(board, self.selected_cell),
+                    None => return,
+                },
+                _ => Err("Invalid player turn".to_string()),
+            },
+            _ => return,
+        };
+        self.end_turn(play_turn_result);
+    }
+
+    /// Records the result of a turn and moves the game on.
+    ///
+    /// A failed turn only shows its error. A played move stops the pondering
+    /// AI and is added to the history, then either the game is over and the
+    /// winner is announced, or the next player is to move.
+    ///
+    /// # Arguments
+    ///
+    /// * `play_turn_result` - What `Player::play_turn` returned on `self.board`
+    fn end_turn(&mut self, play_turn_result: Result<HistoryAction, String>) {
+        match play_turn_result {
+            Err(e) => {
+                self.set_game_message(Some(e));
+            }
+            Ok(history_action) => {
+                // The human has moved: the pondering AI gets its turn back
+                self.stop_pondering();
+                let board = match &mut self.board {
+                    Some(board) => board,
+                    None => return,
+                };
+                let book_move = if history_action.from_book {
+                    history_action.coordinates.clone()
+                } else {
+                    None
+                };
+                board.add_to_history(history_action);
+
+                if board.check_game_over() {
+                    self.timer.as_mut().unwrap().stop();
+                    let message = match board.get_winner() {
+                        Some(winner) => format!("Game over! {} is the WINNER!", winner),
+                        None => "Game over! It's a draw!".to_string(),
+                    };
+                    self.set_game_message(Some(message));
+                    self.stop_analysis();
+                    self.analysis = None;
+                } else {
+                    board.next_turn();
+                    let message = match book_move {
+                        Some(coordinates) => format!(
+                            "Book move {} ! It's {} turn !",
+                            coordinates,
+                            board.get_player_turn()
+                        ),
+                        None => format!("It's {} turn !", board.get_player_turn()),
+                    };
+                    self.set_game_message(Some(message));
+                    self.refresh_analysis();
                 }
+            }
+        }
+    }
 
-                match play_turn_result {
-                    Err(e) => {
-                        self.set_game_message(Some(e));
-                    }
-                    Ok(history_action) => {
-                        board.add_to_history(history_action);
-
-                        if board.check_game_over() {
-                            self.timer.as_mut().unwrap().stop();
-                            if let Some(winner) = board.get_winner() {
-                                new_message = Some(format!("Game over! {} is the WINNER!", winner));
-                            } else {
-                                new_message = Some("Game over! It's a draw!".to_string());
-                            }
-                            if let Some(message) = new_message {
-                                self.set_game_message(Some(message));
-                            }
-                        } else {
-                            board.next_turn();
-                            new_message = Some(format!("It's {} turn !", board.get_player_turn()));
-                            if let Some(message) = new_message {
-                                self.set_game_message(Some(message));
-                            }
-                        }
-                    }
+    /// Plays the turn of the AI to move without blocking the interface.
+    ///
+    /// Called on every iteration of the main loop while an AI is to move.
+    /// The first call moves the AI out of its slot and starts its search on
+    /// a background thread, with a fresh `CancelToken`. The following calls
+    /// only check the thread, and once it is done the AI goes back to its
+    /// slot and its move is played like in `gui_play_turn`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // In the main loop, while the player to move is an AI
+    /// app.play_ai_turn();
+    /// terminal.draw(|f| ui(f, app))?; // The spinner keeps turning
+    /// ```
+    pub fn play_ai_turn(&mut self) {
+        match &self.ai_search {
+            Some(search) if search.handle.is_finished() => {
+                let search = self.ai_search.take().unwrap();
+                let (board, result) = self.join_ai_search(search);
+                if result.is_ok() {
+                    self.board = board;
                 }
+                self.end_turn(result);
+            }
+            Some(_) => {}
+            None => self.start_ai_search(),
+        }
+    }
+
+    /// Moves the AI to play out of its slot and starts its search.
+    ///
+    /// When the AI pondered the position during the human's turn, it plays
+    /// the pondered analysis at once instead.
+    fn start_ai_search(&mut self) {
+        // The AI to move may be analyzing the position for the overlay
+        if self.analysis_search.is_some() {
+            return;
+        }
+        self.stop_pondering();
+        let board = match &mut self.board {
+            Some(board) if !board.is_game_over() => board,
+            _ => return,
+        };
+        let color = board.get_player_turn();
+
+        if let Some(mut cache) = self.ponder_cache.take() {
+            let pondered = cache.remove(&board.to_hash());
+            self.ponder_stats.record(pondered.is_some());
+            if let Some(analysis) = pondered {
+                let result = analysis.play_best(board, color);
+                self.end_turn(result);
+                return;
             }
         }
+
+        let board = board.clone();
+        let slot = match color {
+            Cell::Black => &mut self.player_1,
+            _ => &mut self.player_2,
+        };
+        let mut player = match slot.take() {
+            Some(player) => player,
+            None => return,
+        };
+
+        let cancel = CancelToken::new();
+        player.set_cancel_token(cancel.clone());
+        let handle = thread::spawn(move || {
+            let mut board = board;
+            let result = player.play_turn(&mut board, None);
+            (player, board, result)
+        });
+        self.ai_search = Some(AISearch {
+            color,
+            started: Instant::now(),
+            cancel,
+            handle,
+        });
+    }
+
+    /// Waits for a search thread and puts its player back in its slot.
+    ///
+    /// # Returns
+    ///
+    /// The board the AI played on, and the result of its turn.
+    fn join_ai_search(
+        &mut self,
+        search: AISearch,
+    ) -> (Option<Board>, Result<HistoryAction, String>) {
+        match search.handle.join() {
+            Ok((player, board, result)) => {
+                match search.color {
+                    Cell::Black => self.player_1 = Some(player),
+                    _ => self.player_2 = Some(player),
+                }
+                (Some(board), result)
+            }
+            Err(_) => (None, Err("The AI search thread panicked".to_string())),
+        }
+    }
+
+    /// Stops the AI search running in the background, if any.
+    ///
+    /// The search notices the cancelled token within milliseconds; the AI
+    /// then goes back to its slot and its unfinished turn is dropped. The
+    /// main loop starts a new search when the game screen is shown again.
+    /// Pondering and the overlay analysis are stopped and thrown away the
+    /// same way.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // Called when the user presses 'q' during the AI turn
+    /// app.cancel_ai_search();
+    /// assert!(app.ai_search.is_none());
+    /// ```
+    pub fn cancel_ai_search(&mut self) {
+        self.stop_pondering();
+        self.stop_analysis();
+        self.ponder_cache = None;
+        if let Some(search) = self.ai_search.take() {
+            search.cancel.cancel();
+            if let (_, Err(e)) = self.join_ai_search(search) {
+                if e != SEARCH_CANCELLED {
+                    self.set_game_message(Some(e));
+                }
+            }
+        }
+    }
+
+    /// Lets the AI ponder while the human is to move, if pondering is enabled.
+    ///
+    /// Called on every iteration of the main loop during the human's turn.
+    /// Pondering starts once per turn, in Human vs AI only: the AI moves out
+    /// of its slot and analyzes the positions after the human replies,
+    /// starting with the reply predicted by its last principal variation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// app.pondering = true;
+    /// // In the main loop, while the human is to move
+    /// app.start_pondering();
+    /// ```
+    pub fn start_pondering(&mut self) {
+        if !self.pondering || self.ponder_search.is_some() {
+            return;
+        }
+        let board = match &self.board {
+            Some(board) if !board.is_game_over() => board.clone(),
+            _ => return,
+        };
+        let color = board.get_player_turn().get_opponent();
+        let slot = match color {
+            Cell::Black => &mut self.player_1,
+            _ => &mut self.player_2,
+        };
+        let mut player = match slot.take_if(|player| !player.is_human()) {
+            Some(player) => player,
+            None => return,
+        };
+
+        // The AI expects the second move of the line of its last search
+        let expected = board
+            .get_history()
+            .last()
+            .and_then(|action| action.search_info.as_ref())
+            .and_then(|info| info.pv.get(1))
+            .and_then(|input| Board::input_to_coordinates(input));
+
+        let cancel = CancelToken::new();
+        player.set_cancel_token(cancel.clone());
+        let token = cancel.clone();
+        let handle = thread::spawn(move || {
+            let cache = ponder(player.as_ref(), &board, expected, &token);
+            (player, cache)
+        });
+        self.ponder_search = Some(PonderSearch {
+            color,
+            cancel,
+            handle,
+        });
+    }
+
+    /// Stops the pondering AI and puts it back in its slot.
+    ///
+    /// The analyses finished so far are kept for the AI turn.
+    fn stop_pondering(&mut self) {
+        if let Some(search) = self.ponder_search.take() {
+            search.cancel.cancel();
+            if let Ok((player, cache)) = search.handle.join() {
+                match search.color {
+                    Cell::Black => self.player_1 = Some(player),
+                    _ => self.player_2 = Some(player),
+                }
+                self.ponder_cache = cache.ok();
+            }
+        }
+    }
+
+    /// Stops the running Q-Learning training, if any, and waits for it to
+    /// save its Q-table.
+    ///
+    /// The training finishes its current epoch first, so this returns
+    /// within one training game.
+    pub fn stop_training(&mut self) {
+        if let Some(cancel) = self.qlearning_cancel.take() {
+            cancel.cancel();
+        }
+        if let Some(handle) = self.qlearning_handle.take() {
+            handle.join().ok();
+        }
     }
 
     /// Terminates the current game and returns to the main menu.
@@ -402,6 +844,8 @@ impl App {
     /// - **Players**: Removes both player instances
     /// - **UI state**: Clears selected cell and messages
     /// - **Timer**: Stops and removes game timer
+    /// - **AI search**: Cancels the search running in the background
+    /// - **Q-Learning training**: Stops it and waits for its table to be saved
     ///
     /// # Memory Management
     ///
@@ -432,6 +876,8 @@ impl App {
     /// assert!(app.board.is_none());
     /// ```
     pub fn quit_game(&mut self) {
+        self.cancel_ai_search();
+        self.stop_training();
         self.current_screen = CurrentScreen::Main;
         self.board = None;
         self.game_message = None;
@@ -439,6 +885,137 @@ impl App {
         self.player_2 = None;
         self.selected_cell = None;
         self.timer = None;
+        self.show_analysis = false;
+        self.analysis = None;
+    }
+
+    /// Shows or hides the scores of the candidate moves on the board.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // Called when the user presses 'a' on the game screen
+    /// app.toggle_analysis();
+    /// assert!(app.show_analysis);
+    /// ```
+    pub fn toggle_analysis(&mut self) {
+        self.show_analysis = !self.show_analysis;
+        self.refresh_analysis();
+    }
+
+    /// Starts scoring the legal moves of the current position for the overlay.
+    ///
+    /// The analysis runs on a background thread with its own `CancelToken`,
+    /// and `poll_analysis` posts its result once it is done. An analysis of
+    /// a previous position is cancelled first. The AI to move analyzes the
+    /// position when it can, so the overlay shows the scores this AI is about
+    /// to choose from; it starts its search once the analysis is done.
+    /// Otherwise an Alpha-Beta search of depth `ANALYSIS_DEPTH` scores the
+    /// moves. Nothing is computed while the overlay is hidden or once the
+    /// game is over.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// app.show_analysis = true;
+    /// app.refresh_analysis();
+    /// // In the main loop, until the analysis is done
+    /// app.poll_analysis();
+    /// ```
+    pub fn refresh_analysis(&mut self) {
+        self.stop_analysis();
+        self.analysis = None;
+        let board = match &self.board {
+            Some(board) if self.show_analysis && !board.is_game_over() => board.clone(),
+            _ => return,
+        };
+        let color = board.get_player_turn();
+        let slot = match color {
+            Cell::Black => &mut self.player_1,
+            _ => &mut self.player_2,
+        };
+        let mut player = slot.take_if(|player| !player.is_human());
+
+        let cancel = CancelToken::new();
+        let token = cancel.clone();
+        if let Some(player) = &mut player {
+            player.set_cancel_token(token.clone());
+        }
+        let handle = thread::spawn(move || {
+            let result = match &player {
+                Some(player) => player.analyze(&board),
+                None => Err("The player to move cannot analyze".to_string()),
+            };
+            let result = match result {
+                Err(e) if e != SEARCH_CANCELLED => {
+                    let mut fallback = AIAlphaBeta::new(
+                        ANALYSIS_DEPTH,
+                        HeuristicType::Mixte,
+                        color,
+                        AIHeuristicMatrix::A,
+                    );
+                    fallback.set_cancel_token(token);
+                    fallback.analyze(&board)
+                }
+                result => result,
+            };
+            (player, result)
+        });
+        self.analysis_search = Some(AnalysisSearch {
+            color,
+            cancel,
+            handle,
+        });
+    }
+
+    /// Posts the result of the overlay analysis once its thread is done.
+    ///
+    /// Called on every iteration of the main loop on the game screen. The
+    /// analyzing AI goes back to its slot, and an error is shown as the
+    /// game message.
+    pub fn poll_analysis(&mut self) {
+        match &self.analysis_search {
+            Some(search) if search.handle.is_finished() => {
+                let search = self.analysis_search.take().unwrap();
+                match self.join_analysis(search) {
+                    Ok(analysis) => self.analysis = Some(analysis),
+                    Err(e) => self.set_game_message(Some(e)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Stops the overlay analysis running in the background, if any.
+    ///
+    /// Its unfinished result is dropped and the analyzing AI goes back to
+    /// its slot.
+    fn stop_analysis(&mut self) {
+        if let Some(search) = self.analysis_search.take() {
+            search.cancel.cancel();
+            if let Err(e) = self.join_analysis(search) {
+                if e != SEARCH_CANCELLED {
+                    self.set_game_message(Some(e));
+                }
+            }
+        }
+    }
+
+    /// Waits for the analysis thread and puts the analyzing AI back in its
+    /// slot.
+    fn join_analysis(&mut self, search: AnalysisSearch) -> Result<Analysis, String> {
+        match search.handle.join() {
+            Ok((player, result)) => {
+                if player.is_some() {
+                    match search.color {
+                        Cell::Black => self.player_1 = player,
+                        _ => self.player_2 = player,
+                    }
+                }
+                result
+            }
+            Err(_) => Err("The analysis thread panicked".to_string()),
+        }
     }
 
     /// Updates the current game message displayed to the user.
@@ -483,6 +1060,28 @@ impl App {
         self.game_message = message;
     }
 
+    /// Saves the current game to the saved games file.
+    ///
+    /// The game is appended as one JSON line to `SAVED_GAMES_FILE`, with the
+    /// search statistics of every AI move. The `build-book` command reads it to
+    /// build the opening book. The result is shown in the game message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // Called when the user presses 's' on the game screen
+    /// app.save_game();
+    /// ```
+    pub fn save_game(&mut self) {
+        if let Some(board) = &self.board {
+            let message = match record::save_game(board, SAVED_GAMES_FILE) {
+                Ok(()) => format!("Game saved to {}", SAVED_GAMES_FILE),
+                Err(e) => format!("Could not save the game: {}", e),
+            };
+            self.set_game_message(Some(message));
+        }
+    }
+
     /// Handles keyboard navigation for board cell selection.
     ///
     /// This method processes arrow key input to move the cell selection
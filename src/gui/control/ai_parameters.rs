@@ -0,0 +1,398 @@
+//! Shared input handling for the AI parameter rows of the setup screens.
+//!
+//! The Human vs AI and AI vs AI setup screens show the same list of
+//! parameters for every AI player. This module defines that list and applies
+//! the left/right key presses to the player of a given color, so both
+//! screens stay consistent when new AI types or parameters are added.
+
+use std::{sync::Arc, time::Duration};
+
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    ai::{
+        ai_type::AIType,
+        algo::{
+            alphabeta::AIAlphaBeta,
+            mcts::{AIMcts, SearchBudget},
+            minmax::AIMinMax,
+            qlearning::{list_q_tables, QLearning},
+        },
+        eval_profile::list_profiles,
+        heuristic::HeuristicType,
+    },
+    consts::{
+        MAX_DEPTH, MAX_SEARCH_THREADS, MCTS_EXPLORATION_STEP, MCTS_ITERATIONS,
+        MCTS_ITERATIONS_STEP, MCTS_MAX_EXPLORATION, MCTS_MAX_ITERATIONS, MCTS_MAX_TIME_MS,
+        MCTS_TIME_STEP_MS, PROFILES_DIR, Q_TABLES_DIR,
+    },
+    game::{cell::Cell, player::Player},
+    gui::app::App,
+};
+
+/// A configurable AI setting, shown as one row on the setup screens.
+///
+/// # Examples
+///
+/// ```rust
+/// // The third row of a player block is its heuristic
+/// assert_eq!(AI_PARAMETERS[2], AIParameter::Heuristic);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AIParameter {
+    /// Algorithm used by the player
+    AIType,
+    /// Search depth (or iteration or time budget for MCTS)
+    Depth,
+    /// Heuristic evaluation function
+    Heuristic,
+    /// Heuristic matrix used by matrix-based heuristics
+    Matrix,
+    /// Evaluation profile picked among `PROFILES_DIR` (Weighted heuristic only)
+    Profile,
+    /// Second-level multithreading (MinMax only)
+    DoubleThreading,
+    /// Playout policy (MCTS only)
+    Playout,
+    /// Kind of search budget, iterations or time (MCTS only)
+    Budget,
+    /// Exploration constant of the UCT formula (MCTS only)
+    Exploration,
+    /// Opening book usage (search AIs only)
+    Book,
+    /// Number of search workers (search AIs only)
+    Threads,
+    /// Q-table file picked among `Q_TABLES_DIR` (QLearning only)
+    QTable,
+}
+
+/// Rows shown for each AI player, in display order.
+pub const AI_PARAMETERS: [AIParameter; 12] = [
+    AIParameter::AIType,
+    AIParameter::Depth,
+    AIParameter::Heuristic,
+    AIParameter::Matrix,
+    AIParameter::Profile,
+    AIParameter::DoubleThreading,
+    AIParameter::Playout,
+    AIParameter::Budget,
+    AIParameter::Exploration,
+    AIParameter::Book,
+    AIParameter::Threads,
+    AIParameter::QTable,
+];
+
+/// Returns the player slot of `app` that plays `color`.
+///
+/// Player 1 is always Black and player 2 is always White.
+pub fn player_slot(app: &mut App, color: Cell) -> &mut Option<Box<dyn Player>> {
+    match color {
+        Cell::Black => &mut app.player_1,
+        _ => &mut app.player_2,
+    }
+}
+
+/// Creates an AI player of `ai_type` that keeps the settings of `previous`.
+///
+/// Depth, heuristic, heuristic matrix, threading, opening book and worker options are
+/// carried over when the new algorithm supports them, so cycling through the
+/// AI types does not reset the configuration.
+///
+/// # Arguments
+///
+/// * `ai_type` - The algorithm of the new player
+/// * `previous` - The player being replaced
+/// * `color` - The color the new player plays as
+pub fn new_ai_player(ai_type: AIType, previous: &dyn Player, color: Cell) -> Box<dyn Player> {
+    let mut player: Box<dyn Player> = match ai_type {
+        AIType::AlphaBeta => Box::new(AIAlphaBeta::new(
+            previous.get_depth(),
+            previous.get_heuristic(),
+            color,
+            previous.get_heuristic_matrix(),
+        )),
+        AIType::MinMax => Box::new(AIMinMax::new(
+            previous.get_depth(),
+            previous.get_heuristic(),
+            color,
+            previous.get_heuristic_matrix(),
+            previous.get_double_threading(),
+        )),
+        AIType::QLearning => Box::new(QLearning::new(
+            1000,
+            previous.get_heuristic(),
+            previous.get_heuristic_matrix(),
+            10000,
+            color,
+        )),
+        AIType::Mcts => Box::new(AIMcts::new(
+            SearchBudget::Iterations(MCTS_ITERATIONS),
+            previous.get_heuristic(),
+            color,
+            previous.get_heuristic_matrix(),
+        )),
+    };
+    if let Some(mode) = previous.get_book_mode() {
+        player.set_book_mode(mode);
+    }
+    if let Some(threads) = previous.get_threads() {
+        player.set_threads(threads);
+    }
+    player
+}
+
+/// Applies a left/right key press to one AI parameter of a player.
+///
+/// `KeyCode::Right` moves to the next value and any other key moves to the
+/// previous one. Parameters that do not apply to the player's algorithm are
+/// left untouched and an explanation is shown in the game message.
+///
+/// # Arguments
+///
+/// * `app` - Mutable reference to the application state
+/// * `color` - Color of the player to configure
+/// * `parameter` - The parameter row that was changed
+/// * `key` - The key that was pressed (left or right arrow)
+pub fn ai_parameter_control(app: &mut App, color: Cell, parameter: AIParameter, key: KeyCode) {
+    let forward = key == KeyCode::Right;
+    let player = match player_slot(app, color).as_mut() {
+        Some(player) => player,
+        None => return,
+    };
+    let ai_type = player.get_ai_type().unwrap();
+    if parameter == AIParameter::QTable {
+        let message = q_table_control(player.as_mut(), forward);
+        app.set_game_message(Some(message));
+        return;
+    }
+    if parameter == AIParameter::Profile {
+        let message = if ai_type == AIType::QLearning {
+            "QLearning does not support heuristic change".to_string()
+        } else {
+            profile_control(player.as_mut(), forward)
+        };
+        app.set_game_message(Some(message));
+        return;
+    }
+
+    let message = match parameter {
+        AIParameter::AIType => {
+            let new_type = if forward {
+                ai_type.next()
+            } else {
+                ai_type.previous()
+            };
+            let new_player = new_ai_player(new_type, player.as_ref(), color);
+            *player = new_player;
+            None
+        }
+        AIParameter::Depth => match ai_type {
+            AIType::QLearning => Some("QLearning does not support depth change"),
+            AIType::Mcts => match player.get_search_budget() {
+                Some(SearchBudget::Time(time)) => {
+                    let time = time.as_millis() as u64;
+                    if forward && time + MCTS_TIME_STEP_MS > MCTS_MAX_TIME_MS {
+                        Some("Maximum time reached [see const MCTS_MAX_TIME_MS]")
+                    } else if forward {
+                        player.set_search_budget(SearchBudget::Time(Duration::from_millis(
+                            time + MCTS_TIME_STEP_MS,
+                        )));
+                        None
+                    } else {
+                        if time > MCTS_TIME_STEP_MS {
+                            player.set_search_budget(SearchBudget::Time(Duration::from_millis(
+                                time - MCTS_TIME_STEP_MS,
+                            )));
+                        }
+                        None
+                    }
+                }
+                _ => {
+                    let iterations = player.get_iterations();
+                    if forward && iterations + MCTS_ITERATIONS_STEP > MCTS_MAX_ITERATIONS {
+                        Some("Maximum iterations reached [see const MCTS_MAX_ITERATIONS]")
+                    } else if forward {
+                        player.set_iterations(iterations + MCTS_ITERATIONS_STEP);
+                        None
+                    } else {
+                        if iterations > MCTS_ITERATIONS_STEP {
+                            player.set_iterations(iterations - MCTS_ITERATIONS_STEP);
+                        }
+                        None
+                    }
+                }
+            },
+            _ => {
+                let depth = player.get_depth();
+                if forward && depth >= MAX_DEPTH {
+                    Some("Maximum depth reached [see const MAX_DEPTH]")
+                } else if forward {
+                    player.set_depth(depth + 1);
+                    None
+                } else {
+                    if depth > 1 {
+                        player.set_depth(depth - 1);
+                    }
+                    None
+                }
+            }
+        },
+        AIParameter::Heuristic => {
+            if ai_type == AIType::QLearning {
+                Some("QLearning does not support heuristic change")
+            } else {
+                let heuristic = if forward {
+                    player.get_heuristic().next()
+                } else {
+                    player.get_heuristic().previous()
+                };
+                player.set_heuristic(heuristic);
+                None
+            }
+        }
+        AIParameter::Matrix => {
+            if ai_type == AIType::QLearning {
+                Some("QLearning does not support heuristic matrix change")
+            } else if !player.get_heuristic().uses_matrix() {
+                Some("This heuristic do not support heuristic matrix change")
+            } else {
+                let matrix = if forward {
+                    player.get_heuristic_matrix().next()
+                } else {
+                    player.get_heuristic_matrix().previous()
+                };
+                player.set_heuristic_matrix(matrix);
+                None
+            }
+        }
+        AIParameter::DoubleThreading => {
+            if ai_type == AIType::MinMax {
+                let double_threading = player.get_double_threading();
+                player.set_double_threading(!double_threading);
+                None
+            } else {
+                Some("Only MinMax AI can use double threading")
+            }
+        }
+        AIParameter::Playout => match player.get_playout_policy() {
+            Some(playout) => {
+                player.set_playout_policy(playout.next());
+                None
+            }
+            None => Some("Only MCTS AI can change the playout policy"),
+        },
+        AIParameter::Budget => match player.get_search_budget() {
+            Some(budget) => {
+                player.set_search_budget(budget.other_kind());
+                None
+            }
+            None => Some("Only MCTS AI has an iteration or time budget"),
+        },
+        AIParameter::Exploration => match player.get_exploration() {
+            Some(exploration) => {
+                if forward && exploration + MCTS_EXPLORATION_STEP > MCTS_MAX_EXPLORATION {
+                    Some("Maximum exploration reached [see const MCTS_MAX_EXPLORATION]")
+                } else if forward {
+                    player.set_exploration(exploration + MCTS_EXPLORATION_STEP);
+                    None
+                } else {
+                    if exploration > MCTS_EXPLORATION_STEP {
+                        player.set_exploration(exploration - MCTS_EXPLORATION_STEP);
+                    }
+                    None
+                }
+            }
+            None => Some("Only MCTS AI has an exploration constant"),
+        },
+        AIParameter::Book => match player.get_book_mode() {
+            Some(mode) => {
+                player.set_book_mode(if forward {
+                    mode.next()
+                } else {
+                    mode.previous()
+                });
+                None
+            }
+            None => Some("QLearning does not use the opening book"),
+        },
+        AIParameter::QTable | AIParameter::Profile => None,
+        AIParameter::Threads => match player.get_threads() {
+            Some(threads) => {
+                if forward && threads >= MAX_SEARCH_THREADS {
+                    Some("Maximum threads reached [see const MAX_SEARCH_THREADS]")
+                } else if forward {
+                    player.set_threads(threads + 1);
+                    None
+                } else {
+                    if threads > 1 {
+                        player.set_threads(threads - 1);
+                    }
+                    None
+                }
+            }
+            None => Some("QLearning does not search in parallel"),
+        },
+    };
+
+    if let Some(message) = message {
+        app.set_game_message(Some(message.to_string()));
+    }
+}
+
+/// Picks the next or previous Q-table file of `Q_TABLES_DIR` for a player.
+///
+/// # Returns
+///
+/// The message to show: the picked file with its training metadata, or why
+/// no file could be picked.
+fn q_table_control(player: &mut dyn Player, forward: bool) -> String {
+    let Some(current) = player.get_q_table_file() else {
+        return "Only QLearning AI uses a Q-table".to_string();
+    };
+    let tables = list_q_tables(Q_TABLES_DIR);
+    if tables.is_empty() {
+        return format!("No Q-table in {}/, train one first", Q_TABLES_DIR);
+    }
+    let index = match tables.iter().position(|table| table.path == current) {
+        Some(index) if forward => (index + 1) % tables.len(),
+        Some(index) => (index + tables.len() - 1) % tables.len(),
+        None => 0,
+    };
+    player.set_q_table_file(tables[index].path.clone());
+    tables[index].summary()
+}
+
+/// Picks the next or previous evaluation profile of a `Weighted` player.
+///
+/// The profile is stored in the player's heuristic, so both players of a
+/// game can use different profiles. The current profile is found by its
+/// file, since two files may hold equal profiles.
+///
+/// # Returns
+///
+/// The message to show: the picked profile, or why no profile could be
+/// picked.
+fn profile_control(player: &mut dyn Player, forward: bool) -> String {
+    let HeuristicType::Weighted(current) = player.get_heuristic() else {
+        return "Only the Weighted heuristic uses an evaluation profile".to_string();
+    };
+    let profiles = list_profiles(PROFILES_DIR);
+    if profiles.len() == 1 {
+        return format!(
+            "No evaluation profile in {}/, write one with write-profile",
+            PROFILES_DIR
+        );
+    }
+    let index = match profiles
+        .iter()
+        .position(|file| Arc::ptr_eq(&file.profile, &current))
+    {
+        Some(index) if forward => (index + 1) % profiles.len(),
+        Some(index) => (index + profiles.len() - 1) % profiles.len(),
+        None => 0,
+    };
+    let file = &profiles[index];
+    let message = format!("Evaluation profile: {} ({})", file.profile.name, file.path);
+    player.set_heuristic(HeuristicType::Weighted(file.profile.clone()));
+    message
+}
@@ -27,6 +27,11 @@ use crate::gui::app::{App, CurrentScreen};
 /// * `q` - Quit to exit confirmation screen
 /// * `t` - Open tutorial screen
 ///
+/// Leaving the game screen with `q` or `t` cancels the AI search running in
+/// the background; it starts again when the game screen is shown again.
+/// * `s` - Save the game to the saved games file
+/// * `a` - Show or hide the scores of the candidate moves
+///
 /// ## Human Player Controls (Only when `its_a_human_player` is true and game not over)
 /// * `Up/Down/Left/Right` - Navigate cell selection on the board
 /// * `Enter` - Execute move at selected cell position
@@ -34,7 +39,7 @@ use crate::gui::app::{App, CurrentScreen};
 /// # Behavior
 ///
 /// The function implements different behavior based on context:
-/// - **Game Over**: Only general navigation commands (q, t) are processed
+/// - **Game Over**: Only general navigation commands (q, t, s, a) are processed
 /// - **AI Turn**: Only general commands work, movement/selection is disabled
 /// - **Human Turn**: Full control including cell selection and move execution
 /// - **Move Execution**: Only processes Enter if a cell is currently selected
@@ -62,7 +67,8 @@ use crate::gui::app::{App, CurrentScreen};
 pub fn game_control(app: &mut App, key: KeyEvent, its_a_human_player: bool) {
     match key.code {
         KeyCode::Char('q') => {
-            // Quit - go to exit confirmation
+            // Quit - stop the AI search and go to exit confirmation
+            app.cancel_ai_search();
             app.current_screen = CurrentScreen::Exit;
             app.previous_screen = Some(CurrentScreen::Game);
         }
@@ -98,8 +104,17 @@ pub fn game_control(app: &mut App, key: KeyEvent, its_a_human_player: bool) {
                 }
             }
         }
+        KeyCode::Char('s') => {
+            // Save the game for the opening book builder
+            app.save_game();
+        }
+        KeyCode::Char('a') => {
+            // Analysis overlay - score every candidate move
+            app.toggle_analysis();
+        }
         KeyCode::Char('t') => {
-            // Tutorial - open help screen
+            // Tutorial - stop the AI search and open help screen
+            app.cancel_ai_search();
             app.current_screen = CurrentScreen::Tutorial;
             app.previous_screen = Some(CurrentScreen::Game);
         }
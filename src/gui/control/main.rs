@@ -15,7 +15,7 @@ use crate::{
     },
     consts::MAX_DEPTH,
     game::cell::Cell,
-    gui::app::{App, CurrentScreen},
+    gui::app::{App, CurrentScreen, MatrixEditor},
     human::Human,
 };
 
@@ -41,6 +41,7 @@ use crate::{
 /// * **Option 1**: Human vs AI - Creates human player 1 and AI player 2, goes to configuration screen
 /// * **Option 2**: AI vs AI - Creates two AI players with default settings, goes to configuration screen
 /// * **Option 3**: Q-Learning Training - Sets up Q-Learning parameters and goes to training configuration
+/// * **Option 4**: Matrix Editor - Opens matrix A in the heuristic matrix editor
 ///
 /// # Player Initialization
 ///
@@ -114,6 +115,12 @@ pub fn main_control(app: &mut App, key: KeyEvent) {
                 ));
                 app.current_mode.select_first();
             }
+            Some(4) => {
+                // Matrix Editor - start on matrix A, top-left square
+                app.matrix_editor = Some(MatrixEditor::new(AIHeuristicMatrix::A));
+                app.selected_cell = Some((0, 0));
+                app.current_screen = CurrentScreen::MatrixEditor;
+            }
             _ => {}
         },
         _ => {}
@@ -0,0 +1,51 @@
+//! Input control handler for the heuristic matrix editor.
+//!
+//! This module provides keyboard input handling for the matrix editor:
+//! moving over the squares, adjusting their weights, switching between
+//! matrices and saving the edited one.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use crate::gui::app::{App, CurrentScreen, MatrixEditor};
+
+/// Handles keyboard input for the matrix editor screen.
+///
+/// # Arguments
+///
+/// * `app` - Mutable reference to the application state
+/// * `key` - The keyboard event to process
+///
+/// # Key Bindings
+///
+/// * `Arrow keys` - Move over the squares
+/// * `+/-` - Add or remove 1 to the selected weight and its symmetric squares
+/// * `>/<` - Add or remove 10
+/// * `Tab/BackTab` - Open the next or previous matrix, dropping unsaved edits
+/// * `s` - Save the matrix (edits of A and B are saved as a new matrix)
+/// * `q` - Return to the main menu
+pub fn matrix_editor_control(app: &mut App, key: KeyEvent) {
+    let pos = app.selected_cell.unwrap_or((0, 0));
+    let Some(editor) = app.matrix_editor.as_mut() else {
+        app.current_screen = CurrentScreen::Main;
+        return;
+    };
+    match key.code {
+        KeyCode::Char('q') => {
+            app.matrix_editor = None;
+            app.selected_cell = None;
+            app.current_mode.select_first();
+            app.current_screen = CurrentScreen::Main;
+        }
+        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+            app.select_cell_key(key.code)
+        }
+        KeyCode::Char('+') => editor.adjust(pos, 1),
+        KeyCode::Char('-') => editor.adjust(pos, -1),
+        KeyCode::Char('>') => editor.adjust(pos, 10),
+        KeyCode::Char('<') => editor.adjust(pos, -10),
+        KeyCode::Tab => *editor = MatrixEditor::new(editor.matrix.next()),
+        KeyCode::BackTab => *editor = MatrixEditor::new(editor.matrix.previous()),
+        KeyCode::Char('s') => editor.save(),
+        _ => {}
+    }
+}
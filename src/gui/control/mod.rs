@@ -1,8 +1,10 @@
+pub mod ai_parameters;
 pub mod ai_vs_ai;
 pub mod exit;
 pub mod game;
 pub mod human_vs_ai;
 pub mod main;
+pub mod matrix_editor;
 pub mod q_learning;
 pub mod q_learning_loading;
 pub mod tutorial;
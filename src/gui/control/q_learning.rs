@@ -5,13 +5,16 @@
 //! such as epochs, max steps, heuristics, and matrix settings before
 //! initiating the training process.
 
-use std::sync::mpsc;
+use std::{path::Path, sync::mpsc};
 
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 
 use crate::{
-    ai::heuristic::HeuristicType,
-    consts::QLEARNING_MAX_EPOCHS,
+    ai::{
+        algo::qlearning::{q_table_file_for, EpsilonSchedule, UpdateRule},
+        cancel::CancelToken,
+    },
+    consts::{MAX_DEPTH, QLEARNING_MAX_EPOCHS},
     game::player::Player,
     gui::app::{App, CurrentScreen},
 };
@@ -30,19 +33,33 @@ use crate::{
 ///
 /// # Key Bindings
 ///
-/// * `q` - Return to main menu
+/// * `q` - Return to main menu (typed in the name on the output name row)
+/// * `Backspace` - Erase the last character of the output name
 /// * `Up/Down` - Navigate between parameter options
 /// * `Left/Right` - Adjust selected parameter values
 /// * `Enter` - Start training (when "Start Training" is selected)
 ///
 /// # Configuration Parameters
 ///
-/// The function manages 5 different configuration options (0-4):
+/// The function manages 18 different configuration options (0-17):
 /// * **Option 0**: Training epochs (500-QLEARNING_MAX_EPOCHS, step: 500)
 /// * **Option 1**: Max steps per episode (1-64, step: 1)
 /// * **Option 2**: Heuristic type (cycles through available types)
 /// * **Option 3**: Heuristic matrix (when supported by heuristic)
-/// * **Option 4**: Start training option
+/// * **Option 4**: Learning rate (0.05-1.0, step: 0.05)
+/// * **Option 5**: Discount factor (0.0-1.0, step: 0.01)
+/// * **Option 6**: Initial epsilon (epsilon floor-1.0, step: 0.05)
+/// * **Option 7**: Epsilon floor (0.0-initial epsilon, step: 0.01)
+/// * **Option 8**: Epsilon schedule (cycles Linear, Exponential, Step)
+/// * **Option 9**: Epsilon decay (0.99-0.9995, step: 0.0005, exponential schedule only)
+/// * **Option 10**: Training opponent (cycles Self-play, Random, Alpha-Beta, Min-Max, Snapshot)
+/// * **Option 11**: Opponent depth (1-MAX_DEPTH, Alpha-Beta and Min-Max opponents only)
+/// * **Option 12**: Update rule (cycles Q-learning, SARSA, Expected SARSA, Double Q-learning, TD(λ))
+/// * **Option 13**: Trace decay (0.0-1.0, step: 0.05, TD(λ) only)
+/// * **Option 14**: Colour trained for (Black or White)
+/// * **Option 15**: Output name of the Q-table in `Q_TABLES_DIR`, typed with letters, digits, `_` and `-`
+/// * **Option 16**: Start training option
+/// * **Option 17**: Continue training option, resuming the table of the output name
 ///
 /// # Parameter Constraints
 ///
@@ -51,12 +68,14 @@ use crate::{
 /// - **Max Steps**: Range from 1 to 64 steps per training episode
 /// - **Heuristics**: Some types (Absolute, Mobility) don't support matrix changes
 /// - **Matrix**: Only available for compatible heuristic types
+/// - **Epsilon**: The initial epsilon never goes below the floor
 ///
 /// # Training Initialization
 ///
-/// When training starts (Option 4 + Enter):
+/// When training starts (Option 16 + Enter) or continues (Option 17 + Enter):
 /// - Creates a communication channel for progress updates
-/// - Spawns a background thread for training execution
+/// - Spawns a background thread for training execution, stopped through
+///   a cancel token kept by the app
 /// - Transitions to the loading screen with progress display
 /// - Preserves parameter configuration for potential restarts
 ///
@@ -71,7 +90,21 @@ use crate::{
 /// q_learning_parameters_control(&mut app, key_event); // Start training
 /// ```
 pub fn q_learning_parameters_control(app: &mut App, key: KeyEvent) {
+    let on_output_name = app.current_mode.selected() == Some(15);
     match key.code {
+        KeyCode::Char(c)
+            if on_output_name && (c.is_ascii_alphanumeric() || c == '_' || c == '-') =>
+        {
+            edit_output_name(app, |name| name.push(c));
+        }
+        KeyCode::Backspace if on_output_name => {
+            edit_output_name(app, |name| {
+                // Keep at least one character so the file keeps a name
+                if name.len() > 1 {
+                    name.pop();
+                }
+            });
+        }
         KeyCode::Char('q') => {
             app.current_mode.select_first();
             app.game_message = None;
@@ -120,10 +153,12 @@ pub fn q_learning_parameters_control(app: &mut App, key: KeyEvent) {
             }
             Some(3) => {
                 // Heuristic Matrix - cycle to previous
-                if app.qlearning_parameters.as_ref().unwrap().get_heuristic()
-                    == HeuristicType::Absolute
-                    || app.qlearning_parameters.as_ref().unwrap().get_heuristic()
-                        == HeuristicType::Mobility
+                if !app
+                    .qlearning_parameters
+                    .as_ref()
+                    .unwrap()
+                    .get_heuristic()
+                    .uses_matrix()
                 {
                     app.set_game_message(Some(
                         "This heuristic do not support heuristic matrix change".to_string(),
@@ -141,6 +176,8 @@ pub fn q_learning_parameters_control(app: &mut App, key: KeyEvent) {
                         .set_heuristic_matrix(previous_matrix);
                 }
             }
+            Some(option @ 4..=13) => step_hyperparameter(app, option, false),
+            Some(14) => switch_color(app),
             _ => {}
         },
         KeyCode::Right => match app.current_mode.selected() {
@@ -180,10 +217,12 @@ pub fn q_learning_parameters_control(app: &mut App, key: KeyEvent) {
             }
             Some(3) => {
                 // Heuristic Matrix - cycle to next
-                if app.qlearning_parameters.as_ref().unwrap().get_heuristic()
-                    == HeuristicType::Absolute
-                    || app.qlearning_parameters.as_ref().unwrap().get_heuristic()
-                        == HeuristicType::Mobility
+                if !app
+                    .qlearning_parameters
+                    .as_ref()
+                    .unwrap()
+                    .get_heuristic()
+                    .uses_matrix()
                 {
                     app.set_game_message(Some(
                         "This heuristic do not support heuristic matrix change".to_string(),
@@ -201,22 +240,137 @@ pub fn q_learning_parameters_control(app: &mut App, key: KeyEvent) {
                         .set_heuristic_matrix(next_matrix);
                 }
             }
+            Some(option @ 4..=13) => step_hyperparameter(app, option, true),
+            Some(14) => switch_color(app),
             _ => {}
         },
         KeyCode::Enter => match app.current_mode.selected() {
-            Some(4) => {
+            Some(16) => {
                 // Start Training - begin Q-Learning process
-                app.current_screen = CurrentScreen::QLearningLoading;
-                app.previous_screen = Some(CurrentScreen::QLearningParameters);
-                let (tx, rx) = mpsc::channel();
-                let mut qlearning_params = app.qlearning_parameters.take().unwrap();
-                std::thread::spawn(move || {
-                    qlearning_params.try_q_learning(tx);
-                });
-                app.qlearning_channel = Some(rx);
+                start_training(app);
+            }
+            Some(17) => {
+                // Continue Training - resume the table of the output name
+                match app.qlearning_parameters.as_mut().unwrap().resume_training() {
+                    Ok(()) => start_training(app),
+                    Err(e) => app.set_game_message(Some(e)),
+                }
             }
             _ => {}
         },
         _ => return,
     }
 }
+
+/// Steps one of the training hyperparameters (options 4 to 13) up or down.
+///
+/// # Arguments
+///
+/// * `app` - Mutable reference to the application state
+/// * `option` - Index of the selected option on the screen
+/// * `up` - Whether to increase (Right) or decrease (Left) the value
+fn step_hyperparameter(app: &mut App, option: usize, up: bool) {
+    let qlearning = app.qlearning_parameters.as_mut().unwrap();
+    let mut params = qlearning.get_hyperparameters().clone();
+    let sign = if up { 1.0 } else { -1.0 };
+    match option {
+        4 => params.learning_rate = step(params.learning_rate, 0.05 * sign, 0.05, 1.0),
+        5 => params.discount = step(params.discount, 0.01 * sign, 0.0, 1.0),
+        6 => params.epsilon = step(params.epsilon, 0.05 * sign, params.epsilon_floor, 1.0),
+        7 => params.epsilon_floor = step(params.epsilon_floor, 0.01 * sign, 0.0, params.epsilon),
+        8 => {
+            params.epsilon_schedule = if up {
+                params.epsilon_schedule.next()
+            } else {
+                params.epsilon_schedule.previous()
+            }
+        }
+        9 => {
+            if params.epsilon_schedule != EpsilonSchedule::Exponential {
+                app.set_game_message(Some(
+                    "The epsilon decay is only used by the exponential schedule".to_string(),
+                ));
+                return;
+            }
+            params.epsilon_decay = step(params.epsilon_decay, 0.0005 * sign, 0.99, 0.9995);
+        }
+        10 => {
+            params.opponent = if up {
+                params.opponent.next()
+            } else {
+                params.opponent.previous()
+            }
+        }
+        11 => {
+            if !params.opponent.uses_depth() {
+                app.set_game_message(Some(
+                    "Only the Alpha-Beta and Min-Max opponents use a depth".to_string(),
+                ));
+                return;
+            }
+            params.opponent_depth = if up {
+                (params.opponent_depth + 1).min(MAX_DEPTH)
+            } else {
+                params.opponent_depth.saturating_sub(1).max(1)
+            };
+        }
+        12 => {
+            params.update_rule = if up {
+                params.update_rule.next()
+            } else {
+                params.update_rule.previous()
+            }
+        }
+        13 => {
+            if params.update_rule != UpdateRule::TdLambda {
+                app.set_game_message(Some(
+                    "The trace decay is only used by the TD(λ) rule".to_string(),
+                ));
+                return;
+            }
+            params.trace_decay = step(params.trace_decay, 0.05 * sign, 0.0, 1.0);
+        }
+        _ => return,
+    }
+    qlearning.set_hyperparameters(params);
+}
+
+/// Adds `delta` to `value`, rounded to remove floating point drift and
+/// clamped to `[min, max]`.
+fn step(value: f64, delta: f64, min: f64, max: f64) -> f64 {
+    (((value + delta) * 10000.0).round() / 10000.0).clamp(min, max)
+}
+
+/// Switches the colour the AI trains for.
+fn switch_color(app: &mut App) {
+    let qlearning = app.qlearning_parameters.as_mut().unwrap();
+    qlearning.set_color(qlearning.get_color().get_opponent());
+}
+
+/// Applies `edit` to the name of the Q-table file written by the training.
+fn edit_output_name(app: &mut App, edit: impl FnOnce(&mut String)) {
+    let qlearning = app.qlearning_parameters.as_mut().unwrap();
+    let file = qlearning.get_q_table_file().unwrap();
+    let mut name = Path::new(&file)
+        .file_stem()
+        .map_or(String::new(), |stem| stem.to_string_lossy().into_owned());
+    edit(&mut name);
+    qlearning.set_q_table_file(q_table_file_for(&name));
+}
+
+/// Spawns the training of the configured AI and shows its progress.
+fn start_training(app: &mut App) {
+    app.current_screen = CurrentScreen::QLearningLoading;
+    app.previous_screen = Some(CurrentScreen::QLearningParameters);
+    let (tx, rx) = mpsc::channel();
+    let cancel = CancelToken::new();
+    let mut qlearning_params = app.qlearning_parameters.take().unwrap();
+    qlearning_params.set_cancel_token(cancel.clone());
+    app.qlearning_output = qlearning_params.get_q_table_file();
+    app.qlearning_metrics.clear();
+    app.qlearning_cancel = Some(cancel);
+    app.qlearning_handle = Some(std::thread::spawn(move || {
+        qlearning_params.try_q_learning(tx);
+    }));
+    app.qlearning_channel = Some(rx);
+}
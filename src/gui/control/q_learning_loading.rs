@@ -21,15 +21,17 @@ use crate::gui::app::{App, CurrentScreen};
 ///
 /// # Key Bindings
 ///
-/// * `q` - Exit to confirmation screen (stops training)
+/// * `q` - Exit to confirmation screen (stops training and saves the table)
+/// * `s` - Stop training after the current epoch and save the table
 /// * Other keys - Ignored (no action)
 ///
 /// # Behavior
 ///
 /// During Q-Learning training, most interactions are disabled to prevent
-/// interference with the training process. The only available action is
-/// to request exit, which leads to a confirmation dialog that will
-/// terminate the training if confirmed.
+/// interference with the training process. Training can be stopped, which
+/// saves the table so it can be continued later, or the user can request
+/// exit, which leads to a confirmation dialog that stops the training and
+/// waits for its save if confirmed.
 ///
 /// # Training State
 ///
@@ -47,6 +49,12 @@ use crate::gui::app::{App, CurrentScreen};
 /// ```
 pub fn q_learning_loading_control(app: &mut App, key: KeyEvent) {
     match key.code {
+        KeyCode::Char('s') => {
+            // Stop - the training saves its table, and can be continued later
+            if let Some(cancel) = &app.qlearning_cancel {
+                cancel.cancel();
+            }
+        }
         KeyCode::Char('q') => {
             // Exit - go to confirmation (stops training)
             app.current_screen = CurrentScreen::Exit;
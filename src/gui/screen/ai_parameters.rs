@@ -0,0 +1,123 @@
+//! Shared rendering of the AI parameter rows of the setup screens.
+//!
+//! This module turns the parameters of an AI player into list rows for the
+//! Human vs AI and AI vs AI setup screens. Rows that do not apply to the
+//! player's algorithm or heuristic are grayed out.
+
+use std::path::Path;
+
+use ratatui::{
+    style::{Color, Style, Stylize},
+    text::Span,
+};
+
+use crate::{
+    ai::{ai_type::AIType, algo::mcts::SearchBudget, heuristic::HeuristicType},
+    game::player::Player,
+    gui::control::ai_parameters::{AIParameter, AI_PARAMETERS},
+};
+
+/// Builds the setup rows for one AI player.
+///
+/// Each row shows a label on the left and the current value between angle
+/// brackets on the right, following the layout used by every setup screen.
+///
+/// # Arguments
+///
+/// * `player` - The AI player whose parameters are displayed
+/// * `prefix` - Text put before each label (e.g. "1 - " in AI vs AI)
+///
+/// # Returns
+///
+/// One `Span` per entry of `AI_PARAMETERS`, in the same order.
+pub fn ai_parameter_items(player: &dyn Player, prefix: &str) -> Vec<Span<'static>> {
+    let ai_type = player.get_ai_type().unwrap();
+    let is_qlearning = ai_type == AIType::QLearning;
+    let heuristic_dont_use_matrix = !player.get_heuristic().uses_matrix();
+
+    AI_PARAMETERS
+        .iter()
+        .map(|parameter| {
+            let (label, value, disabled) = match parameter {
+                AIParameter::AIType => ("AI Type", ai_type.to_string(), false),
+                AIParameter::Depth => match player.get_search_budget() {
+                    Some(SearchBudget::Time(time)) => {
+                        ("Time (ms)", time.as_millis().to_string(), false)
+                    }
+                    Some(SearchBudget::Iterations(iterations)) => {
+                        ("Iterations", iterations.to_string(), false)
+                    }
+                    None => (
+                        "Depth of tree",
+                        player.get_depth().to_string(),
+                        is_qlearning,
+                    ),
+                },
+                AIParameter::Heuristic => (
+                    "Heuristic Type",
+                    player.get_heuristic().to_string(),
+                    is_qlearning,
+                ),
+                AIParameter::Matrix => (
+                    "Matrix Heuristic",
+                    player.get_heuristic_matrix().to_string(),
+                    is_qlearning || heuristic_dont_use_matrix,
+                ),
+                AIParameter::Profile => match player.get_heuristic() {
+                    HeuristicType::Weighted(profile) => {
+                        ("Eval Profile", profile.name.clone(), is_qlearning)
+                    }
+                    _ => ("Eval Profile", "-".to_string(), true),
+                },
+                AIParameter::DoubleThreading => (
+                    "Double Threading",
+                    player.get_double_threading().to_string(),
+                    ai_type != AIType::MinMax,
+                ),
+                AIParameter::Playout => match player.get_playout_policy() {
+                    Some(playout) => ("Playouts", playout.to_string(), false),
+                    None => ("Playouts", "-".to_string(), true),
+                },
+                AIParameter::Budget => match player.get_search_budget() {
+                    Some(budget) => ("Search Budget", budget.kind().to_string(), false),
+                    None => ("Search Budget", "-".to_string(), true),
+                },
+                AIParameter::Exploration => match player.get_exploration() {
+                    Some(exploration) => {
+                        ("Exploration (UCT)", format!("{:.2}", exploration), false)
+                    }
+                    None => ("Exploration (UCT)", "-".to_string(), true),
+                },
+                AIParameter::Book => match player.get_book_mode() {
+                    Some(mode) => ("Opening Book", mode.to_string(), false),
+                    None => ("Opening Book", "-".to_string(), true),
+                },
+                AIParameter::Threads => match player.get_threads() {
+                    Some(threads) => ("Threads", threads.to_string(), false),
+                    None => ("Threads", "-".to_string(), true),
+                },
+                AIParameter::QTable => match player.get_q_table_file() {
+                    Some(file) => (
+                        "Q-table",
+                        Path::new(&file)
+                            .file_stem()
+                            .map_or(file.clone(), |stem| stem.to_string_lossy().into_owned()),
+                        false,
+                    ),
+                    None => ("Q-table", "-".to_string(), true),
+                },
+            };
+
+            let span = Span::from(format!(
+                "{:<30}{:>20}",
+                format!("{}{}", prefix, label),
+                format!("< {} >", value)
+            ));
+            if disabled {
+                span.fg(Color::DarkGray)
+            } else {
+                span.style(Style::default())
+            }
+        })
+        .collect()
+}
@@ -12,12 +12,10 @@ use ratatui::{
     Frame,
 };
 
-use crate::{
-    ai::{ai_type::AIType, heuristic::HeuristicType},
-    gui::{
-        app::App,
-        ui::{centered_rect, footer, widget_title},
-    },
+use crate::gui::{
+    app::App,
+    screen::ai_parameters::ai_parameter_items,
+    ui::{centered_rect, footer, widget_title},
 };
 
 /// Renders the AI vs AI configuration screen.
@@ -38,11 +36,17 @@ use crate::{
 /// # AI Configuration Options
 ///
 /// For each AI player, the following parameters can be configured:
-/// - **AI Type**: Algorithm selection (MinMax, Alpha-Beta, Q-Learning)
-/// - **Search Depth**: How many moves ahead to analyze (tree-based algorithms)
+/// - **AI Type**: Algorithm selection (MinMax, Alpha-Beta, Q-Learning, MCTS)
+/// - **Search Depth**: How many moves ahead to analyze (playouts or milliseconds per move for MCTS)
 /// - **Heuristic Type**: Evaluation function for position assessment
 /// - **Heuristic Matrix**: Strategic focus matrix for evaluation
+/// - **Eval Profile**: Evaluation profile of the Weighted heuristic
 /// - **Multi-threading**: Performance optimization for compatible algorithms
+/// - **Playouts**: Random or heuristic-guided simulations (MCTS only)
+/// - **Search Budget**: Iterations or time per move (MCTS only)
+/// - **Exploration**: Constant C of the UCT formula (MCTS only)
+/// - **Opening Book**: Off, best book move or random good book move
+/// - **Threads**: Number of search workers (search AIs only)
 ///
 /// # Visual Feedback
 ///
@@ -60,7 +64,7 @@ use crate::{
 /// - Options are dynamically enabled/disabled based on AI type compatibility
 /// - Q-Learning AIs have depth and heuristic options disabled
 /// - Multi-threading only available for MinMax algorithm
-/// - Matrix heuristics disabled for Absolute and Mobility heuristic types
+/// - Matrix heuristics disabled for heuristic types that do not read the matrix
 ///
 /// # Examples
 ///
@@ -85,132 +89,11 @@ pub fn ai_vs_ai_screen(frame: &mut Frame, app: &mut App) {
 
     widget_title(frame, app, chunks[0]);
 
-    let is_qlearning_1 = app.player_1.as_ref().unwrap().get_ai_type().unwrap() == AIType::QLearning;
-    let is_qlearning_2 = app.player_2.as_ref().unwrap().get_ai_type().unwrap() == AIType::QLearning;
-    let heuristic_dont_use_matrix_1 = app.player_1.as_ref().unwrap().get_heuristic()
-        == HeuristicType::Absolute
-        || app.player_1.as_ref().unwrap().get_heuristic() == HeuristicType::Mobility;
-    let heuristic_dont_use_matrix_2 = app.player_2.as_ref().unwrap().get_heuristic()
-        == HeuristicType::Absolute
-        || app.player_2.as_ref().unwrap().get_heuristic() == HeuristicType::Mobility;
-    let can_use_double_threading_1 =
-        app.player_1.as_ref().unwrap().get_ai_type().unwrap() == AIType::MinMax;
-    let can_use_double_threading_2 =
-        app.player_2.as_ref().unwrap().get_ai_type().unwrap() == AIType::MinMax;
+    let mut items = ai_parameter_items(app.player_1.as_deref().unwrap(), "1 - ");
+    items.extend(ai_parameter_items(app.player_2.as_deref().unwrap(), "2 - "));
+    items.push(Span::from(format!("{:<50}", "Play")));
 
-    let items = [
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "1 - AI Type",
-            format!(
-                "< {} >",
-                app.player_1.as_ref().unwrap().get_ai_type().unwrap()
-            )
-        )),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "1 - Depth of tree",
-            format!("< {} >", app.player_1.as_ref().unwrap().get_depth())
-        ))
-        .style(if is_qlearning_1 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "1 - Heuristic Type",
-            format!("< {} >", app.player_1.as_ref().unwrap().get_heuristic())
-        ))
-        .style(if is_qlearning_1 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "1 - Matrix Heuristic",
-            format!(
-                "< {} >",
-                app.player_1.as_ref().unwrap().get_heuristic_matrix()
-            )
-        ))
-        .style(if is_qlearning_1 || heuristic_dont_use_matrix_1 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "1 - Double Threading",
-            format!(
-                "< {} >",
-                app.player_1.as_ref().unwrap().get_double_threading()
-            )
-        ))
-        .style(if !can_use_double_threading_1 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "2 - AI Type",
-            format!(
-                "< {} >",
-                app.player_2.as_ref().unwrap().get_ai_type().unwrap()
-            )
-        )),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "2 - Depth of tree",
-            format!("< {} >", app.player_2.as_ref().unwrap().get_depth())
-        ))
-        .style(if is_qlearning_2 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "2 - Heuristic Type",
-            format!("< {} >", app.player_2.as_ref().unwrap().get_heuristic())
-        ))
-        .style(if is_qlearning_2 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "2 - Matrix Heuristic",
-            format!(
-                "< {} >",
-                app.player_2.as_ref().unwrap().get_heuristic_matrix()
-            )
-        ))
-        .style(if is_qlearning_2 || heuristic_dont_use_matrix_2 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "2 - Double Threading",
-            format!(
-                "< {} >",
-                app.player_2.as_ref().unwrap().get_double_threading()
-            )
-        ))
-        .style(if !can_use_double_threading_2 {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!("{:<50}", "Play")),
-    ];
-
-    let layout = centered_rect(60, 15, chunks[1]);
+    let layout = centered_rect(60, items.len() as u16 + 4, chunks[1]);
 
     let list = List::new(items)
         .block(
@@ -15,7 +15,7 @@ use ratatui::{
 use tui_big_text::{BigText, PixelSize};
 
 use crate::{
-    consts::SIZE,
+    consts::{SIZE, SPINNER_FRAMES},
     game::cell::Cell,
     gui::{app::App, ui::footer},
 };
@@ -31,7 +31,7 @@ use crate::{
 ///
 /// The game screen uses a sophisticated multi-panel layout:
 /// - **Main horizontal split**: Game board (left) and information panel (right)
-/// - **Right panel sections**: History, timer, messages, and player scores
+/// - **Right panel sections**: History, search statistics, timer, messages, and player scores
 /// - **Footer area**: Navigation and control instructions
 ///
 /// # UI Components
@@ -39,6 +39,7 @@ use crate::{
 /// The screen integrates several specialized widgets:
 /// - **Game board**: Interactive 8x8 grid showing disc positions and selection
 /// - **Move history**: Scrollable list of all moves played with details
+/// - **Search panel**: Statistics of the last AI search (nodes, speed, depth, line)
 /// - **Timer display**: Real-time game duration tracking
 /// - **Message area**: Current game status and notifications
 /// - **Score displays**: Large, prominent score counters for both players
@@ -65,6 +66,8 @@ use crate::{
 /// - **Arrow keys**: Navigate board cell selection
 /// - **Enter**: Place disc at selected position
 /// - **'t'**: Access tutorial overlay
+/// - **'s'**: Save the game
+/// - **'a'**: Show the scores of the candidate moves on the board
 /// - **'q'**: Quit current game
 ///
 /// # Arguments
@@ -76,7 +79,7 @@ use crate::{
 ///
 /// The function uses complex constraint-based layouts:
 /// - Main area: Minimum 66 units for board, 40% for information panel
-/// - Right panel: Flexible history, fixed timer/message areas, 30% for scores
+/// - Right panel: Flexible history, fixed search/timer/message areas, 30% for scores
 /// - Score area: Equal 50% split for both player score displays
 ///
 /// # Error Handling
@@ -114,6 +117,7 @@ pub fn game_screen(frame: &mut Frame, app: &mut App) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Fill(1),
+            Constraint::Length(if app.pondering { 8 } else { 7 }),
             Constraint::Length(5),
             Constraint::Length(5),
             Constraint::Percentage(30),
@@ -124,15 +128,17 @@ pub fn game_screen(frame: &mut Frame, app: &mut App) {
     let score_area = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(right_area[3]);
+        .split(right_area[4]);
 
     widget_grid(frame, app, main_area[0]);
 
     widget_history(frame, app, right_area[0]);
 
-    widget_timer(frame, app, right_area[1]);
+    widget_search(frame, app, right_area[1]);
 
-    widget_message(frame, app, right_area[2]);
+    widget_timer(frame, app, right_area[2]);
+
+    widget_message(frame, app, right_area[3]);
 
     // Récupérer les scores des joueurs
     let mut black_score = String::new();
@@ -159,7 +165,7 @@ pub fn game_screen(frame: &mut Frame, app: &mut App) {
         frame,
         app,
         chunks[1],
-        " (↑↓←→) to choose / (ENTER) to play / (t) for tutorial / (q) to quit ",
+        " (↑↓←→) to choose / (ENTER) to play / (s) to save / (a) for analysis / (t) for tutorial / (q) to quit ",
     );
 }
 
@@ -178,6 +184,8 @@ pub fn game_screen(frame: &mut Frame, app: &mut App) {
 /// - **Disc positions**: Color-coded cells showing black (blue) and white (yellow) discs
 /// - **Empty cells**: Bordered cells indicating available board positions
 /// - **Selection highlight**: Double-bordered cell showing current cursor position
+/// - **Analysis overlay**: When enabled, the score of every candidate move on
+///   its square, the best move highlighted in green
 ///
 /// # Visual Layout
 ///
@@ -245,7 +253,11 @@ fn widget_grid(frame: &mut Frame, app: &mut App, area: Rect) {
     // Zone de jeu
     let game_board = Block::bordered()
         .border_type(BorderType::Rounded)
-        .title(" Game Board ")
+        .title(if app.show_analysis {
+            " Game Board (analysis) "
+        } else {
+            " Game Board "
+        })
         .title_alignment(Alignment::Center)
         .padding(Padding::uniform(0));
 
@@ -313,7 +325,32 @@ fn widget_grid(frame: &mut Frame, app: &mut App, area: Rect) {
                             );
                         }
                         _ => {
-                            frame.render_widget(Block::bordered().style(Style::default()), cell);
+                            let pos = (i / 9 - 1, i % 9 - 1);
+                            let analysis = app.analysis.as_ref();
+                            match analysis.and_then(|analysis| analysis.score_of(pos)) {
+                                Some(score) => {
+                                    // Candidate move: show its score, best move in green
+                                    let is_best = analysis
+                                        .and_then(|analysis| analysis.best())
+                                        .is_some_and(|best| best.action.pos == pos);
+                                    frame.render_widget(
+                                        Paragraph::new(score.to_string())
+                                            .alignment(Alignment::Center)
+                                            .style(if is_best {
+                                                Style::default().bg(Color::Green).fg(Color::Black)
+                                            } else {
+                                                Style::default().fg(Color::Gray).reversed()
+                                            }),
+                                        cell,
+                                    );
+                                }
+                                None => {
+                                    frame.render_widget(
+                                        Block::bordered().style(Style::default()),
+                                        cell,
+                                    );
+                                }
+                            }
                         }
                     }
                     if app.selected_cell == Some((i / 9 - 1, i % 9 - 1)) {
@@ -355,6 +392,9 @@ fn widget_grid(frame: &mut Frame, app: &mut App, area: Rect) {
 ///
 /// # Message Source
 ///
+/// While an AI searches in the background, a spinner with the thinking
+/// time replaces the message.
+///
 /// Messages are retrieved from `app.game_message`:
 /// - **Dynamic content**: Updates based on game events and user actions
 /// - **Optional handling**: Gracefully handles None values with fallback
@@ -373,8 +413,21 @@ fn widget_grid(frame: &mut Frame, app: &mut App, area: Rect) {
 /// widget_message(&mut frame, &app, message_area);
 /// ```
 fn widget_message(frame: &mut Frame, app: &App, area: Rect) {
-    // Zone message de jeu
-    let game_message = Paragraph::new(app.game_message.clone().unwrap_or("No message".into()))
+    // Zone message de jeu, remplacée par un spinner pendant la réflexion de l'IA
+    let message = match &app.ai_search {
+        Some(search) => {
+            let elapsed = search.started.elapsed();
+            let frame_index = (elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len();
+            format!(
+                "{} {} is thinking... {:.1} s",
+                SPINNER_FRAMES[frame_index],
+                search.color,
+                elapsed.as_secs_f64()
+            )
+        }
+        None => app.game_message.clone().unwrap_or("No message".into()),
+    };
+    let game_message = Paragraph::new(message)
         .block(
             Block::bordered()
                 .border_type(BorderType::Rounded)
@@ -411,6 +464,7 @@ fn widget_message(frame: &mut Frame, app: &App, area: Rect) {
 /// # Move Display Format
 ///
 /// Regular moves: "Move X: [Player] played at [Position]. +[Discs] discs."
+/// Book moves: "Move X: [Player] played at [Position] (book). +[Discs] discs."
 /// Pass moves: "Move X: [Player] passed (no legal move)."
 ///
 /// # Data Processing
@@ -469,10 +523,11 @@ fn widget_history(frame: &mut Frame, app: &App, area: Rect) {
                     ));
                 } else {
                     ListItem::new(format!(
-                        "Move {}: {} played at {}. +{} discs.",
+                        "Move {}: {} played at {}{}. +{} discs.",
                         action.move_number,
                         action.player_turn,
                         action.coordinates.clone().unwrap_or("0".into()),
+                        if action.from_book { " (book)" } else { "" },
                         action.gained_discs.unwrap_or(0)
                     ))
                     .style(Style::default().fg(
@@ -496,6 +551,81 @@ fn widget_history(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
+/// Renders the statistics of the last AI search.
+///
+/// The panel shows the `SearchInfo` attached to the most recent move played by
+/// an AI, so the effect of a heuristic or depth change can be read directly
+/// from the game screen.
+///
+/// # Displayed Information
+///
+/// - **Move**: The AI move the statistics belong to
+/// - **Nodes and depth**: Positions visited (playouts for MCTS) and deepest ply reached
+/// - **Speed**: Search time in milliseconds and nodes per second
+/// - **Score and line**: Score of the played move and the principal variation
+/// - **Ponder hits**: With pondering enabled, how often the human played a pondered reply
+///
+/// Book moves and human moves carry no statistics and are skipped; "No AI
+/// search yet" is displayed until an AI has searched a position.
+///
+/// # Arguments
+///
+/// * `frame` - Ratatui frame for rendering the search widget
+/// * `app` - Application state containing game board with move history
+/// * `area` - Screen rectangle allocated for the search display
+///
+/// # Examples
+///
+/// ```rust
+/// // Called internally by game_screen() to show the last search
+/// widget_search(&mut frame, &app, search_area);
+/// ```
+fn widget_search(frame: &mut Frame, app: &App, area: Rect) {
+    let search_block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title("Search")
+        .title_alignment(Alignment::Center)
+        .padding(Padding::horizontal(1));
+
+    let last_search = app.board.as_ref().and_then(|board| {
+        board
+            .get_history()
+            .iter()
+            .rev()
+            .find_map(|action| action.search_info.as_ref().map(|info| (action, info)))
+    });
+
+    let mut lines = match last_search {
+        Some((action, info)) => vec![
+            format!(
+                "Move {}: {} at {}",
+                action.move_number,
+                action.player_turn,
+                action.coordinates.clone().unwrap_or("--".into())
+            ),
+            format!("Nodes: {} / Depth: {}", info.nodes, info.depth),
+            format!("Time: {} ms / {} nodes/s", info.time.as_millis(), info.nps),
+            format!("Score: {}", info.best_score),
+            format!("Line: {}", info.pv.join(" ")),
+        ],
+        None => vec!["No AI search yet".to_string()],
+    };
+    if app.pondering {
+        lines.push(format!("Ponder hits: {}", app.ponder_stats));
+    }
+
+    let search_paragraph =
+        Paragraph::new(lines.join("\n"))
+            .block(search_block)
+            .style(Style::default().fg(match last_search {
+                Some((action, _)) if action.color == Cell::Black => Color::Blue,
+                Some(_) => Color::Yellow,
+                None => Color::Reset,
+            }));
+
+    frame.render_widget(search_paragraph, area);
+}
+
 /// Renders a player score display with large, prominent number presentation.
 ///
 /// This function creates an individual score display widget for one player,
@@ -12,12 +12,10 @@ use ratatui::{
     Frame,
 };
 
-use crate::{
-    ai::{ai_type::AIType, heuristic::HeuristicType},
-    gui::{
-        app::App,
-        ui::{centered_rect, footer, widget_title},
-    },
+use crate::gui::{
+    app::App,
+    screen::ai_parameters::ai_parameter_items,
+    ui::{centered_rect, footer, widget_title},
 };
 
 /// Renders the Human vs AI configuration screen.
@@ -38,18 +36,25 @@ use crate::{
 /// # AI Configuration Options
 ///
 /// Players can configure the following AI parameters:
-/// - **AI Type**: Choose algorithm (MinMax, Alpha-Beta, Q-Learning)
-/// - **Search Depth**: Set analysis depth for tree-based algorithms
+/// - **AI Type**: Choose algorithm (MinMax, Alpha-Beta, Q-Learning, MCTS)
+/// - **Search Depth**: Set analysis depth (playouts or milliseconds per move for MCTS)
 /// - **Heuristic Type**: Select evaluation function approach
 /// - **Heuristic Matrix**: Choose strategic focus for position evaluation
+/// - **Eval Profile**: Evaluation profile of the Weighted heuristic
 /// - **Multi-threading**: Enable performance optimization where applicable
+/// - **Playouts**: Random or heuristic-guided simulations (MCTS only)
+/// - **Search Budget**: Iterations or time per move (MCTS only)
+/// - **Exploration**: Constant C of the UCT formula (MCTS only)
+/// - **Opening Book**: Off, best book move or random good book move
+/// - **Threads**: Number of search workers (search AIs only)
+/// - **Pondering**: Let the AI think during the human's turn
 ///
 /// # Dynamic UI Behavior
 ///
 /// The interface adapts based on selected AI type:
 /// - **Q-Learning**: Depth and heuristic options are disabled (grayed out)
 /// - **MinMax/Alpha-Beta**: All options available, multi-threading for MinMax only
-/// - **Matrix heuristics**: Disabled for Absolute and Mobility heuristic types
+/// - **Matrix heuristics**: Disabled for heuristic types that do not read the matrix
 ///
 /// # Visual Feedback
 ///
@@ -100,72 +105,15 @@ pub fn human_vs_ai_screen(frame: &mut Frame, app: &mut App) {
 
     widget_title(frame, app, chunks[0]);
 
-    let is_qlearning = app.player_2.as_ref().unwrap().get_ai_type().unwrap() == AIType::QLearning;
-    let can_use_double_threading =
-        app.player_2.as_ref().unwrap().get_ai_type().unwrap() == AIType::MinMax;
-    let heuristic_dont_use_matrix = app.player_1.as_ref().unwrap().get_heuristic()
-        == HeuristicType::Absolute
-        || app.player_2.as_ref().unwrap().get_heuristic() == HeuristicType::Mobility;
+    let mut items = ai_parameter_items(app.player_2.as_deref().unwrap(), "");
+    items.push(Span::from(format!(
+        "{:<30}{:>20}",
+        "Pondering",
+        format!("< {} >", app.pondering)
+    )));
+    items.push(Span::from(format!("{:<50}", "Play")));
 
-    let items = [
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "AI Type",
-            format!(
-                "< {} >",
-                app.player_2.as_ref().unwrap().get_ai_type().unwrap()
-            )
-        )),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "Depth of tree",
-            format!("< {} >", app.player_2.as_ref().unwrap().get_depth())
-        ))
-        .style(if is_qlearning {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "Heuristic Type",
-            format!("< {} >", app.player_2.as_ref().unwrap().get_heuristic())
-        ))
-        .style(if is_qlearning {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "Matrix Heuristic",
-            format!(
-                "< {} >",
-                app.player_2.as_ref().unwrap().get_heuristic_matrix()
-            )
-        ))
-        .style(if is_qlearning || !heuristic_dont_use_matrix {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!(
-            "{:<30}{:>20}",
-            "Double Threading",
-            format!(
-                "< {} >",
-                app.player_1.as_ref().unwrap().get_double_threading()
-            )
-        ))
-        .style(if !can_use_double_threading {
-            Style::default().fg(Color::DarkGray)
-        } else {
-            Style::default()
-        }),
-        Span::from(format!("{:<50}", "Play")),
-    ];
-
-    let layout = centered_rect(60, 10, chunks[1]);
+    let layout = centered_rect(60, items.len() as u16 + 4, chunks[1]);
 
     let list = List::new(items)
         .block(
@@ -38,6 +38,7 @@ use crate::gui::{
 /// - **Human vs AI**: Single-player mode against computer opponent
 /// - **AI vs AI**: Automated game between two AI players
 /// - **Q-Learning Training**: AI training and learning interface
+/// - **Matrix Editor**: Heuristic matrices drawn as a heatmap and edited
 ///
 /// # Visual Design
 ///
@@ -61,7 +62,7 @@ use crate::gui::{
 /// # Layout Behavior
 ///
 /// The layout uses flexible constraints to maintain proper spacing:
-/// - Fixed heights for title (8 lines), menu (8 lines), and footer (1 line)
+/// - Fixed heights for title (8 lines), menu (9 lines), and footer (1 line)
 /// - Flexible fill areas provide responsive spacing between sections
 /// - Center flex alignment ensures optimal visual balance
 ///
@@ -80,7 +81,7 @@ pub fn main_screen(frame: &mut Frame, app: &mut App) {
         .constraints([
             Constraint::Length(8), // au moins 1 ligne de hauteur pour la liste, prend plus si possible
             Constraint::Fill(1),
-            Constraint::Length(8), // au moins 1 ligne de hauteur pour la liste, prend plus si possible
+            Constraint::Length(9), // au moins 1 ligne de hauteur pour la liste, prend plus si possible
             Constraint::Fill(1),
             Constraint::Length(1), // 3 lignes FIXES de hauteur pour le footer
         ])
@@ -107,11 +108,12 @@ pub fn main_screen(frame: &mut Frame, app: &mut App) {
 ///
 /// # Menu Options
 ///
-/// The menu displays four primary game modes:
+/// The menu displays the four game modes and the matrix editor:
 /// - **"Human vs Human"**: Local multiplayer for two human players
 /// - **"Human vs AI"**: Single-player mode against computer opponent
 /// - **"AI vs AI"**: Automated match between two AI players
 /// - **"Q-Learning Training"**: AI training and development interface
+/// - **"Matrix Editor"**: Heuristic matrix editing
 ///
 /// # Visual Layout
 ///
@@ -159,6 +161,7 @@ fn widget_menu(frame: &mut Frame, app: &mut App, area: Rect) {
         "Human vs AI",
         "AI vs AI",
         "Q-Learning Training",
+        "Matrix Editor",
     ];
 
     let middle_layout = Layout::default()
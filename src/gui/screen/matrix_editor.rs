@@ -0,0 +1,212 @@
+//! Heuristic matrix editor screen.
+//!
+//! This module draws a heuristic matrix as a heatmap over the board, the
+//! weight of the selected square, and the matrix files that could not be
+//! loaded. Positive weights are drawn in green and negative ones in red,
+//! brighter as they get further from zero.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    ai::heuristic_matrix::{matrix_errors, AIHeuristicMatrix},
+    consts::SIZE,
+    gui::{app::App, ui::footer},
+};
+
+/// Renders the matrix editor screen.
+///
+/// # Screen Layout
+///
+/// - **Left**: The matrix as a heatmap, on the same grid as the game board
+/// - **Right top**: Matrix name, selected square and its weight, save status
+/// - **Right bottom**: Matrix files rejected when loaded, with the reason
+/// - **Footer**: Key bindings
+///
+/// # Arguments
+///
+/// * `frame` - Ratatui frame for rendering widgets to the terminal
+/// * `app` - Application state holding the editor
+pub fn matrix_editor_screen(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(1)])
+        .flex(Flex::Center)
+        .split(frame.area());
+
+    let main_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(66), Constraint::Percentage(40)])
+        .split(chunks[0]);
+
+    let right_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(11), Constraint::Fill(1)])
+        .split(main_area[1]);
+
+    widget_heatmap(frame, app, main_area[0]);
+
+    widget_details(frame, app, right_area[0]);
+
+    widget_errors(frame, right_area[1]);
+
+    footer(
+        frame,
+        app,
+        chunks[1],
+        " (↑↓←→) to choose / (+-) ±1 / (<>) ±10 / (TAB) next matrix / (s) to save / (q) to quit ",
+    );
+}
+
+/// Returns the heatmap color of a weight.
+///
+/// # Arguments
+///
+/// * `value` - The weight of the square
+/// * `max` - The largest absolute weight of the matrix, at least 1
+fn heat_color(value: isize, max: isize) -> Color {
+    let intensity = (value.abs() as f64 / max as f64).min(1.0);
+    let strong = (40.0 + 180.0 * intensity) as u8;
+    let weak = (40.0 * (1.0 - intensity)) as u8;
+    if value >= 0 {
+        Color::Rgb(weak, strong, weak)
+    } else {
+        Color::Rgb(strong, weak, weak)
+    }
+}
+
+/// Renders the matrix as a heatmap on a board-shaped grid.
+///
+/// The grid has the same labels and cell sizes as the game board, with the
+/// weight written in every square. The selected square is drawn reversed.
+fn widget_heatmap(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(editor) = &app.matrix_editor else {
+        return;
+    };
+    let title = format!(
+        " {}{} ",
+        editor.matrix,
+        if editor.modified { " (modified)" } else { "" }
+    );
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .title(title)
+        .title_alignment(Alignment::Center);
+    frame.render_widget(block, area);
+
+    let horizontal_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(58),
+            Constraint::Fill(1),
+        ])
+        .split(area);
+    let grid_area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(27),
+            Constraint::Fill(1),
+        ])
+        .split(horizontal_area[1]);
+
+    let max = editor
+        .values
+        .iter()
+        .flatten()
+        .map(|value| value.abs())
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let horizontal = Layout::horizontal((0..SIZE + 1).map(|_| Constraint::Length(4))).spacing(2);
+    let vertical = Layout::vertical((0..SIZE + 1).map(|_| Constraint::Length(2))).spacing(1);
+    let rows = vertical.split(grid_area[1]);
+    let cells = rows.iter().flat_map(|&row| horizontal.split(row).to_vec());
+
+    for (i, cell) in cells.enumerate() {
+        let (row, col) = (i / 9, i % 9);
+        let widget = match (row, col) {
+            (0, 0) => continue,
+            (0, col) => Paragraph::new(
+                Span::raw(char::from_u32(col as u32 + 64).unwrap().to_string())
+                    .into_centered_line(),
+            ),
+            (row, 0) => Paragraph::new(Span::raw(row.to_string()).into_centered_line()),
+            (row, col) => {
+                let value = editor.values[row - 1][col - 1];
+                let style = Style::default()
+                    .bg(heat_color(value, max))
+                    .fg(Color::White)
+                    .bold();
+                let style = if app.selected_cell == Some((row - 1, col - 1)) {
+                    style.reversed()
+                } else {
+                    style
+                };
+                Paragraph::new(value.to_string())
+                    .alignment(Alignment::Center)
+                    .style(style)
+            }
+        };
+        frame.render_widget(widget, cell);
+    }
+}
+
+/// Renders the selected square, its weight and the save status.
+fn widget_details(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(editor) = &app.matrix_editor else {
+        return;
+    };
+    let (row, col) = app.selected_cell.unwrap_or((0, 0));
+    let saving = match editor.matrix {
+        AIHeuristicMatrix::Custom(_) => "Saving overwrites the matrix file",
+        _ => "Built in: saving creates a new matrix",
+    };
+    let lines = vec![
+        Line::from(format!("Matrix: {}", editor.matrix)),
+        // Same labels as the grid: column letter, row number from 1
+        Line::from(format!(
+            "Square {}{}: {}",
+            (b'A' + col as u8) as char,
+            row + 1,
+            editor.values[row][col]
+        )),
+        Line::from("Edits apply to the symmetric squares too"),
+        Line::from(saving),
+        Line::from(""),
+        Line::from(editor.message.clone().unwrap_or_default()),
+    ];
+    let details = Paragraph::new(lines).block(
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Matrix")
+            .padding(Padding::uniform(1)),
+    );
+    frame.render_widget(details, area);
+}
+
+/// Renders the matrix files that were rejected when loaded.
+fn widget_errors(frame: &mut Frame, area: Rect) {
+    let errors = matrix_errors();
+    let lines: Vec<Line> = if errors.is_empty() {
+        vec![Line::from("Every matrix file is valid")]
+    } else {
+        errors
+            .into_iter()
+            .map(|error| Line::from(error).fg(Color::Red))
+            .collect()
+    };
+    let errors = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title("Matrix files")
+            .padding(Padding::uniform(1)),
+    );
+    frame.render_widget(errors, area);
+}
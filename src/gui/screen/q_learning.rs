@@ -5,6 +5,8 @@
 //! and monitor the reinforcement learning process for developing improved
 //! AI strategies through self-play and experimentation.
 
+use std::path::Path;
+
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Flex, Layout},
     style::{Color, Style, Stylize},
@@ -14,7 +16,7 @@ use ratatui::{
 };
 
 use crate::{
-    ai::heuristic::HeuristicType,
+    ai::algo::qlearning::{EpsilonSchedule, UpdateRule},
     game::player::Player,
     gui::{
         app::App,
@@ -44,6 +46,12 @@ use crate::{
 /// - **Max steps per epoch**: Maximum moves allowed in each training game
 /// - **Heuristic Type**: Evaluation function for position assessment during learning
 /// - **Matrix Heuristic**: Strategic focus matrix for position evaluation
+/// - **Learning rate / Discount factor**: Weight of new experiences and of future rewards
+/// - **Initial epsilon / Epsilon floor**: Exploration rate at the start and at the end
+/// - **Epsilon schedule / Epsilon decay**: How exploration decreases between them
+/// - **Training opponent / Opponent depth**: Who the AI plays against while learning
+/// - **Update rule / Trace decay**: How the values are updated after each move
+/// - **Color / Output name**: Colour trained for and name of the Q-table written in `Q_TABLES_DIR`
 ///
 /// # Learning Configuration
 ///
@@ -56,7 +64,7 @@ use crate::{
 /// # Dynamic UI Behavior
 ///
 /// The interface adapts based on selected heuristic type:
-/// - **Matrix heuristics**: Disabled (grayed out) for Absolute and Mobility types
+/// - **Matrix heuristics**: Disabled (grayed out) for types that do not read the matrix
 /// - **Parameter validation**: Ensures compatible combinations of settings
 /// - **Visual feedback**: Unavailable options shown in dark gray
 ///
@@ -82,7 +90,7 @@ use crate::{
 ///
 /// # Layout Specifications
 ///
-/// - **Configuration area**: Centered rectangle (60% width, 8 rows height)
+/// - **Configuration area**: Centered rectangle (60% width, 22 rows height)
 /// - **Responsive design**: Adapts to various terminal dimensions
 /// - **Visual hierarchy**: Clear separation between sections
 ///
@@ -109,9 +117,16 @@ pub fn q_learning_parameters_screen(frame: &mut Frame, app: &mut App) {
 
     widget_title(frame, app, chunks[0]);
 
-    let heuristic_dont_use_matrix = app.qlearning_parameters.as_ref().unwrap().get_heuristic()
-        == HeuristicType::Absolute
-        || app.qlearning_parameters.as_ref().unwrap().get_heuristic() == HeuristicType::Mobility;
+    let heuristic_dont_use_matrix = !app
+        .qlearning_parameters
+        .as_ref()
+        .unwrap()
+        .get_heuristic()
+        .uses_matrix();
+
+    let qlearning = app.qlearning_parameters.as_ref().unwrap();
+    let hyperparameters = qlearning.get_hyperparameters().clone();
+    let decay_unused = hyperparameters.epsilon_schedule != EpsilonSchedule::Exponential;
 
     let items = [
         Span::from(format!(
@@ -154,10 +169,88 @@ pub fn q_learning_parameters_screen(frame: &mut Frame, app: &mut App) {
         } else {
             Style::default()
         }),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Learning rate",
+            format!("< {:.2} >", hyperparameters.learning_rate)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Discount factor",
+            format!("< {:.2} >", hyperparameters.discount)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Initial epsilon",
+            format!("< {:.2} >", hyperparameters.epsilon)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Epsilon floor",
+            format!("< {:.2} >", hyperparameters.epsilon_floor)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Epsilon schedule",
+            format!("< {} >", hyperparameters.epsilon_schedule)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Epsilon decay",
+            format!("< {:.4} >", hyperparameters.epsilon_decay)
+        ))
+        .style(if decay_unused {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default()
+        }),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Training opponent",
+            format!("< {} >", hyperparameters.opponent)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Opponent depth",
+            format!("< {} >", hyperparameters.opponent_depth)
+        ))
+        .style(if hyperparameters.opponent.uses_depth() {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Update rule",
+            format!("< {} >", hyperparameters.update_rule)
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Trace decay",
+            format!("< {:.2} >", hyperparameters.trace_decay)
+        ))
+        .style(if hyperparameters.update_rule == UpdateRule::TdLambda {
+            Style::default()
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Color",
+            format!("< {} >", qlearning.get_color())
+        )),
+        Span::from(format!(
+            "{:<30}{:>20}",
+            "Output name",
+            Path::new(&qlearning.get_q_table_file().unwrap())
+                .file_name()
+                .map_or(String::new(), |name| name.to_string_lossy().into_owned())
+        )),
         Span::from(format!("{:<50}", "Start training")),
+        Span::from(format!("{:<50}", "Continue training")),
     ];
 
-    let layout = centered_rect(60, 9, chunks[1]);
+    let layout = centered_rect(60, 22, chunks[1]);
 
     let list = List::new(items)
         .block(
@@ -186,6 +279,6 @@ pub fn q_learning_parameters_screen(frame: &mut Frame, app: &mut App) {
         frame,
         app,
         chunks[3],
-        " (↑↓←→) to choose / (ENTER) to validate / (q) to return to main menu ",
+        " (↑↓←→) to choose / (a-z 0-9 _ -) to type the output name / (ENTER) to validate / (q) to return to main menu ",
     );
 }
@@ -1,24 +1,31 @@
 //! Q-Learning training progress screen for the Othello game GUI.
 //!
 //! This module provides the visual interface for displaying Q-Learning AI training progress.
-//! It shows a progress bar, completion percentage, status messages, and handles
-//! real-time updates from the training thread through channel communication.
+//! It shows a progress bar, the latest training metrics and evaluation, charts
+//! of the average reward, win rate and evaluation win rates, sparklines of
+//! epsilon, Q-table size and speed, status
+//! messages, and handles real-time updates from the training thread through
+//! channel communication.
 
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Flex, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
+    symbols::Marker,
     text::Line,
-    widgets::{Block, Borders, Gauge},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Gauge, GraphType, Sparkline},
     Frame,
 };
 
-use crate::gui::{app::App, ui::footer};
+use crate::{
+    ai::training_metrics::{TrainingMetrics, EVALUATION_OPPONENTS},
+    gui::{app::App, ui::footer},
+};
 
 /// Renders the Q-Learning training progress screen.
 ///
 /// This function displays a real-time progress interface for Q-Learning AI training,
-/// including a visual progress bar, percentage completion, status messages, and
-/// navigation instructions. It communicates with the training thread through
+/// including a visual progress bar, percentage completion, training metrics,
+/// status messages, and navigation instructions. It communicates with the training thread through
 /// a channel to receive progress updates.
 ///
 /// # Arguments
@@ -29,25 +36,35 @@ use crate::gui::{app::App, ui::footer};
 /// # Layout Structure
 ///
 /// The screen is organized in a vertical layout with:
-/// - Fill space (top padding)
-/// - Progress bar section (5 lines)
+/// - Progress bar section (3 lines)
+/// - Latest metrics line (1 line)
+/// - Latest evaluation line (1 line)
+/// - Charts of the average reward, win rate and evaluation win rates (remaining space)
+/// - Sparklines of epsilon, Q-table size and epochs per second (5 lines)
 /// - Status message line (1 line)
-/// - Fill space (bottom padding)
 /// - Footer with navigation instructions (1 line)
 ///
 /// # Progress Communication
 ///
-/// The function uses `app.qlearning_channel` to receive training progress:
-/// - Uses `try_recv()` for non-blocking progress updates
-/// - Updates `app.qlearning_loading` with the latest progress value
+/// The function uses `app.qlearning_channel` to receive training metrics:
+/// - Uses `try_iter()` for non-blocking updates, taking every pending epoch
+/// - Appends them to `app.qlearning_metrics`, whose last entry gives the progress
 /// - Handles channel communication errors gracefully
 ///
 /// # Visual Elements
 ///
 /// * **Progress Bar**: Yellow gauge with percentage label showing training completion
+/// * **Metrics**: Epoch, epsilon, average reward and win rate over the last
+///   games, Q-table size and training speed
+/// * **Evaluation**: Win, draw and loss rates of the latest evaluation games
+/// * **Charts**: Average reward, win rate and evaluation win rates over the epochs
+/// * **Sparklines**: Latest epsilon, Q-table size and epochs per second
 /// * **Status Messages**:
 ///   - "Training in progress, please wait..." during training
-///   - "Training complete! The Q table has been saved as 'q_table_player_1.json'." when finished
+///   - "Training complete! The Q table has been saved as '<file>' with its hyperparameters." when finished
+///   - "Stopping after the current epoch..." then "Training stopped! ..." after `s`
+///   - The last problem of the training (failed evaluation game, metrics log
+///     error), in yellow after the message
 /// * **Footer**: Navigation instructions for returning to main menu
 ///
 /// # Examples
@@ -61,46 +78,321 @@ pub fn q_learning_loading_screen(frame: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Fill(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(8),
             Constraint::Length(5),
             Constraint::Length(1),
-            Constraint::Fill(1),
             Constraint::Length(1),
         ])
-        .flex(Flex::Center)
         .split(frame.area());
 
-    // use try_recv to get the current progress
+    // use try_iter to take every metrics sent since the last frame
     if let Some(rx) = &app.qlearning_channel {
-        if let Ok(progress) = rx.try_recv() {
-            app.qlearning_loading = Some(progress);
-        }
+        app.qlearning_metrics.extend(rx.try_iter());
     }
+    let latest = app.qlearning_metrics.last();
+    let progress = latest.map_or(0.0, TrainingMetrics::progress);
 
-    let label = format!(
-        "{:.1}%",
-        app.qlearning_loading.unwrap_or(0.0) as f64 * 100.0
-    );
+    let label = format!("{:.1}%", progress * 100.0);
     let gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL))
         .gauge_style(Style::default().fg(Color::Yellow).bg(Color::Black))
-        .ratio(app.qlearning_loading.unwrap_or(0.0) as f64)
+        .ratio(progress.clamp(0.0, 1.0))
         .label(label);
-    frame.render_widget(gauge, chunks[1]);
+    frame.render_widget(gauge, chunks[0]);
+
+    if let Some(metrics) = latest {
+        let summary = format!(
+            "Epoch {}/{}  |  epsilon {:.3}  |  avg reward {:.1}  |  win rate {:.1}%  |  {} states  |  {:.1} epochs/s",
+            metrics.epoch,
+            metrics.epochs,
+            metrics.epsilon,
+            metrics.average_reward,
+            metrics.win_rate * 100.0,
+            metrics.q_table_size,
+            metrics.epochs_per_second
+        );
+        frame.render_widget(
+            Line::from(summary)
+                .style(Style::default().fg(Color::White))
+                .alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+    let last_evaluation = app
+        .qlearning_metrics
+        .iter()
+        .rev()
+        .find_map(|metrics| Some((metrics.epoch, metrics.evaluation.as_ref()?)));
+    if let Some((epoch, results)) = last_evaluation {
+        let results: Vec<String> = results
+            .iter()
+            .map(|result| {
+                format!(
+                    "{} W/D/L {:.0}/{:.0}/{:.0}%",
+                    result.opponent,
+                    result.win_rate() * 100.0,
+                    result.draw_rate() * 100.0,
+                    result.loss_rate() * 100.0
+                )
+            })
+            .collect();
+        frame.render_widget(
+            Line::from(format!(
+                "Evaluation at epoch {}: {}",
+                epoch,
+                results.join("  |  ")
+            ))
+            .style(Style::default().fg(Color::White))
+            .alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
 
-    let mut message = "Training in progress, please wait...";
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(chunks[3]);
+    metric_chart(
+        frame,
+        charts[0],
+        &app.qlearning_metrics,
+        "Average reward",
+        Color::Cyan,
+        |metrics| metrics.average_reward,
+    );
+    metric_chart(
+        frame,
+        charts[1],
+        &app.qlearning_metrics,
+        "Win rate (%)",
+        Color::Green,
+        |metrics| metrics.win_rate * 100.0,
+    );
+    evaluation_chart(frame, charts[2], &app.qlearning_metrics);
 
-    if let Some(progress) = app.qlearning_loading {
-        if progress >= 1.0 {
-            message = "Training complete! The Q table has been saved as 'q_table_player_1.json'.";
-        }
+    let sparklines = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(chunks[4]);
+    metric_sparkline(
+        frame,
+        sparklines[0],
+        &app.qlearning_metrics,
+        "Epsilon",
+        Color::Magenta,
+        |metrics| (metrics.epsilon * 1000.0) as u64,
+    );
+    metric_sparkline(
+        frame,
+        sparklines[1],
+        &app.qlearning_metrics,
+        "Q-table size",
+        Color::Blue,
+        |metrics| metrics.q_table_size as u64,
+    );
+    metric_sparkline(
+        frame,
+        sparklines[2],
+        &app.qlearning_metrics,
+        "Epochs/s",
+        Color::Yellow,
+        |metrics| metrics.epochs_per_second.round() as u64,
+    );
+
+    let mut message = "Training in progress, please wait...".to_string();
+
+    let stopped = app
+        .qlearning_cancel
+        .as_ref()
+        .is_some_and(|cancel| cancel.is_cancelled());
+    let finished = app
+        .qlearning_handle
+        .as_ref()
+        .is_some_and(|handle| handle.is_finished());
+    if stopped {
+        message = if finished {
+            format!(
+                "Training stopped! The Q table has been saved as '{}', select Continue training to resume it.",
+                app.qlearning_output.as_deref().unwrap_or_default()
+            )
+        } else {
+            "Stopping after the current epoch...".to_string()
+        };
+    } else if progress >= 1.0 {
+        message = format!(
+            "Training complete! The Q table has been saved as '{}' with its hyperparameters.",
+            app.qlearning_output.as_deref().unwrap_or_default()
+        );
     }
 
+    // Problems of the training are shown until the end, after the message
+    let status = app
+        .qlearning_metrics
+        .iter()
+        .rev()
+        .find_map(|metrics| metrics.status.as_ref());
+    let color = match status {
+        Some(status) => {
+            message = format!("{}  |  {}", message, status);
+            Color::Yellow
+        }
+        None => Color::White,
+    };
+
     let message_widget = Line::from(message)
-        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .style(Style::default().fg(color).bg(Color::Black))
         .alignment(Alignment::Center);
 
-    frame.render_widget(message_widget, chunks[2]);
+    frame.render_widget(message_widget, chunks[5]);
+
+    footer(
+        frame,
+        app,
+        chunks[6],
+        "(s) to stop and save / (q) to return to main menu ",
+    );
+}
+
+/// Draws a line chart of one metric over the epochs.
+///
+/// Long trainings are thinned out to about two points per column, which is
+/// all the braille markers can show.
+fn metric_chart(
+    frame: &mut Frame,
+    area: Rect,
+    history: &[TrainingMetrics],
+    title: &str,
+    color: Color,
+    value: impl Fn(&TrainingMetrics) -> f64,
+) {
+    let stride = (history.len() / (area.width.max(1) as usize * 2)).max(1);
+    let points: Vec<(f64, f64)> = history
+        .iter()
+        .step_by(stride)
+        .chain(history.last())
+        .map(|metrics| (metrics.epoch as f64, value(metrics)))
+        .collect();
+    let (x_min, x_max) = points
+        .first()
+        .zip(points.last())
+        .map_or((0.0, 1.0), |(first, last)| {
+            (first.0, last.0.max(first.0 + 1.0))
+        });
+    let (y_min, y_max) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), point| {
+            (min.min(point.1), max.max(point.1))
+        });
+    let (y_min, y_max) = if y_min.is_finite() {
+        (y_min, y_max.max(y_min + 1.0))
+    } else {
+        (0.0, 1.0)
+    };
+
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(&points);
+    let chart = Chart::new(vec![dataset])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string()),
+        )
+        .x_axis(
+            Axis::default()
+                .title("Epoch")
+                .bounds([x_min, x_max])
+                .labels([format!("{}", x_min), format!("{}", x_max)]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([y_min, y_max])
+                .labels([format!("{:.1}", y_min), format!("{:.1}", y_max)]),
+        );
+    frame.render_widget(chart, area);
+}
+
+/// Draws the win rates of the evaluations over the epochs, one line per
+/// evaluation opponent.
+fn evaluation_chart(frame: &mut Frame, area: Rect, history: &[TrainingMetrics]) {
+    let colors = [Color::Green, Color::Yellow, Color::LightRed, Color::Red];
+    let points: Vec<Vec<(f64, f64)>> = EVALUATION_OPPONENTS
+        .iter()
+        .map(|opponent| {
+            history
+                .iter()
+                .filter_map(|metrics| {
+                    let result = metrics
+                        .evaluation
+                        .iter()
+                        .flatten()
+                        .find(|result| result.opponent == *opponent)?;
+                    Some((metrics.epoch as f64, result.win_rate() * 100.0))
+                })
+                .collect()
+        })
+        .collect();
+    let x_max = points
+        .iter()
+        .flatten()
+        .map(|point| point.0)
+        .fold(1.0, f64::max);
+
+    let datasets = EVALUATION_OPPONENTS
+        .iter()
+        .zip(&points)
+        .zip(colors)
+        .map(|((opponent, points), color)| {
+            Dataset::default()
+                .name(opponent.to_string())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(points)
+        })
+        .collect();
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Evaluation win rate (%)"),
+        )
+        .x_axis(
+            Axis::default()
+                .title("Epoch")
+                .bounds([0.0, x_max])
+                .labels(["0".to_string(), format!("{}", x_max)]),
+        )
+        .y_axis(Axis::default().bounds([0.0, 100.0]).labels(["0", "100"]));
+    frame.render_widget(chart, area);
+}
 
-    footer(frame, app, chunks[4], "(q) to return to main menu ");
+/// Draws a sparkline of the latest values of one metric, one per column.
+fn metric_sparkline(
+    frame: &mut Frame,
+    area: Rect,
+    history: &[TrainingMetrics],
+    title: &str,
+    color: Color,
+    value: impl Fn(&TrainingMetrics) -> u64,
+) {
+    let columns = area.width.saturating_sub(2) as usize;
+    let data: Vec<u64> = history[history.len().saturating_sub(columns)..]
+        .iter()
+        .map(value)
+        .collect();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title.to_string()),
+        )
+        .style(Style::default().fg(color))
+        .data(&data);
+    frame.render_widget(sparkline, area);
 }
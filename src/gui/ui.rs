@@ -18,7 +18,7 @@ use crate::gui::{
     app::{App, CurrentScreen},
     screen::{
         ai_vs_ai::ai_vs_ai_screen, exit::exit_screen, game::game_screen,
-        human_vs_ai::human_vs_ai_screen, main::main_screen,
+        human_vs_ai::human_vs_ai_screen, main::main_screen, matrix_editor::matrix_editor_screen,
         q_learning::q_learning_parameters_screen, q_learning_loading::q_learning_loading_screen,
         tutorial::tutorial_screen,
     },
@@ -203,6 +203,9 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         CurrentScreen::QLearningLoading => {
             q_learning_loading_screen(frame, app);
         }
+        CurrentScreen::MatrixEditor => {
+            matrix_editor_screen(frame, app);
+        }
     }
 }
 
@@ -197,6 +197,8 @@ impl Player for Human {
                     color: self.get_color(),
                     player_turn: board.get_player_turn(),
                     move_number: board.get_turn_number(),
+                    from_book: false,
+                    search_info: None,
                 }),
                 Err(e) => Err(format!("Error playing move: {}", e)),
             }
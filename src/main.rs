@@ -7,7 +7,7 @@
 //! # Features
 //!
 //! * **Multiple Game Modes**: Human vs Human, Human vs AI, AI vs AI, Q-Learning training
-//! * **Advanced AI Algorithms**: MinMax, Alpha-Beta pruning, Q-Learning reinforcement learning
+//! * **Advanced AI Algorithms**: MinMax, Alpha-Beta pruning, Monte Carlo Tree Search, Q-Learning reinforcement learning
 //! * **Terminal UI**: Rich interactive terminal interface using Ratatui framework
 //! * **Configurable AI**: Adjustable search depth, heuristics, and training parameters
 //! * **Real-time Training**: Background Q-Learning with progress visualization
@@ -24,6 +24,7 @@
 //!
 //! * **MinMax**: Classic minimax algorithm with optional multithreading
 //! * **Alpha-Beta**: Optimized minimax with alpha-beta pruning
+//! * **MCTS**: Monte Carlo Tree Search with UCT selection and parallel root search
 //! * **Q-Learning**: Reinforcement learning with configurable parameters
 //!
 //! # Usage
@@ -36,6 +37,12 @@
 //! Navigate using arrow keys and Enter to select options. Each game mode
 //! provides its own configuration interface before starting play.
 //!
+//! Command line tools (see the `cli` module) run instead of the interface
+//! when a command is given:
+//! ```bash
+//! cargo run --release -- arena --first mcts --second alphabeta --games 10
+//! ```
+//!
 //! # Author
 //!
 //! SALTEL Baptiste - July 2025
@@ -52,6 +59,7 @@
 //  ===================================================================
 
 mod ai;
+mod cli;
 mod consts;
 mod game;
 mod gui;
@@ -64,7 +72,7 @@ use crate::{
         control::{
             ai_vs_ai::ai_vs_ai_control, exit::exit_control, game::game_control,
             human_vs_ai::human_vs_ai_control, main::main_control,
-            q_learning::q_learning_parameters_control,
+            matrix_editor::matrix_editor_control, q_learning::q_learning_parameters_control,
             q_learning_loading::q_learning_loading_control, tutorial::tutorial_control,
         },
         ui::ui,
@@ -113,10 +121,16 @@ use std::{error::Error, io, time::Duration};
 /// - Disables mouse capture
 /// - Shows cursor
 ///
+/// # Command Line
+///
+/// When arguments are given, the matching command line tool runs instead
+/// of the terminal interface (see `cli::run`).
+///
 /// # Examples
 ///
 /// ```bash
 /// cargo run
+/// cargo run -- help
 /// ```
 ///
 /// # Returns
@@ -124,6 +138,15 @@ use std::{error::Error, io, time::Duration};
 /// * `Ok(())` - Application completed successfully
 /// * `Err(Box<dyn Error>)` - Terminal or application error occurred
 fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(e) = cli::run(&args) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
 
@@ -175,6 +198,9 @@ fn main() -> Result<(), Box<dyn Error>> {
 /// - Determines current player based on board state (Black/White)
 /// - Identifies if current player is human or AI
 /// - Automatically triggers AI moves when it's an AI player's turn
+/// - Runs AI searches on a background thread, so the screen keeps redrawing
+///   (timer, spinner) and 'q' can cancel them
+/// - Lets the AI ponder during the human's turn when pondering is enabled
 /// - Prevents moves when game is over
 ///
 /// # Event Handling
@@ -186,6 +212,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 /// - **Exit**: Confirmation dialog
 /// - **Configuration Screens**: Parameter adjustment for AI settings
 /// - **Training**: Q-Learning progress and control
+/// - **Matrix Editor**: Heuristic matrix editing
 ///
 /// # Input Processing
 ///
@@ -217,6 +244,8 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
         // Handle player turns during active gameplay
         match app.current_screen {
             CurrentScreen::Game => {
+                // Show the overlay analysis once it is done
+                app.poll_analysis();
                 // Determine current player based on board state
                 match app.board.as_ref().unwrap().get_player_turn() {
                     Cell::Black => {
@@ -229,12 +258,19 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                         player_turn = &None; // Invalid state
                     }
                 }
-                // Process AI moves automatically
-                if let Some(player) = player_turn {
-                    its_a_human_player = player.is_human();
-                    if !player.is_human() && !app.board.as_ref().unwrap().is_game_over() {
-                        app.gui_play_turn(); // AI makes move automatically
+                // Process AI moves automatically, in the background
+                match player_turn {
+                    Some(player) => {
+                        its_a_human_player = player.is_human();
+                        if !player.is_human() && !app.board.as_ref().unwrap().is_game_over() {
+                            app.play_ai_turn(); // AI starts searching its move
+                        } else if its_a_human_player {
+                            app.start_pondering(); // AI thinks on the human's time
+                        }
                     }
+                    // The AI to move is out of its slot while it searches
+                    None if app.ai_search.is_some() => app.play_ai_turn(),
+                    None => {}
                 }
             }
             _ => {} // Other screens don't need turn processing
@@ -264,6 +300,7 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     CurrentScreen::AIVsAI => ai_vs_ai_control(app, key),
                     CurrentScreen::QLearningParameters => q_learning_parameters_control(app, key),
                     CurrentScreen::QLearningLoading => q_learning_loading_control(app, key),
+                    CurrentScreen::MatrixEditor => matrix_editor_control(app, key),
                 }
             }
         }